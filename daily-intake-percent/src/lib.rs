@@ -0,0 +1,4 @@
+//! The daily values of nutrients, so they can be shared with the server.
+
+pub mod nutrients;
+mod pretty;
@@ -6,9 +6,9 @@ use std::io;
 #[macro_use]
 extern crate lazy_static;
 
-mod nutrients;
 mod pretty;
 
+use daily_intake_percent::nutrients;
 use pretty::pretty;
 
 macro_rules! do_while {
@@ -48,12 +48,12 @@ impl<'a> Default for AminoAcid<'a> {
     }
 }
 
-impl<'a> NutrientType for AminoAcid<'a> {
+impl<'a> NutrientType<'a> for AminoAcid<'a> {
     fn get_name(&self) -> &'static str {
         "Amino Acids"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![
             &self.tryptophan,
             &self.histidine,
@@ -77,7 +77,7 @@ impl<'a> NutrientType for AminoAcid<'a> {
         ]
     }
 
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![
             &mut self.tryptophan,
             &mut self.histidine,
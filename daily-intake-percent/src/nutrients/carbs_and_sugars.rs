@@ -24,12 +24,12 @@ impl<'a> Default for CarbsAndSugar<'a> {
     }
 }
 
-impl<'a> NutrientType for CarbsAndSugar<'a> {
+impl<'a> NutrientType<'a> for CarbsAndSugar<'a> {
     fn get_name(&self) -> &'static str {
         "Carbs and Sugars"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![
             &self.starch,
             &self.sucrose,
@@ -41,7 +41,7 @@ impl<'a> NutrientType for CarbsAndSugar<'a> {
         ]
     }
 
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![
             &mut self.starch,
             &mut self.sucrose,
@@ -14,16 +14,16 @@ impl<'a> Default for FattyAcid<'a> {
     }
 }
 
-impl<'a> NutrientType for FattyAcid<'a> {
+impl<'a> NutrientType<'a> for FattyAcid<'a> {
     fn get_name(&self) -> &'static str {
         "Fatty Acids"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![&self.omega_3s, &self.omega_6s]
     }
 
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![&mut self.omega_3s, &mut self.omega_6s]
     }
 }
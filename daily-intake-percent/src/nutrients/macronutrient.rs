@@ -14,7 +14,7 @@ pub struct Macronutrient<'a> {
 impl<'a> Default for Macronutrient<'a> {
     fn default() -> Self {
         Self {
-            calories: Nutrient::new("Calories", &["calories", "calorie", "cal"], "", 2000.0),
+            calories: Nutrient::new("Calories", &["calories", "calorie", "cal"], "kcal", 2000.0),
             fat: Nutrient::new("Fat", &["fat", "fats", "f"], "g", 78.0),
             saturated_fat: Nutrient::new(
                 "Saturated Fat",
@@ -48,12 +48,12 @@ impl<'a> Default for Macronutrient<'a> {
     }
 }
 
-impl<'a> NutrientType for Macronutrient<'a> {
+impl<'a> NutrientType<'a> for Macronutrient<'a> {
     fn get_name(&self) -> &'static str {
         "Macronutrients"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![
             &self.calories,
             &self.fat,
@@ -66,7 +66,7 @@ impl<'a> NutrientType for Macronutrient<'a> {
         ]
     }
 
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![
             &mut self.calories,
             &mut self.fat,
@@ -36,12 +36,12 @@ impl<'a> Default for Mineral<'a> {
     }
 }
 
-impl<'a> NutrientType for Mineral<'a> {
+impl<'a> NutrientType<'a> for Mineral<'a> {
     fn get_name(&self) -> &'static str {
         "Minerals"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![
             &self.calcium,
             &self.copper,
@@ -59,7 +59,7 @@ impl<'a> NutrientType for Mineral<'a> {
         ]
     }
 
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![
             &mut self.calcium,
             &mut self.copper,
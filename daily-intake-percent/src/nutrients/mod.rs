@@ -11,15 +11,15 @@ mod vitamin;
 
 /// A trait implemented on each nutrient category. Used by the Nutrients struct
 /// for dynamic dispatch.
-pub trait NutrientType {
+pub trait NutrientType<'a> {
     /// Returns the name of the nutrient category.
     fn get_name(&self) -> &str;
 
     /// Returns the nutrients in the category.
-    fn get_nutrients(&self) -> Vec<&Nutrient>;
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>>;
 
     /// Returns the mutable nutrients in the category.
-    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient>;
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>>;
 
     /// Displays a table of the nutrients in the category.
     /// Overriding this function isn't recommended as its default is sensible
@@ -84,7 +84,7 @@ pub trait NutrientType {
     }
 }
 
-impl fmt::Display for dyn NutrientType {
+impl<'a> fmt::Display for dyn NutrientType<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.display(0., f)
     }
@@ -101,7 +101,7 @@ pub struct Nutrients<'a> {
 }
 
 impl<'a> Nutrients<'a> {
-    pub fn get_nutrient_types(&self) -> Vec<&dyn NutrientType> {
+    pub fn get_nutrient_types(&self) -> Vec<&dyn NutrientType<'a>> {
         vec![
             &self.macros,
             &self.vitamins,
@@ -112,7 +112,7 @@ impl<'a> Nutrients<'a> {
         ]
     }
 
-    pub fn get_nutrient_types_mut(&mut self) -> Vec<&mut dyn NutrientType> {
+    pub fn get_nutrient_types_mut(&mut self) -> Vec<&mut dyn NutrientType<'a>> {
         vec![
             &mut self.macros,
             &mut self.vitamins,
@@ -123,7 +123,7 @@ impl<'a> Nutrients<'a> {
         ]
     }
 
-    pub fn get_nutrient(&self, alias: &str) -> Option<&Nutrient> {
+    pub fn get_nutrient(&self, alias: &str) -> Option<&Nutrient<'a>> {
         let alias = alias.to_lowercase();
         for nutrient_type in self.get_nutrient_types() {
             for nutrient in nutrient_type.get_nutrients() {
@@ -135,7 +135,7 @@ impl<'a> Nutrients<'a> {
         None
     }
 
-    pub fn get_nutrient_mut(&mut self, alias: &str) -> Option<&mut Nutrient> {
+    pub fn get_nutrient_mut(&mut self, alias: &str) -> Option<&mut Nutrient<'a>> {
         let alias = alias.to_lowercase();
         for nutrient_type in self.get_nutrient_types_mut() {
             for nutrient in nutrient_type.get_nutrients_mut() {
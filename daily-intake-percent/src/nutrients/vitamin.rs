@@ -37,7 +37,7 @@ impl<'a> Default for Vitamin<'a> {
             c: Nutrient::new("Vitamin C", &["c", "vit c", "vit c", "vc"], "mg", 90.0),
             d: Nutrient::new("Vitamin D", &["d", "vit d", "vit d", "vd"], "μg", 20.0),
             e: Nutrient::new("Vitamin E", &["e", "vit e", "vit e", "ve"], "mg", 15.0),
-            k: Nutrient::new("Vitamin K", &["k", "vit k", "vit k", "vk"], "mg", 120.0),
+            k: Nutrient::new("Vitamin K", &["k", "vit k", "vit k", "vk"], "μg", 120.0),
             choline: Nutrient::new(
                 "Choline",
                 &["vitamin choline", "vitcholine", "choli", "chl", "vch"],
@@ -71,12 +71,12 @@ impl<'a> Default for Vitamin<'a> {
     }
 }
 
-impl<'a> NutrientType for Vitamin<'a> {
+impl<'a> NutrientType<'a> for Vitamin<'a> {
     fn get_name(&self) -> &'static str {
         "Vitamins"
     }
 
-    fn get_nutrients(&self) -> Vec<&Nutrient> {
+    fn get_nutrients(&self) -> Vec<&Nutrient<'a>> {
         vec![
             &self.a,
             &self.b1,
@@ -95,7 +95,7 @@ impl<'a> NutrientType for Vitamin<'a> {
             &self.lutein_zeazanthin,
         ]
     }
-    fn get_nutrients_mut(&mut self) -> Vec<&'a mut Nutrient> {
+    fn get_nutrients_mut(&mut self) -> Vec<&mut Nutrient<'a>> {
         vec![
             &mut self.a,
             &mut self.b1,
@@ -1,6 +1,7 @@
 use crate::v1::utils::collection::{Collections, GetCollection};
-use crate::v1::utils::WeeklyRecipeGetter;
+use crate::v1::utils::{blob_store_from_env, WeeklyRecipeGetter};
 use actix_cors::Cors;
+use actix_files::Files;
 use actix_web::{web, App as ActixApp, HttpServer};
 use clap::{App as ClapApp, Arg};
 use mongodb::bson::doc;
@@ -53,15 +54,176 @@ async fn create_db_client(env_file: &str) -> Result<mongodb::Client, String> {
     )
     .await
     .map_err(|_| "Could not create text index on recipes collection".to_string())?;
-    // Create an index on the short field.
+    // Older versions created a non-unique index on the short field, which
+    // conflicts with the unique one below. It does not exist on new databases,
+    // so the error is ignored.
+    let _ = coll.drop_index("short_1", None).await;
+    // Create a unique index on the short field.
     coll.create_index(
         mongodb::IndexModel::builder()
             .keys(doc! { "short": 1 })
+            .options(
+                mongodb::options::IndexOptions::builder()
+                    .name("short_unique".to_string())
+                    .unique(true)
+                    .build(),
+            )
             .build(),
         None,
     )
     .await
-    .map_err(|_| "Could not create index on recipe short field".to_string())?;
+    .map_err(|_| "Could not create unique index on recipe short field. Check no two recipes have the same short".to_string())?;
+    // Create an index on the previous shorts, used to redirect old links.
+    coll.create_index(
+        mongodb::IndexModel::builder()
+            .keys(doc! { "previousShorts": 1 })
+            .build(),
+        None,
+    )
+    .await
+    .map_err(|_| "Could not create index on recipe previous shorts field".to_string())?;
+
+    // Create a unique index on the revisions collection, so each revision
+    // of a recipe can only be stored once.
+    client
+        .get_collection::<crate::v1::types::database::RecipeRevision>(Collections::Revisions)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "recipe": 1, "revision": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on revisions collection".to_string())?;
+
+    // Create a unique index on the quiz rewards in the points ledger, so a
+    // learner can only earn each reward of a recipe once. It replaces the
+    // index that only allowed one reward per recipe, which may not exist.
+    let points =
+        client.get_collection::<crate::v1::types::database::PointsEntry>(Collections::Points);
+    let _ = points.drop_index("quiz_reward_unique", None).await;
+    points
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1, "source.recipe": 1, "source.reward": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .name("quiz_reward_unique_per_reward".to_string())
+                        .unique(true)
+                        .partial_filter_expression(doc! { "source.type": "quiz" })
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create unique index on points collection".to_string())?;
+    // Create an index on the learner of each entry, used to list and sum
+    // their points.
+    points
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1, "date": -1 })
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on points collection".to_string())?;
+
+    // Create an index on the learners of accounts, used to find the account
+    // a learner belongs to.
+    client
+        .get_collection::<crate::v1::types::database::Account>(Collections::Accounts)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learners._id": 1 })
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on accounts collection".to_string())?;
+
+    // Create a unique index on badges, so each achievement can only be
+    // unlocked once by a learner.
+    client
+        .get_collection::<crate::v1::types::database::Badge>(Collections::Badges)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1, "achievement": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on badges collection".to_string())?;
+    // Create an index on the learner of each quiz attempt, used to check
+    // their progress towards achievements.
+    client
+        .get_collection::<crate::v1::types::database::QuizAttempt>(Collections::QuizAttempts)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1 })
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on quiz attempts collection".to_string())?;
+
+    // Create a unique index on favourites, so each recipe can only be saved
+    // once by a learner.
+    client
+        .get_collection::<crate::v1::types::database::Favourite>(Collections::Favourites)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1, "recipe": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on favourites collection".to_string())?;
+    // Create an index on the learner of each cook log entry, used to list
+    // what they cooked.
+    client
+        .get_collection::<crate::v1::types::database::CookLogEntry>(Collections::CookLog)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "learner": 1, "date": -1 })
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on cook log collection".to_string())?;
+    // Create a unique index on the families that cooked each recipe, so a
+    // family is only counted once.
+    client
+        .get_collection::<crate::v1::types::database::FamilyCook>(Collections::FamilyCooks)
+        .create_index(
+            mongodb::IndexModel::builder()
+                .keys(doc! { "recipe": 1, "account": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            None,
+        )
+        .await
+        .map_err(|_| "Could not create index on family cooks collection".to_string())?;
 
     Ok(client)
 }
@@ -105,6 +267,18 @@ async fn main() -> std::io::Result<()> {
             .help("The environment to run the server in. Valid modes are: development (dev, d), localproduction (localprod, lprod, lp), production (prod, p)")
             .takes_value(true)
         )
+        .arg(Arg::with_name("purge-deleted")
+            .long("purge-deleted")
+            .value_name("DAYS")
+            .help("Instead of starting the server, permanently removes recipes that were deleted more than DAYS days ago, then exits.")
+            .takes_value(true)
+        )
+        .arg(Arg::with_name("purge-uploads")
+            .long("purge-uploads")
+            .value_name("DAYS")
+            .help("Instead of starting the server, permanently removes images that were uploaded more than DAYS days ago and are not used, then exits.")
+            .takes_value(true)
+        )
         .get_matches();
 
     // Default to dev in debug mode, or prod in release mode.
@@ -165,7 +339,49 @@ async fn main() -> std::io::Result<()> {
         .expect("Could not ping MongoDB");
     println!("Connected to the database successfully.");
 
+    // Purge deleted recipes instead of starting the server, if requested.
+    if let Some(days) = matches.value_of("purge-deleted") {
+        let days = days
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Could not parse `--purge-deleted {}` as a u64", days));
+        let purged = v1::utils::purge_deleted_recipes(&client, days)
+            .await
+            .unwrap();
+        println!("Purged {} deleted recipes.", purged);
+        return Ok(());
+    }
+
+    // Get where uploaded files are stored. Uploads are disabled if it is
+    // not set up, rather than stopping the rest of the server.
+    let blob_store = match blob_store_from_env() {
+        Ok(blob_store) => Some(blob_store),
+        Err(e) => {
+            tracing::error!(
+                "Uploads are disabled, as the blob store is not set up: {}",
+                e
+            );
+            None
+        }
+    };
+
+    // Purge unused uploads instead of starting the server, if requested.
+    if let Some(days) = matches.value_of("purge-uploads") {
+        let days = days
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Could not parse `--purge-uploads {}` as a u64", days));
+        let blob_store = blob_store.expect("The blob store must be set up to purge uploads");
+        let purged = v1::utils::purge_unattached_uploads(&client, blob_store.as_ref(), days)
+            .await
+            .unwrap();
+        println!("Purged {} unused images.", purged);
+        return Ok(());
+    }
+
     let weekly_recipe_getter = Arc::new(WeeklyRecipeGetter::new(client.clone()));
+    let uploads_directory = blob_store
+        .as_ref()
+        .and_then(|blob_store| blob_store.local_directory())
+        .map(|dir| dir.to_path_buf());
 
     // Start the web server
     println!("Starting Actix-web server on http://0.0.0.0:{}", port);
@@ -175,12 +391,19 @@ async fn main() -> std::io::Result<()> {
         //     .max_age(3600);
         let cors = Cors::permissive();
 
-        ActixApp::new()
+        let app = ActixApp::new()
             .wrap(cors)
             .app_data(web::Data::new(env))
             .app_data(web::Data::new(client.clone()))
             .app_data(web::Data::new(weekly_recipe_getter.clone()))
-            .service(web::scope("/api").service(v1::init(web::scope("/v1"))))
+            .app_data(web::Data::new(blob_store.clone()))
+            .service(web::scope("/api").service(v1::init(web::scope("/v1"))));
+
+        // Serve uploaded files if they are stored on this server.
+        match &uploads_directory {
+            Some(dir) => app.service(Files::new("/uploads", dir)),
+            None => app,
+        }
     })
     // Docker requires 0.0.0.0 and i wasted over an hour of my life
     // figuring this out.
@@ -0,0 +1,63 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum AccountResponse {
+    #[success(json)]
+    Account(database::Account),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Gets an account along with its learners.
+#[get("/account/id/{uuid}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to get Account from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return AccountResponse::InvalidUuid(path_uuid),
+    };
+
+    // The account is needed to check its tokens have not been revoked.
+    let db = client.get_collection::<database::Account>(Collections::Accounts);
+    match db.find_one(doc! {"_id": uuid}, None).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {
+            AccountResponse::Account(account)
+        }
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            AccountResponse::InvalidAuth
+        }
+        Ok(None) => AccountResponse::NotFound(uuid),
+        Err(err) => AccountResponse::InternalError(id_error!(
+            "Error getting account from database: {}",
+            err
+        )),
+    }
+}
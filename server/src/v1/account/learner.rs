@@ -0,0 +1,126 @@
+use crate::id_error;
+use crate::v1::account::RequestName;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum LearnerResponse {
+    /// If the learner was added, returns them.
+    #[success(message = "Successfully added the learner", json)]
+    Success(database::Learner),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// Returns if the account already has a learner with the name.
+    #[failure(message = "The account already has a learner named `{}`.")]
+    #[status_code(409)]
+    LearnerExists(String),
+    /// Returns if the account was modified while the learner was being
+    /// added. Contains the UUID of the account.
+    #[failure(
+        message = "The account was modified while the learner was being added. Please try again.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to add a learner to an account.
+///
+/// Every learner of an account must have a different name.
+#[post("/account/id/{uuid}/learner")]
+pub async fn insert(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    body: web::Json<RequestName>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to add learner to Account from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return LearnerResponse::InvalidUuid(path_uuid),
+    };
+
+    // The account is needed to check its tokens have not been revoked.
+    let db = client.get_collection::<database::Account>(Collections::Accounts);
+    let mut account = match db.find_one(doc! {"_id": uuid}, None).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => account,
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return LearnerResponse::InvalidAuth;
+        }
+        Ok(None) => return LearnerResponse::NotFound(uuid),
+        Err(err) => {
+            return LearnerResponse::InternalError(id_error!(
+                "Error getting account from database: {}",
+                err
+            ));
+        }
+    };
+
+    let name = body.into_inner().into_name();
+    if account
+        .learners
+        .iter()
+        .any(|learner| learner.name.trim().eq_ignore_ascii_case(name.trim()))
+    {
+        return LearnerResponse::LearnerExists(name);
+    }
+    let learner = match account.add_learner(name) {
+        Ok(learner) => learner.clone(),
+        Err(err) => return LearnerResponse::InvalidRequest(err),
+    };
+
+    // Only replaced if no learners were added since the account was read,
+    // so two learners added at once can not take it over the limit.
+    let filter = doc! {
+        "_id": uuid,
+        "learners": {"$size": (account.learners.len() - 1) as i64},
+    };
+    match db.replace_one(filter, &account, None).await {
+        Ok(result) if result.matched_count == 1 => {}
+        Ok(_) => {
+            trace!("Account {} was modified while adding a learner.", uuid);
+            return LearnerResponse::Conflict(uuid);
+        }
+        Err(err) => {
+            return LearnerResponse::InternalError(id_error!(
+                "Error adding learner to account: {}",
+                err
+            ));
+        }
+    }
+
+    trace!("Added learner {} to account {}.", learner.uuid, uuid);
+    LearnerResponse::Success(learner)
+}
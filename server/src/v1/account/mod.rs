@@ -0,0 +1,29 @@
+use actix_web::Scope;
+
+pub mod get;
+pub mod learner;
+pub mod post;
+pub mod token;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(post::insert)
+        .service(get::uuid)
+        .service(learner::insert)
+        .service(token::issue)
+}
+
+/// The name of an account or learner, sent in the request body.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestName {
+    /// The name to give the account or learner.
+    name: String,
+}
+
+impl RequestName {
+    /// Returns the name.
+    pub fn into_name(self) -> String {
+        self.name
+    }
+}
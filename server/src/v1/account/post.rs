@@ -0,0 +1,72 @@
+use crate::id_error;
+use crate::v1::account::RequestName;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, Responder};
+use tracing::trace;
+
+/// A newly created account, along with the token to act for it.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NewAccount {
+    /// The account that was created.
+    account: database::Account,
+    /// The [`AccountToken`] to send as the bearer token when acting for the
+    /// account. It is not stored, so must be kept by the parent. It expires
+    /// after 90 days, before which a new one can be issued with it.
+    token: String,
+}
+
+#[derive(ActixApiEnum)]
+enum AccountResponse {
+    /// If the account was created, returns it along with its token.
+    #[success(message = "Successfully created the account", json)]
+    Success(NewAccount),
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to create a parent account, without any learners.
+///
+/// Anyone can create an account. The returned token is the only way to act
+/// for it afterwards.
+#[post("/account")]
+pub async fn insert(
+    client: web::Data<mongodb::Client>,
+    body: web::Json<RequestName>,
+) -> impl Responder {
+    trace!("Attempting to create account.");
+    let account = match database::Account::new(body.into_inner().into_name()) {
+        Ok(account) => account,
+        Err(err) => return AccountResponse::InvalidRequest(err),
+    };
+
+    let token = match AccountToken::new(&account).sign() {
+        Ok(token) => token,
+        Err(e) => {
+            return AccountResponse::InternalError(id_error!("Error signing account token: {}", e));
+        }
+    };
+
+    if let Err(e) = client
+        .get_collection::<database::Account>(Collections::Accounts)
+        .insert_one(&account, None)
+        .await
+    {
+        return AccountResponse::InternalError(id_error!(
+            "Error inserting account into database: {:?}",
+            e
+        ));
+    }
+
+    trace!("Successfully created account {}.", account.uuid);
+    AccountResponse::Success(NewAccount { account, token })
+}
@@ -0,0 +1,126 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use tracing::trace;
+
+/// A newly issued token for an account.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IssuedToken {
+    /// The [`AccountToken`] to send as the bearer token when acting for the
+    /// account.
+    token: String,
+    /// The date the token expires.
+    expires: Date,
+}
+
+#[derive(ActixApiEnum)]
+enum TokenResponse {
+    /// If the token was issued, returns it.
+    #[success(message = "Successfully issued a token", json)]
+    Success(IssuedToken),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// The query parameters of a request for a new token.
+#[derive(Debug, serde::Deserialize)]
+pub struct TokenQuery {
+    /// Whether to revoke every token issued for the account before.
+    #[serde(default)]
+    revoke: bool,
+}
+
+/// Issues a new token for an account, as tokens expire after 90 days.
+///
+/// If `revoke` is set, every token issued for the account before stops
+/// working, such as if one was lost.
+#[post("/account/id/{uuid}/token")]
+pub async fn issue(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    query: web::Query<TokenQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to issue token for Account from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return TokenResponse::InvalidUuid(path_uuid),
+    };
+
+    // The account is needed to check its tokens have not been revoked.
+    let db = client.get_collection::<database::Account>(Collections::Accounts);
+    let mut account = match db.find_one(doc! {"_id": uuid}, None).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => account,
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return TokenResponse::InvalidAuth;
+        }
+        Ok(None) => return TokenResponse::NotFound(uuid),
+        Err(err) => {
+            return TokenResponse::InternalError(id_error!(
+                "Error getting account from database: {}",
+                err
+            ));
+        }
+    };
+
+    if query.revoke {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        account = match db
+            .find_one_and_update(
+                doc! {"_id": uuid},
+                doc! {"$inc": {"tokenVersion": 1}},
+                options,
+            )
+            .await
+        {
+            Ok(Some(account)) => account,
+            Ok(None) => return TokenResponse::NotFound(uuid),
+            Err(err) => {
+                return TokenResponse::InternalError(id_error!(
+                    "Error revoking account tokens: {}",
+                    err
+                ));
+            }
+        };
+        trace!("Revoked every token of account {}.", uuid);
+    }
+
+    let token = AccountToken::new(&account);
+    match token.sign() {
+        Ok(signed) => TokenResponse::Success(IssuedToken {
+            token: signed,
+            expires: token.expires,
+        }),
+        Err(e) => TokenResponse::InternalError(id_error!("Error signing account token: {}", e)),
+    }
+}
@@ -0,0 +1,126 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+use tracing::trace;
+
+/// An achievement a learner has unlocked.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnlockedBadge {
+    /// The achievement that was unlocked.
+    achievement: database::Achievement,
+    /// The date it was unlocked.
+    unlocked: Date,
+}
+
+#[derive(ActixApiEnum)]
+enum BadgesResponse {
+    /// Returns the learner's badges, newest first.
+    #[success(json)]
+    Badges(Vec<UnlockedBadge>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Lists every achievement a learner has unlocked.
+#[get("/learner/id/{uuid}/badges")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get badges of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return BadgesResponse::InvalidUuid(path_uuid),
+    };
+
+    // Only the learner's account can see their badges.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return BadgesResponse::InvalidAuth;
+        }
+        Ok(None) => return BadgesResponse::NotFound(uuid),
+        Err(err) => {
+            return BadgesResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    match unlocked_badges(&client, uuid).await {
+        Ok(badges) => BadgesResponse::Badges(badges),
+        Err(err) => BadgesResponse::InternalError(id_error!(
+            "Error getting learner's badges from database: {}",
+            err
+        )),
+    }
+}
+
+/// Returns the badges a learner has unlocked, newest first, along with the
+/// achievements they are for.
+async fn unlocked_badges(
+    client: &mongodb::Client,
+    learner: Uuid,
+) -> Result<Vec<UnlockedBadge>, mongodb::error::Error> {
+    let find_options = FindOptions::builder().sort(doc! { "date": -1 }).build();
+    let mut cursor = client
+        .get_collection::<database::Badge>(Collections::Badges)
+        .find(doc! {"learner": learner}, find_options)
+        .await?;
+    let mut badges = vec![];
+    while cursor.advance().await? {
+        badges.push(cursor.deserialize_current()?);
+    }
+
+    let uuids: Vec<Uuid> = badges.iter().map(|badge| badge.achievement).collect();
+    let mut cursor = client
+        .get_collection::<database::Achievement>(Collections::Achievements)
+        .find(doc! {"_id": {"$in": uuids}}, None)
+        .await?;
+    let mut achievements = vec![];
+    while cursor.advance().await? {
+        achievements.push(cursor.deserialize_current()?);
+    }
+
+    // The database returns the achievements in any order.
+    Ok(badges
+        .into_iter()
+        .filter_map(|badge| {
+            let achievement = achievements
+                .iter()
+                .find(|achievement| achievement.uuid == badge.achievement)?;
+            Some(UnlockedBadge {
+                achievement: achievement.clone(),
+                unlocked: badge.date,
+            })
+        })
+        .collect())
+}
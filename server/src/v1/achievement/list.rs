@@ -0,0 +1,58 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Responder};
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum AchievementsResponse {
+    #[success(json)]
+    Achievements(Vec<database::Achievement>),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// Lists every achievement that can be unlocked, oldest first.
+#[get("/achievements")]
+pub async fn achievements(client: web::Data<mongodb::Client>) -> impl Responder {
+    trace!("Listing achievements.");
+
+    let db = client.get_collection::<database::Achievement>(Collections::Achievements);
+    let find_options = FindOptions::builder().sort(doc! { "dateAdded": 1 }).build();
+    let mut cursor = match db.find(doc! {}, find_options).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return AchievementsResponse::InternalError(id_error!(
+                "Error getting achievements from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut achievements = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(achievement) => achievements.push(achievement),
+                Err(err) => {
+                    return AchievementsResponse::InternalError(id_error!(
+                        "Error deserializing achievement: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return AchievementsResponse::InternalError(id_error!(
+                    "Error getting achievements from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    AchievementsResponse::Achievements(achievements)
+}
@@ -0,0 +1,47 @@
+use crate::v1::types::*;
+use actix_web::Scope;
+
+pub mod badges;
+pub mod list;
+pub mod post;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(post::insert)
+        .service(list::achievements)
+        .service(badges::uuid)
+}
+
+/// The type of achievement sent in the request body.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestAchievement {
+    /// The unique identifier of the achievement.
+    /// If set, overwrites the achievement with the same id.
+    uuid: Option<Uuid>,
+    /// The title of the achievement.
+    title: String,
+    /// What must be done to unlock the achievement.
+    description: String,
+    /// The URL to the badge image.
+    image: Url,
+    /// The rule that unlocks the achievement.
+    rule: database::AchievementRule,
+}
+
+impl RequestAchievement {
+    /// Tries to convert a RequestAchievement into a
+    /// [`database::Achievement`].
+    ///
+    /// Returns every field that breaks a limit of the achievement if it
+    /// fails.
+    pub fn into_achievement(self) -> Result<database::Achievement, Vec<Violation>> {
+        database::Achievement::new(
+            self.uuid,
+            self.title,
+            self.description,
+            self.image,
+            self.rule,
+        )
+    }
+}
@@ -0,0 +1,99 @@
+use crate::id_error;
+use crate::v1::achievement::RequestAchievement;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum AchievementResponse {
+    /// If the insertion was successful, returns the newly updated
+    /// [`Achievement`].
+    ///
+    /// [`Achievement`]: crate::v1::types::database::Achievement
+    #[success(message = "Successfully inserted into the database", json)]
+    Success(database::Achievement),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to insert or update an achievement.
+///
+/// If the UUID of an existing achievement is given, that achievement is
+/// replaced. Learners keep badges they have already unlocked, even if they
+/// no longer meet the new rule.
+#[post("/achievement")]
+pub async fn insert(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    body: web::Json<RequestAchievement>,
+) -> impl Responder {
+    trace!("Attempting to insert achievement.");
+    // Important endpoint. Check for authorization before allowing
+    // access to insert data.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return AchievementResponse::InvalidAuth;
+    }
+
+    // Convert the request achievement to a database achievement.
+    let mut achievement = match body.into_inner().into_achievement() {
+        Ok(achievement) => achievement,
+        Err(err) => {
+            trace!(
+                "Could not insert achievement due to invalid request body: {:?}",
+                err
+            );
+            return AchievementResponse::InvalidRequest(err);
+        }
+    };
+
+    let achievement_uuid = achievement.uuid;
+    let db = client.get_collection::<database::Achievement>(Collections::Achievements);
+
+    // Check if we are overwriting an existing achievement.
+    match db.find_one(doc! {"_id": achievement_uuid}, None).await {
+        Ok(Some(existing)) => achievement.inherit(&existing),
+        Ok(None) => {}
+        Err(e) => {
+            return AchievementResponse::InternalError(id_error!(
+                "Error getting achievement from database: {:?}",
+                e
+            ));
+        }
+    }
+
+    let options = mongodb::options::ReplaceOptions::builder()
+        .upsert(true)
+        .build();
+    if let Err(e) = db
+        .replace_one(doc! {"_id": achievement_uuid}, &achievement, options)
+        .await
+    {
+        return AchievementResponse::InternalError(id_error!(
+            "Error inserting achievement into database: {:?}",
+            e
+        ));
+    }
+
+    trace!(
+        "Successfully inserted/updated achievement {}.",
+        achievement_uuid
+    );
+    AchievementResponse::Success(achievement)
+}
@@ -0,0 +1,73 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{error, get, web, Either, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum ExportResponse {
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Exports every recipe in the database as NDJSON, one recipe per line.
+///
+/// Recipes are exported exactly as they are stored, including deleted and
+/// unreleased recipes, so the output can be given to `POST /recipes/import`
+/// on another database.
+#[get("/recipes/export")]
+pub async fn export(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+) -> Either<ExportResponse, HttpResponse> {
+    // The export contains unreleased content, so is only available to staff.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return Either::Left(ExportResponse::InvalidAuth);
+    }
+
+    trace!("Exporting all recipes.");
+    let cursor = client
+        .get_collection::<database::Recipe>(Collections::Recipes)
+        .find(doc! {}, None)
+        .await;
+    let cursor = match cursor {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return Either::Left(ExportResponse::InternalError(id_error!(
+                "Error getting recipes from database: {}",
+                err
+            )));
+        }
+    };
+
+    // The response has already started by the time an error can occur,
+    // so the error ends the stream early rather than changing the status.
+    let lines = cursor.map(|recipe| {
+        let recipe = recipe.map_err(|err| {
+            error::ErrorInternalServerError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ))
+        })?;
+        let mut line = serde_json::to_vec(&recipe)?;
+        line.push(b'\n');
+        Ok::<_, actix_web::Error>(web::Bytes::from(line))
+    });
+
+    Either::Right(
+        HttpResponse::Ok()
+            .content_type("application/x-ndjson")
+            .streaming(lines),
+    )
+}
@@ -0,0 +1,406 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use futures_util::StreamExt;
+use mongodb::bson::doc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::trace;
+
+/// What to do with a record whose UUID is already in the database.
+#[derive(Debug, Default, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OnExisting {
+    /// Leaves the stored recipe untouched.
+    #[default]
+    Skip,
+    /// Replaces the stored recipe, archiving it as a revision first.
+    Overwrite,
+}
+
+/// The query parameters of an import.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportQuery {
+    /// If true, every record is checked but nothing is written.
+    #[serde(default)]
+    dry_run: bool,
+    /// What to do with records whose UUID is already in the database.
+    #[serde(default)]
+    existing: OnExisting,
+}
+
+/// A record of the import that could not be imported.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineError {
+    /// The line of the record, starting at 1.
+    line: usize,
+    /// The UUID of the record, if it could be read.
+    uuid: Option<Uuid>,
+    /// Everything that is wrong with the record.
+    violations: Vec<Violation>,
+}
+
+impl LineError {
+    /// Constructs the error of a record whose short is already used.
+    fn short_taken(line: usize, uuid: Uuid, short: &str) -> Self {
+        Self {
+            line,
+            uuid: Some(uuid),
+            violations: vec![Violation::new(
+                "short",
+                format!("The short `{}` is used by another recipe.", short),
+            )],
+        }
+    }
+}
+
+/// The outcome of an import.
+///
+/// For a dry run, the counts are what would have happened.
+#[derive(Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportReport {
+    /// Whether this was a dry run, in which case nothing was written.
+    dry_run: bool,
+    /// The number of new recipes.
+    inserted: u32,
+    /// The number of stored recipes that were replaced.
+    overwritten: u32,
+    /// The number of records skipped as their UUID was already stored.
+    skipped: u32,
+    /// The records that could not be imported.
+    errors: Vec<LineError>,
+}
+
+#[derive(ActixApiEnum)]
+enum ImportResponse {
+    /// Returns what was imported, and the errors of each record that could
+    /// not be.
+    #[success(json)]
+    Report(ImportReport),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    /// Returns if the request body could not be read.
+    #[failure(message = "Could not read the request body: `{}`.")]
+    #[status_code(400)]
+    InvalidBody(String),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue. Records
+    /// before the failed one may have already been imported.
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Imports recipes from an NDJSON body, one recipe per line, as created by
+/// `GET /recipes/export`.
+///
+/// Every record is validated in the same way as `POST /recipe`, and invalid
+/// records are reported by line without stopping the import. Records keep
+/// their dates and authors, so content can be moved between databases
+/// unchanged.
+///
+/// `?dryRun=true` validates the records without writing them, and
+/// `?existing=skip|overwrite` chooses what happens to records whose UUID is
+/// already stored. Existing recipes are skipped by default.
+#[post("/recipes/import")]
+pub async fn import(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    query: web::Query<ImportQuery>,
+    mut body: web::Payload,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to insert data.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return ImportResponse::InvalidAuth;
+    }
+
+    let query = query.into_inner();
+    trace!(
+        "Importing recipes. Dry run: {}, existing: {:?}",
+        query.dry_run,
+        query.existing
+    );
+    let mut report = ImportReport {
+        dry_run: query.dry_run,
+        ..ImportReport::default()
+    };
+
+    // Records are imported as their line arrives, so the whole body never
+    // has to be held in memory.
+    let mut buffer = vec![];
+    let mut line_number = 0;
+    // The short of every record imported so far, so a dry run finds
+    // records that share a short with an earlier one.
+    let mut shorts = HashMap::new();
+    loop {
+        let chunk = body.next().await;
+        let finished = chunk.is_none();
+        match chunk {
+            Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+            Some(Err(err)) => return ImportResponse::InvalidBody(err.to_string()),
+            None => {}
+        }
+
+        while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            line_number += 1;
+            if let Err(err) = import_line(
+                &client,
+                &query,
+                &line,
+                line_number,
+                &mut shorts,
+                &mut report,
+            )
+            .await
+            {
+                return ImportResponse::InternalError(id_error!(
+                    "Error importing line {}: {:?}",
+                    line_number,
+                    err
+                ));
+            }
+        }
+
+        if finished {
+            // The last line may not end with a newline.
+            if !buffer.is_empty() {
+                line_number += 1;
+                if let Err(err) = import_line(
+                    &client,
+                    &query,
+                    &buffer,
+                    line_number,
+                    &mut shorts,
+                    &mut report,
+                )
+                .await
+                {
+                    return ImportResponse::InternalError(id_error!(
+                        "Error importing line {}: {:?}",
+                        line_number,
+                        err
+                    ));
+                }
+            }
+            break;
+        }
+    }
+
+    // The import may have changed the weekly recipe.
+    if !query.dry_run && report.inserted + report.overwritten > 0 {
+        weekly_cacher.invalidate();
+    }
+
+    trace!(
+        "Imported recipes. Inserted {}, overwrote {}, skipped {}, {} errors.",
+        report.inserted,
+        report.overwritten,
+        report.skipped,
+        report.errors.len()
+    );
+    ImportResponse::Report(report)
+}
+
+/// Reads the recipe on a single line, checking it against every documented
+/// limit.
+///
+/// Returns None for a blank line, such as a trailing newline, as it is not
+/// a record.
+fn parse_line(line: &[u8], line_number: usize) -> Result<Option<database::Recipe>, LineError> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let recipe = serde_json::from_str::<database::Recipe>(line).map_err(|err| LineError {
+        line: line_number,
+        uuid: None,
+        violations: vec![Violation::new("", format!("Invalid record: {}", err))],
+    })?;
+    match recipe.validate() {
+        Ok(()) => Ok(Some(recipe)),
+        Err(violations) => Err(LineError {
+            line: line_number,
+            uuid: Some(recipe.uuid),
+            violations,
+        }),
+    }
+}
+
+/// Imports the recipe on a single line, adding the outcome to the report.
+///
+/// `shorts` is every short and previous short of the records imported
+/// before, keyed to their UUID, and the record's are added to it if it is
+/// imported.
+///
+/// Only returns an error if the database could not be reached. Problems with
+/// the record itself are added to the report.
+async fn import_line(
+    client: &mongodb::Client,
+    query: &ImportQuery,
+    line: &[u8],
+    line_number: usize,
+    shorts: &mut HashMap<String, Uuid>,
+    report: &mut ImportReport,
+) -> Result<(), mongodb::error::Error> {
+    let mut recipe = match parse_line(line, line_number) {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return Ok(()),
+        Err(error) => {
+            report.errors.push(error);
+            return Ok(());
+        }
+    };
+    let uuid = recipe.uuid;
+
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let existing = db.find_one(doc! {"_id": uuid}, None).await?;
+    if existing.is_some() && query.existing == OnExisting::Skip {
+        report.skipped += 1;
+        return Ok(());
+    }
+
+    // Keep the short history, so an import that changes the short still
+    // redirects links to the old one.
+    if let Some(existing) = &existing {
+        recipe.inherit_shorts(existing);
+    }
+
+    // Shorts are unique, and the previous shorts of other recipes are kept
+    // for their redirects, so the import is rejected rather than renaming
+    // the recipe. Nothing is written in a dry run, so earlier records of
+    // the import are checked as well as the database.
+    let short_taken = shorts
+        .get(&recipe.short)
+        .is_some_and(|other| *other != uuid)
+        || db
+            .find_one(
+                doc! {
+                    "_id": {"$ne": uuid},
+                    "$or": [
+                        {"short": &recipe.short},
+                        {"previousShorts": &recipe.short},
+                    ],
+                },
+                None,
+            )
+            .await?
+            .is_some();
+    if short_taken {
+        report
+            .errors
+            .push(LineError::short_taken(line_number, uuid, &recipe.short));
+        return Ok(());
+    }
+
+    if !query.dry_run {
+        let result = match existing {
+            Some(ref existing) => {
+                // Keep the stored version so the import can be rolled back,
+                // and only replace it if it was not modified since.
+                archive_revision(client, existing.clone()).await?;
+                recipe.set_revision(existing.revision + 1);
+                db.replace_one(revision_filter(uuid, existing.revision), &recipe, None)
+                    .await
+                    .map(|result| result.matched_count > 0)
+            }
+            None => db.insert_one(&recipe, None).await.map(|_| true),
+        };
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                report.errors.push(LineError {
+                    line: line_number,
+                    uuid: Some(uuid),
+                    violations: vec![Violation::new(
+                        "",
+                        "The recipe was modified during the import. Please try again.",
+                    )],
+                });
+                return Ok(());
+            }
+            // Another recipe took the short since it was checked.
+            Err(err) if is_duplicate_key_error(&err) => {
+                report
+                    .errors
+                    .push(LineError::short_taken(line_number, uuid, &recipe.short));
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    for short in std::iter::once(&recipe.short).chain(&recipe.previous_shorts) {
+        shorts.insert(short.clone(), uuid);
+    }
+    if existing.is_some() {
+        report.overwritten += 1;
+    } else {
+        report.inserted += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the record in `tests/golden/minimal.recipe.json`, changed by
+    /// `change`, on a single line.
+    fn record(change: impl FnOnce(&mut serde_json::Value)) -> Vec<u8> {
+        serde_json::to_vec(&database::fixture_recipe(change)).unwrap()
+    }
+
+    #[test]
+    fn parses_records() {
+        let recipe = parse_line(&record(|_| {}), 1).unwrap().unwrap();
+        assert_eq!(recipe.short, "toast");
+        assert!(parse_line(b"  \r", 2).unwrap().is_none());
+    }
+
+    #[test]
+    fn reports_unreadable_records() {
+        let error = parse_line(b"{\"title\": ", 3).unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.uuid, None);
+        assert_eq!(error.violations.len(), 1);
+    }
+
+    #[test]
+    fn reports_invalid_records() {
+        let line = record(|json| json["title"] = "a".repeat(1000).into());
+        let error = parse_line(&line, 4).unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(
+            error.uuid.unwrap().to_string(),
+            "7a3e9c42-5b1d-4f08-8c6e-2d9b0a4f1e37"
+        );
+        assert!(!error.violations.is_empty());
+    }
+
+    #[test]
+    fn reads_query() {
+        let query = web::Query::<ImportQuery>::from_query("").unwrap();
+        assert!(!query.dry_run);
+        assert_eq!(query.existing, OnExisting::Skip);
+        let query =
+            web::Query::<ImportQuery>::from_query("dryRun=true&existing=overwrite").unwrap();
+        assert!(query.dry_run);
+        assert_eq!(query.existing, OnExisting::Overwrite);
+    }
+}
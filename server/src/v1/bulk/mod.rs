@@ -0,0 +1,8 @@
+use actix_web::Scope;
+
+pub mod export;
+pub mod import;
+
+pub fn init(scope: Scope) -> Scope {
+    scope.service(export::export).service(import::import)
+}
@@ -0,0 +1,75 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+/// How many families have cooked a recipe.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FamiliesCooked {
+    /// The UUID of the recipe.
+    recipe: Uuid,
+    /// The number of different accounts whose learners have cooked it.
+    families: u32,
+}
+
+#[derive(ActixApiEnum)]
+enum CountResponse {
+    #[success(json)]
+    Count(FamiliesCooked),
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// Gets how many families have cooked a recipe.
+#[get("/recipe/id/{uuid}/cooked")]
+pub async fn uuid(
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    query: web::Query<PreviewQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get families cooked of Recipe from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return CountResponse::InvalidUuid(path_uuid),
+    };
+
+    // Unreleased and deleted recipes are treated as if they do not exist.
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    match db.find_one(doc! {"_id": uuid}, None).await {
+        Ok(Some(recipe)) if can_view(&recipe, query.preview.as_deref()) => {}
+        Ok(_) => return CountResponse::NotFound(uuid),
+        Err(err) => {
+            return CountResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    }
+
+    match cook_count(&client, uuid).await {
+        Ok(families) => CountResponse::Count(FamiliesCooked {
+            recipe: uuid,
+            families,
+        }),
+        Err(err) => CountResponse::InternalError(id_error!(
+            "Error getting families cooked from database: {}",
+            err
+        )),
+    }
+}
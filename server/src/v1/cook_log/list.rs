@@ -0,0 +1,147 @@
+use crate::id_error;
+use crate::v1::favourite::public_recipes;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+/// A recipe a learner cooked, along with what they logged about it.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CookedRecipe {
+    /// The recipe that was cooked.
+    #[serde(flatten)]
+    recipe: BasicRecipe,
+    /// The UUID of the entry in the learner's cook log.
+    entry: Uuid,
+    /// The date the recipe was cooked.
+    cooked: Date,
+    /// The URL to a photo of what was cooked.
+    photo: Option<Url>,
+    /// How much the learner liked the recipe, from 1 to 5.
+    rating: Option<u8>,
+}
+
+#[derive(ActixApiEnum)]
+enum CookLogResponse {
+    /// Returns the recipes the learner cooked, most recently cooked first.
+    #[success(json)]
+    Recipes(Vec<CookedRecipe>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Error with request: {}")]
+    #[status_code(400)]
+    RequestError(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Lists the recipes a learner has cooked, a page at a time.
+///
+/// A recipe cooked more than once is listed each time it was cooked.
+/// Recipes that have since been deleted are left out, so a page may have
+/// fewer recipes than asked for.
+#[get("/learner/id/{uuid}/cooked")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    page: web::Query<PageQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get cook log of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return CookLogResponse::InvalidUuid(path_uuid),
+    };
+    if let Err(e) = page.validate() {
+        return CookLogResponse::RequestError(e);
+    }
+
+    // Only the learner's account can see what they cooked.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return CookLogResponse::InvalidAuth;
+        }
+        Ok(None) => return CookLogResponse::NotFound(uuid),
+        Err(err) => {
+            return CookLogResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    let (entries, recipes) = match cooked_recipes(&client, uuid, &page).await {
+        Ok(cooked) => cooked,
+        Err(err) => {
+            return CookLogResponse::InternalError(id_error!(
+                "Error getting learner's cook log from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut cooked = vec![];
+    for entry in entries {
+        let recipe = match recipes.iter().find(|recipe| recipe.uuid == entry.recipe) {
+            Some(recipe) => recipe,
+            None => continue,
+        };
+        cooked.push(CookedRecipe {
+            recipe: BasicRecipe::from_recipe(recipe, &weekly_cacher).await,
+            entry: entry.uuid,
+            cooked: entry.date,
+            photo: entry.photo.clone(),
+            rating: entry.rating,
+        });
+    }
+    CookLogResponse::Recipes(cooked)
+}
+
+/// Returns the entries on a page of a learner's cook log, most recently
+/// cooked first, along with the recipes in them that can be shown.
+async fn cooked_recipes(
+    client: &mongodb::Client,
+    learner: Uuid,
+    page: &PageQuery,
+) -> Result<(Vec<database::CookLogEntry>, Vec<database::Recipe>), mongodb::error::Error> {
+    let mut cursor = client
+        .get_collection::<database::CookLogEntry>(Collections::CookLog)
+        .find(
+            doc! {"learner": learner},
+            page.find_options(doc! {"date": -1}),
+        )
+        .await?;
+    let mut entries: Vec<database::CookLogEntry> = vec![];
+    while cursor.advance().await? {
+        entries.push(cursor.deserialize_current()?);
+    }
+
+    let uuids: Vec<Uuid> = entries.iter().map(|entry| entry.recipe).collect();
+    let recipes = public_recipes(client, &uuids).await?;
+    Ok((entries, recipes))
+}
@@ -0,0 +1,12 @@
+use actix_web::Scope;
+
+pub mod count;
+pub mod list;
+pub mod post;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(post::insert)
+        .service(list::uuid)
+        .service(count::uuid)
+}
@@ -0,0 +1,157 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::{error, trace};
+
+/// A time a learner cooked a recipe, sent in the request body.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestCook {
+    /// The recipe that was cooked.
+    recipe: Uuid,
+    /// The URL to a photo of what was cooked, uploaded as a `cookLog`
+    /// image.
+    photo: Option<Url>,
+    /// How much the learner liked the recipe, from 1 to 5.
+    rating: Option<u8>,
+}
+
+/// A recorded time a learner cooked a recipe.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LoggedCook {
+    /// The entry added to the learner's cook log.
+    entry: database::CookLogEntry,
+    /// The achievements cooking the recipe unlocked.
+    achievements: Vec<database::Achievement>,
+}
+
+#[derive(ActixApiEnum)]
+enum CookResponse {
+    /// If the cook was logged, returns the entry along with any
+    /// achievements it unlocked.
+    #[success(message = "Successfully logged the recipe as cooked", json)]
+    Success(LoggedCook),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to log that a learner cooked a recipe.
+///
+/// A recipe can be logged as many times as it is cooked, but each family
+/// is only counted once towards how many families have cooked it.
+#[post("/learner/id/{uuid}/cooked")]
+pub async fn insert(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    body: web::Json<RequestCook>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to log cook of Learner from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return CookResponse::InvalidUuid(path_uuid),
+    };
+
+    // Only the learner's account can log what they cooked.
+    let account = match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => account,
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return CookResponse::InvalidAuth;
+        }
+        Ok(None) => return CookResponse::NotFound(uuid),
+        Err(err) => {
+            return CookResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    };
+
+    // Unreleased and deleted recipes are treated as if they do not exist.
+    let body = body.into_inner();
+    let recipes = client.get_collection::<database::Recipe>(Collections::Recipes);
+    match recipes.find_one(doc! {"_id": body.recipe}, None).await {
+        Ok(Some(recipe)) if can_view(&recipe, None) => {}
+        Ok(_) => return CookResponse::NotFound(body.recipe),
+        Err(err) => {
+            return CookResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    }
+
+    // Achievements can ask for the weekly recipe to be cooked while it is
+    // the weekly recipe.
+    let weekly = match weekly_cacher.get().await {
+        Ok(weekly) if weekly.uuid == body.recipe => weekly.weekly_timestamp,
+        _ => None,
+    };
+    let entry = match database::CookLogEntry::new(
+        uuid,
+        account.uuid,
+        body.recipe,
+        weekly,
+        body.photo,
+        body.rating,
+    ) {
+        Ok(entry) => entry,
+        Err(err) => return CookResponse::InvalidRequest(err),
+    };
+
+    if let Err(err) = record_cook(&client, &entry).await {
+        return CookResponse::InternalError(id_error!("Error recording cooked recipe: {:?}", err));
+    }
+
+    // The cook has already been recorded, so it is still returned if the
+    // achievements can not be checked.
+    let event = database::LearnerEvent::RecipeCooked(&entry);
+    let achievements = match unlock_achievements(&client, event).await {
+        Ok(achievements) => achievements,
+        Err(err) => {
+            error!("Error unlocking achievements of learner {}: {}", uuid, err);
+            vec![]
+        }
+    };
+
+    trace!(
+        "Logged recipe {} as cooked by learner {}.",
+        entry.recipe,
+        uuid
+    );
+    CookResponse::Success(LoggedCook {
+        entry,
+        achievements,
+    })
+}
@@ -0,0 +1,73 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{delete, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum DeleteResponse {
+    /// If the cookbook was deleted, returns its UUID.
+    #[success(message = "Successfully deleted the cookbook", json)]
+    Success(Uuid),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if there is no cookbook with the UUID.
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to delete a cookbook.
+///
+/// Unlike recipes, cookbooks are removed from the database straight away.
+/// The recipes in the cookbook are not affected.
+#[delete("/cookbook/id/{uuid}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to delete data.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return DeleteResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to delete Cookbook from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return DeleteResponse::InvalidUuid(path_uuid),
+    };
+
+    let db = client.get_collection::<database::Cookbook>(Collections::Cookbooks);
+    match db.delete_one(doc! {"_id": uuid}, None).await {
+        Ok(result) if result.deleted_count == 0 => DeleteResponse::NotFound(uuid),
+        Ok(_) => {
+            trace!("Successfully deleted cookbook {}.", uuid);
+            DeleteResponse::Success(uuid)
+        }
+        Err(err) => DeleteResponse::InternalError(id_error!(
+            "Error deleting cookbook from database: {:?}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,78 @@
+use crate::id_error;
+use crate::v1::cookbook::public_recipes;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum CookbookResponse {
+    #[success(json)]
+    Cookbook(Cookbook),
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// The query parameters that change how a cookbook is shown.
+#[derive(Debug, serde::Deserialize)]
+pub struct CookbookQuery {
+    /// The format to also return the description in. If not set, it is
+    /// only returned as it was written.
+    pub markup: Option<MarkupFormat>,
+}
+
+#[get("/cookbook/id/{uuid}")]
+pub async fn uuid(
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    options: web::Query<CookbookQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to get Cookbook from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return CookbookResponse::InvalidUuid(path_uuid),
+    };
+
+    // Get the cookbook from the database
+    let db = client.get_collection::<database::Cookbook>(Collections::Cookbooks);
+    let cookbook = match db.find_one(doc! {"_id": uuid}, None).await {
+        Ok(Some(cookbook)) => cookbook,
+        Ok(None) => return CookbookResponse::NotFound(uuid),
+        Err(err) => {
+            return CookbookResponse::InternalError(id_error!(
+                "Error getting cookbook from database: {}",
+                err
+            ));
+        }
+    };
+
+    let recipes = match public_recipes(&client, &weekly_cacher, &cookbook).await {
+        Ok(recipes) => recipes,
+        Err(err) => {
+            return CookbookResponse::InternalError(id_error!(
+                "Error getting cookbook recipes from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut cookbook = Cookbook::from_cookbook(&cookbook, recipes);
+    if let Some(markup) = options.markup {
+        cookbook = cookbook.with_markup(markup);
+    }
+    CookbookResponse::Cookbook(cookbook)
+}
@@ -0,0 +1,60 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Responder};
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum CookbooksResponse {
+    #[success(json)]
+    Cookbooks(Vec<BasicCookbook>),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// Lists every cookbook, newest first, without the recipes in them.
+#[get("/cookbooks")]
+pub async fn cookbooks(client: web::Data<mongodb::Client>) -> impl Responder {
+    trace!("Listing cookbooks.");
+
+    let db = client.get_collection::<database::Cookbook>(Collections::Cookbooks);
+    let find_options = FindOptions::builder()
+        .sort(doc! { "dateAdded": -1 })
+        .build();
+    let mut cursor = match db.find(doc! {}, find_options).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return CookbooksResponse::InternalError(id_error!(
+                "Error getting cookbooks from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut cookbooks = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(cookbook) => cookbooks.push(BasicCookbook::from(&cookbook)),
+                Err(err) => {
+                    return CookbooksResponse::InternalError(id_error!(
+                        "Error deserializing cookbook: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return CookbooksResponse::InternalError(id_error!(
+                    "Error getting cookbooks from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    CookbooksResponse::Cookbooks(cookbooks)
+}
@@ -0,0 +1,144 @@
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_web::Scope;
+use mongodb::bson::doc;
+
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod post;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(post::insert)
+        .service(delete::uuid)
+        .service(list::cookbooks)
+        .service(get::uuid)
+}
+
+/// The type of cookbook sent in the request body.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestCookbook {
+    /// The unique identifier of the cookbook.
+    /// If set, overwrites the cookbook with the same id.
+    uuid: Option<Uuid>,
+    /// The title of the cookbook.
+    title: String,
+    /// A description of the cookbook.
+    description: Formattable,
+    /// The URL to the cookbook image. Should be on S3
+    image: Url,
+    /// The gradient of the cookbook.
+    gradient: Gradient,
+    /// The UUIDs of the recipes in the cookbook, in the order they are shown.
+    recipes: Vec<Uuid>,
+}
+
+impl RequestCookbook {
+    /// Tries to convert a RequestCookbook into a [`database::Cookbook`].
+    ///
+    /// Returns every field that breaks a limit of the cookbook if it fails.
+    pub fn into_cookbook(self) -> Result<database::Cookbook, Vec<Violation>> {
+        database::Cookbook::new(
+            self.uuid,
+            self.title,
+            self.description,
+            self.image,
+            self.gradient,
+            self.recipes,
+        )
+    }
+}
+
+/// Gets the recipes in a cookbook that can be shown to the public,
+/// in the order they are in the cookbook.
+pub async fn public_recipes(
+    client: &mongodb::Client,
+    weekly_cacher: &WeeklyRecipeGetter,
+    cookbook: &database::Cookbook,
+) -> Result<Vec<BasicRecipe>, mongodb::error::Error> {
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let mut cursor = db
+        .find(
+            only_public(doc! {"_id": {"$in": cookbook.recipes.clone()}}),
+            None,
+        )
+        .await?;
+
+    let mut recipes = vec![];
+    while cursor.advance().await? {
+        recipes.push(cursor.deserialize_current()?);
+    }
+    // The database returns the recipes in any order.
+    sort_in_order(&mut recipes, &cookbook.recipes);
+
+    let mut basic_recipes = vec![];
+    for recipe in recipes {
+        basic_recipes.push(BasicRecipe::from_recipe(&recipe, weekly_cacher).await);
+    }
+    Ok(basic_recipes)
+}
+
+/// Sorts recipes into the order their UUIDs are in `order`.
+fn sort_in_order(recipes: &mut [database::Recipe], order: &[Uuid]) {
+    recipes.sort_by_key(|recipe| order.iter().position(|uuid| *uuid == recipe.uuid));
+}
+
+/// Returns a violation for every recipe in the cookbook that does not exist
+/// or has been deleted.
+///
+/// Unreleased recipes are allowed, so cookbooks can be prepared ahead of
+/// the recipes in them being released.
+pub async fn missing_recipes(
+    client: &mongodb::Client,
+    cookbook: &database::Cookbook,
+) -> Result<Vec<Violation>, mongodb::error::Error> {
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let mut cursor = db
+        .find(
+            doc! {"_id": {"$in": cookbook.recipes.clone()}, "deleted": null},
+            None,
+        )
+        .await?;
+
+    let mut found = vec![];
+    while cursor.advance().await? {
+        found.push(cursor.deserialize_current()?.uuid);
+    }
+
+    Ok(cookbook
+        .recipes
+        .iter()
+        .enumerate()
+        .filter(|(_, uuid)| !found.contains(uuid))
+        .map(|(i, _)| Violation::new(format!("recipes[{}]", i), "No recipe has this UUID."))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+
+    #[test]
+    fn keeps_cookbook_order() {
+        let uuids = [
+            "7a3e9c42-5b1d-4f08-8c6e-2d9b0a4f1e37",
+            "0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f",
+            "1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f",
+        ];
+        let mut recipes: Vec<database::Recipe> = [uuids[1], uuids[2], uuids[0]]
+            .into_iter()
+            .map(|uuid| fixture_recipe(|json| json["_id"] = uuid.into()))
+            .collect();
+        let order: Vec<Uuid> = [uuids[2], uuids[0], uuids[1]]
+            .into_iter()
+            .map(|uuid| Uuid::try_from(uuid).unwrap())
+            .collect();
+
+        sort_in_order(&mut recipes, &order);
+        let sorted: Vec<Uuid> = recipes.iter().map(|recipe| recipe.uuid).collect();
+        assert_eq!(sorted, order);
+    }
+}
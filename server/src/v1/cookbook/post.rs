@@ -0,0 +1,105 @@
+use crate::id_error;
+use crate::v1::cookbook::{missing_recipes, RequestCookbook};
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum CookbookResponse {
+    /// If the insertion was successful, returns the newly updated [`Cookbook`].
+    ///
+    /// [`Cookbook`]: crate::v1::types::database::Cookbook
+    #[success(message = "Successfully inserted into the database", json)]
+    Success(database::Cookbook),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to insert or update a cookbook.
+///
+/// If the UUID of an existing cookbook is given, that cookbook is replaced.
+#[post("/cookbook")]
+pub async fn insert(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    body: web::Json<RequestCookbook>,
+) -> impl Responder {
+    trace!("Attempting to insert cookbook.");
+    // Important endpoint. Check for authorization before allowing
+    // access to insert data.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return CookbookResponse::InvalidAuth;
+    }
+
+    // Convert the request cookbook to a database cookbook.
+    let mut cookbook = match body.into_inner().into_cookbook() {
+        Ok(cookbook) => cookbook,
+        Err(err) => {
+            trace!(
+                "Could not insert cookbook due to invalid request body: {:?}",
+                err
+            );
+            return CookbookResponse::InvalidRequest(err);
+        }
+    };
+
+    // Every recipe in the cookbook must exist.
+    match missing_recipes(&client, &cookbook).await {
+        Ok(violations) if violations.is_empty() => {}
+        Ok(violations) => return CookbookResponse::InvalidRequest(violations),
+        Err(e) => {
+            return CookbookResponse::InternalError(id_error!(
+                "Error getting cookbook recipes from database: {:?}",
+                e
+            ));
+        }
+    }
+
+    let cookbook_uuid = cookbook.uuid;
+    let db = client.get_collection::<database::Cookbook>(Collections::Cookbooks);
+
+    // Check if we are overwriting an existing cookbook.
+    match db.find_one(doc! {"_id": cookbook_uuid}, None).await {
+        Ok(Some(existing)) => cookbook.inherit(&existing),
+        Ok(None) => {}
+        Err(e) => {
+            return CookbookResponse::InternalError(id_error!(
+                "Error getting cookbook from database: {:?}",
+                e
+            ));
+        }
+    }
+
+    let options = mongodb::options::ReplaceOptions::builder()
+        .upsert(true)
+        .build();
+    if let Err(e) = db
+        .replace_one(doc! {"_id": cookbook_uuid}, &cookbook, options)
+        .await
+    {
+        return CookbookResponse::InternalError(id_error!(
+            "Error inserting cookbook into database: {:?}",
+            e
+        ));
+    }
+
+    trace!("Successfully inserted/updated cookbook {}.", cookbook_uuid);
+    CookbookResponse::Success(cookbook)
+}
@@ -0,0 +1,114 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{put, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum FavouriteResponse {
+    /// If the recipe was saved, returns the favourite. If it was already
+    /// saved, returns the existing favourite.
+    #[success(message = "Successfully saved the recipe", json)]
+    Success(database::Favourite),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to save a recipe to a learner's favourites.
+///
+/// Saving a recipe that is already saved does nothing, so the request can
+/// safely be repeated.
+#[put("/learner/id/{uuid}/favourites/{recipe}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    // Get the UUIDs
+    let (path_uuid, path_recipe) = path.into_inner();
+    trace!(
+        "Attempting to save Recipe {} to favourites of Learner from UUID: {}",
+        path_recipe,
+        path_uuid
+    );
+    let uuid = match Uuid::try_from(path_uuid.clone()) {
+        Ok(uuid) => uuid,
+        Err(_) => return FavouriteResponse::InvalidUuid(path_uuid),
+    };
+    let recipe = match Uuid::try_from(path_recipe.clone()) {
+        Ok(recipe) => recipe,
+        Err(_) => return FavouriteResponse::InvalidUuid(path_recipe),
+    };
+
+    // Only the learner's account can change their favourites.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return FavouriteResponse::InvalidAuth;
+        }
+        Ok(None) => return FavouriteResponse::NotFound(uuid),
+        Err(err) => {
+            return FavouriteResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    // Unreleased and deleted recipes are treated as if they do not exist.
+    let recipes = client.get_collection::<database::Recipe>(Collections::Recipes);
+    match recipes.find_one(doc! {"_id": recipe}, None).await {
+        Ok(Some(found)) if can_view(&found, None) => {}
+        Ok(_) => return FavouriteResponse::NotFound(recipe),
+        Err(err) => {
+            return FavouriteResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    }
+
+    // The unique index on favourites means only the first save is stored.
+    let db = client.get_collection::<database::Favourite>(Collections::Favourites);
+    let favourite = database::Favourite::new(uuid, recipe);
+    match db.insert_one(&favourite, None).await {
+        Ok(_) => FavouriteResponse::Success(favourite),
+        Err(err) if is_duplicate_key_error(&err) => {
+            match db
+                .find_one(doc! {"learner": uuid, "recipe": recipe}, None)
+                .await
+            {
+                Ok(Some(existing)) => FavouriteResponse::Success(existing),
+                // Removed again straight after it was found to be saved.
+                Ok(None) => FavouriteResponse::NotFound(recipe),
+                Err(err) => FavouriteResponse::InternalError(id_error!(
+                    "Error getting favourite from database: {}",
+                    err
+                )),
+            }
+        }
+        Err(err) => FavouriteResponse::InternalError(id_error!(
+            "Error inserting favourite into database: {:?}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,117 @@
+use crate::id_error;
+use crate::v1::favourite::public_recipes;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum FavouritesResponse {
+    /// Returns the learner's favourite recipes, most recently saved first.
+    #[success(json)]
+    Recipes(Vec<BasicRecipe>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Error with request: {}")]
+    #[status_code(400)]
+    RequestError(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Lists a learner's favourite recipes, a page at a time.
+///
+/// Favourites of recipes that have since been deleted are left out, so a
+/// page may have fewer recipes than asked for.
+#[get("/learner/id/{uuid}/favourites")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    page: web::Query<PageQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get favourites of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return FavouritesResponse::InvalidUuid(path_uuid),
+    };
+    if let Err(e) = page.validate() {
+        return FavouritesResponse::RequestError(e);
+    }
+
+    // Only the learner's account can see their favourites.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return FavouritesResponse::InvalidAuth;
+        }
+        Ok(None) => return FavouritesResponse::NotFound(uuid),
+        Err(err) => {
+            return FavouritesResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    let recipes = match favourite_recipes(&client, uuid, &page).await {
+        Ok(recipes) => recipes,
+        Err(err) => {
+            return FavouritesResponse::InternalError(id_error!(
+                "Error getting learner's favourites from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut basic_recipes = vec![];
+    for recipe in recipes {
+        basic_recipes.push(BasicRecipe::from_recipe(&recipe, &weekly_cacher).await);
+    }
+    FavouritesResponse::Recipes(basic_recipes)
+}
+
+/// Returns the recipes on a page of a learner's favourites, most recently
+/// saved first.
+async fn favourite_recipes(
+    client: &mongodb::Client,
+    learner: Uuid,
+    page: &PageQuery,
+) -> Result<Vec<database::Recipe>, mongodb::error::Error> {
+    let mut cursor = client
+        .get_collection::<database::Favourite>(Collections::Favourites)
+        .find(
+            doc! {"learner": learner},
+            page.find_options(doc! {"date": -1}),
+        )
+        .await?;
+    let mut uuids = vec![];
+    while cursor.advance().await? {
+        uuids.push(cursor.deserialize_current()?.recipe);
+    }
+
+    public_recipes(client, &uuids).await
+}
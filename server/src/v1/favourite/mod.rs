@@ -0,0 +1,38 @@
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_web::Scope;
+use mongodb::bson::doc;
+
+pub mod add;
+pub mod list;
+pub mod remove;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(add::uuid)
+        .service(remove::uuid)
+        .service(list::uuid)
+}
+
+/// Gets the recipes with `uuids` that can be shown to the public, in the
+/// same order. Recipes that have been deleted or are not yet released are
+/// left out.
+pub async fn public_recipes(
+    client: &mongodb::Client,
+    uuids: &[Uuid],
+) -> Result<Vec<database::Recipe>, mongodb::error::Error> {
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let mut cursor = db
+        .find(only_public(doc! {"_id": {"$in": uuids.to_vec()}}), None)
+        .await?;
+
+    let mut recipes = vec![];
+    while cursor.advance().await? {
+        recipes.push(cursor.deserialize_current()?);
+    }
+    // The database returns the recipes in any order.
+    recipes.sort_by_key(|recipe: &database::Recipe| {
+        uuids.iter().position(|uuid| *uuid == recipe.uuid)
+    });
+    Ok(recipes)
+}
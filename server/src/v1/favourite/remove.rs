@@ -0,0 +1,87 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{delete, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum RemoveResponse {
+    /// If the recipe was removed from the favourites, returns its UUID.
+    #[success(message = "Successfully removed the recipe from the favourites", json)]
+    Success(Uuid),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if there is no learner with the UUID, or the recipe is not
+    /// in their favourites.
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to remove a recipe from a learner's favourites.
+#[delete("/learner/id/{uuid}/favourites/{recipe}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    // Get the UUIDs
+    let (path_uuid, path_recipe) = path.into_inner();
+    trace!(
+        "Attempting to remove Recipe {} from favourites of Learner from UUID: {}",
+        path_recipe,
+        path_uuid
+    );
+    let uuid = match Uuid::try_from(path_uuid.clone()) {
+        Ok(uuid) => uuid,
+        Err(_) => return RemoveResponse::InvalidUuid(path_uuid),
+    };
+    let recipe = match Uuid::try_from(path_recipe.clone()) {
+        Ok(recipe) => recipe,
+        Err(_) => return RemoveResponse::InvalidUuid(path_recipe),
+    };
+
+    // Only the learner's account can change their favourites.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return RemoveResponse::InvalidAuth;
+        }
+        Ok(None) => return RemoveResponse::NotFound(uuid),
+        Err(err) => {
+            return RemoveResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    let db = client.get_collection::<database::Favourite>(Collections::Favourites);
+    match db
+        .delete_one(doc! {"learner": uuid, "recipe": recipe}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 1 => RemoveResponse::Success(recipe),
+        Ok(_) => RemoveResponse::NotFound(recipe),
+        Err(err) => RemoveResponse::InternalError(id_error!(
+            "Error deleting favourite from database: {}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,79 @@
+use crate::id_error;
+use crate::v1::feed::published_recipes;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Either, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use tracing::trace;
+
+/// The most recently released recipes included in the feeds.
+const FEED_LIMIT: i64 = 50;
+
+#[derive(ActixApiEnum)]
+enum FeedResponse {
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// An RSS 2.0 feed of the newest public recipes and the weekly recipe.
+#[get("/feed/rss.xml")]
+pub async fn rss(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+) -> Either<FeedResponse, HttpResponse> {
+    trace!("Generating RSS feed.");
+    let (site_url, items) = match feed(&client, &weekly_cacher).await {
+        Ok(feed) => feed,
+        Err(err) => return Either::Left(err),
+    };
+
+    Either::Right(
+        HttpResponse::Ok()
+            .content_type("application/rss+xml")
+            .body(render_rss(&site_url, req.full_url().as_str(), &items)),
+    )
+}
+
+/// An Atom feed of the newest public recipes and the weekly recipe.
+#[get("/feed/atom.xml")]
+pub async fn atom(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+) -> Either<FeedResponse, HttpResponse> {
+    trace!("Generating Atom feed.");
+    let (site_url, items) = match feed(&client, &weekly_cacher).await {
+        Ok(feed) => feed,
+        Err(err) => return Either::Left(err),
+    };
+
+    Either::Right(
+        HttpResponse::Ok()
+            .content_type("application/atom+xml")
+            .body(render_atom(&site_url, req.full_url().as_str(), &items)),
+    )
+}
+
+/// Gets the site URL and the entries shared by both feeds.
+///
+/// Embargoed recipes are left out by both the database query and
+/// [`feed_items`], so they can never be leaked through a feed.
+async fn feed(
+    client: &mongodb::Client,
+    weekly_cacher: &WeeklyRecipeGetter,
+) -> Result<(String, Vec<FeedItem>), FeedResponse> {
+    let site_url = site_url().map_err(|err| FeedResponse::InternalError(id_error!("{}", err)))?;
+    let recipes = published_recipes(client, Some(FEED_LIMIT))
+        .await
+        .map_err(|err| {
+            FeedResponse::InternalError(id_error!("Error getting recipes from database: {}", err))
+        })?;
+    // The feeds still work without the weekly recipe.
+    let weekly = weekly_cacher.get().await.ok();
+
+    let items = feed_items(&site_url, &recipes, weekly.as_ref());
+    Ok((site_url, items))
+}
@@ -0,0 +1,38 @@
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_web::Scope;
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+
+pub mod feeds;
+pub mod sitemap;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(sitemap::sitemap)
+        .service(feeds::rss)
+        .service(feeds::atom)
+}
+
+/// Gets the public recipes, most recently released first.
+///
+/// If `limit` is set, only that many recipes are returned.
+pub async fn published_recipes(
+    client: &mongodb::Client,
+    limit: Option<i64>,
+) -> Result<Vec<database::Recipe>, mongodb::error::Error> {
+    let find_options = FindOptions::builder()
+        .sort(doc! { "becomesPublic": -1, "_id": 1 })
+        .limit(limit)
+        .build();
+    let mut cursor = client
+        .get_collection::<database::Recipe>(Collections::Recipes)
+        .find(only_public(doc! {}), find_options)
+        .await?;
+
+    let mut recipes = vec![];
+    while cursor.advance().await? {
+        recipes.push(cursor.deserialize_current()?);
+    }
+    Ok(recipes)
+}
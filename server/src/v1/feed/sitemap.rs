@@ -0,0 +1,43 @@
+use crate::id_error;
+use crate::v1::feed::published_recipes;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Either, HttpResponse};
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum SitemapResponse {
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// An XML sitemap of every public recipe on the website.
+///
+/// Unreleased and deleted recipes are never included.
+#[get("/sitemap.xml")]
+pub async fn sitemap(client: web::Data<mongodb::Client>) -> Either<SitemapResponse, HttpResponse> {
+    trace!("Generating sitemap.");
+    let site_url = match site_url() {
+        Ok(site_url) => site_url,
+        Err(err) => {
+            return Either::Left(SitemapResponse::InternalError(id_error!("{}", err)));
+        }
+    };
+    let recipes = match published_recipes(&client, None).await {
+        Ok(recipes) => recipes,
+        Err(err) => {
+            return Either::Left(SitemapResponse::InternalError(id_error!(
+                "Error getting recipes from database: {}",
+                err
+            )));
+        }
+    };
+
+    Either::Right(
+        HttpResponse::Ok()
+            .content_type("application/xml")
+            .body(render_sitemap(&site_url, &recipes)),
+    )
+}
@@ -1,9 +1,19 @@
 use actix_web::Scope;
 
+mod account;
+mod achievement;
+mod bulk;
+mod cook_log;
+mod cookbook;
+mod favourite;
+mod feed;
 mod index;
+mod points;
 mod recipe;
+mod revision;
 mod search;
 pub mod types;
+mod upload;
 pub mod utils;
 
 /// This trait allows for simpler creation of service generators.
@@ -133,6 +143,16 @@ impl Router for Scope {
 pub fn init(scope: Scope) -> Scope {
     scope
         .service(index::get)
+        .service_generator(account::init)
+        .service_generator(achievement::init)
+        .service_generator(bulk::init)
+        .service_generator(cook_log::init)
+        .service_generator(cookbook::init)
+        .service_generator(favourite::init)
+        .service_generator(feed::init)
+        .service_generator(points::init)
         .service_generator(recipe::init)
+        .service_generator(revision::init)
         .service_generator(search::init)
+        .service_generator(upload::init)
 }
@@ -0,0 +1,109 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum AdjustResponse {
+    /// If the adjustment was made, returns the entry added to the ledger.
+    #[success(message = "Successfully adjusted the learner's points", json)]
+    Success(database::PointsEntry),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if the user provided an invalid request body.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// An adjustment to a learner's points, sent in the request body.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestAdjustment {
+    /// The number of points to give. Negative to take points away.
+    amount: i32,
+    /// Why the adjustment was made. Max 200 chars.
+    reason: String,
+}
+
+/// A request by staff to give or take away a learner's points.
+///
+/// The ledger can not be edited, so mistakes are corrected by adding an
+/// adjustment that undoes them.
+#[post("/learner/id/{uuid}/points/adjustment")]
+pub async fn insert(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    body: web::Json<RequestAdjustment>,
+) -> impl Responder {
+    let author = request_author(&req);
+    // Important endpoint. Check for authorization before allowing
+    // access to change points.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return AdjustResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to adjust points of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return AdjustResponse::InvalidUuid(path_uuid),
+    };
+
+    match learner_account(&client, uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return AdjustResponse::NotFound(uuid),
+        Err(err) => {
+            return AdjustResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    let body = body.into_inner();
+    let entry = match database::PointsEntry::adjustment(uuid, body.amount, body.reason, author) {
+        Ok(entry) => entry,
+        Err(err) => return AdjustResponse::InvalidRequest(err),
+    };
+
+    if let Err(e) = client
+        .get_collection::<database::PointsEntry>(Collections::Points)
+        .insert_one(&entry, None)
+        .await
+    {
+        return AdjustResponse::InternalError(id_error!(
+            "Error inserting points entry into database: {:?}",
+            e
+        ));
+    }
+
+    trace!("Adjusted points of learner {} by {}.", uuid, entry.amount);
+    AdjustResponse::Success(entry)
+}
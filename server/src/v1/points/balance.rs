@@ -0,0 +1,85 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use tracing::trace;
+
+/// The number of points a learner has.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Balance {
+    /// The UUID of the learner.
+    learner: Uuid,
+    /// The sum of every entry in the learner's ledger.
+    balance: i64,
+}
+
+#[derive(ActixApiEnum)]
+enum BalanceResponse {
+    #[success(json)]
+    Balance(Balance),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Gets the number of points a learner has.
+#[get("/learner/id/{uuid}/points")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get points of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return BalanceResponse::InvalidUuid(path_uuid),
+    };
+
+    // Only the learner's account can see their points.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return BalanceResponse::InvalidAuth;
+        }
+        Ok(None) => return BalanceResponse::NotFound(uuid),
+        Err(err) => {
+            return BalanceResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    match points_balance(&client, uuid).await {
+        Ok(balance) => BalanceResponse::Balance(Balance {
+            learner: uuid,
+            balance,
+        }),
+        Err(err) => BalanceResponse::InternalError(id_error!(
+            "Error getting learner's points from database: {}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum HistoryResponse {
+    /// Returns the entries of the learner's ledger, newest first.
+    #[success(json)]
+    Entries(Vec<database::PointsEntry>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Error with request: {}")]
+    #[status_code(400)]
+    RequestError(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Lists the entries of a learner's points ledger, a page at a time.
+#[get("/learner/id/{uuid}/points/history")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    page: web::Query<PageQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get points history of Learner from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return HistoryResponse::InvalidUuid(path_uuid),
+    };
+    if let Err(e) = page.validate() {
+        return HistoryResponse::RequestError(e);
+    }
+
+    // Only the learner's account can see their points.
+    match learner_account(&client, uuid).await {
+        Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+        Ok(Some(_)) => {
+            trace!("Invalid authorization attempt.");
+            return HistoryResponse::InvalidAuth;
+        }
+        Ok(None) => return HistoryResponse::NotFound(uuid),
+        Err(err) => {
+            return HistoryResponse::InternalError(id_error!(
+                "Error getting learner's account from database: {}",
+                err
+            ));
+        }
+    }
+
+    let db = client.get_collection::<database::PointsEntry>(Collections::Points);
+    let options = page.find_options(doc! {"date": -1});
+    let mut cursor = match db.find(doc! {"learner": uuid}, options).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return HistoryResponse::InternalError(id_error!(
+                "Error getting learner's points from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut entries = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(entry) => entries.push(entry),
+                Err(err) => {
+                    return HistoryResponse::InternalError(id_error!(
+                        "Error deserializing points entry: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return HistoryResponse::InternalError(id_error!(
+                    "Error getting learner's points from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    HistoryResponse::Entries(entries)
+}
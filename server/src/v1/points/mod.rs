@@ -0,0 +1,12 @@
+use actix_web::Scope;
+
+pub mod adjust;
+pub mod balance;
+pub mod history;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(balance::uuid)
+        .service(history::uuid)
+        .service(adjust::insert)
+}
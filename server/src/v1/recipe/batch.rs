@@ -0,0 +1,243 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{post, web, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+/// The most recipes that can be fetched in a single batch.
+const MAX_BATCH_ITEMS: usize = 50;
+
+/// How much of each recipe to return.
+#[derive(Debug, Default, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchDetail {
+    /// Returns a [`BasicRecipe`] for each recipe.
+    #[default]
+    Basic,
+    /// Returns a full [`Recipe`] for each recipe.
+    Full,
+}
+
+/// A request to fetch several recipes at once.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    /// The UUIDs or shorts of the recipes to fetch. Max 50.
+    ids: Vec<String>,
+    /// How much of each recipe to return. Defaults to `basic`.
+    #[serde(default)]
+    detail: BatchDetail,
+}
+
+/// A recipe fetched in a batch, in as much detail as was asked for.
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+enum BatchRecipe {
+    Basic(BasicRecipe),
+    Full(Recipe),
+}
+
+/// The result of fetching a single recipe in a batch.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchItem {
+    /// The UUID or short that was asked for.
+    id: String,
+    /// Whether a public recipe has the UUID or short.
+    found: bool,
+    /// The current short of the recipe. None if it was not found.
+    ///
+    /// If a recipe was asked for by a short it used to have, this is the
+    /// short it has now, so the caller can update its links.
+    short: Option<String>,
+    /// The recipe. None if it was not found.
+    recipe: Option<BatchRecipe>,
+}
+
+#[derive(ActixApiEnum)]
+enum BatchResponse {
+    /// Returns an item for each UUID or short, in the order they were asked
+    /// for.
+    #[success(json)]
+    Recipes(Vec<BatchItem>),
+    /// Returns if more recipes were asked for than can be fetched at once.
+    /// Contains the number that were asked for.
+    #[failure(message = "Too many recipes requested: {}. Please limit to 50.")]
+    #[status_code(400)]
+    TooManyItems(usize),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// Fetches several recipes by their UUIDs or shorts in a single request.
+///
+/// Unreleased and deleted recipes, and UUIDs or shorts that belong to no
+/// recipe, are returned as not found rather than failing the whole batch.
+/// Recipes asked for by a short they used to have are still found, and
+/// each item contains the short the recipe has now.
+#[post("/recipes/batch")]
+pub async fn batch(
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    body: web::Json<BatchRequest>,
+) -> impl Responder {
+    let request = body.into_inner();
+    trace!("Batch request got for {} recipes.", request.ids.len());
+
+    if request.ids.len() > MAX_BATCH_ITEMS {
+        return BatchResponse::TooManyItems(request.ids.len());
+    }
+
+    // Anything that is not a UUID is taken to be a short.
+    let mut uuids = vec![];
+    let mut shorts = vec![];
+    for id in &request.ids {
+        match Uuid::try_from(id.clone()) {
+            Ok(uuid) => uuids.push(uuid),
+            Err(_) => shorts.push(id.clone()),
+        }
+    }
+
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let query = only_public(doc! {
+        "$or": [
+            { "_id": { "$in": uuids } },
+            { "short": { "$in": &shorts } },
+            // The recipe may have been renamed.
+            { "previousShorts": { "$in": shorts } },
+        ]
+    });
+    let mut cursor = match db.find(query, None).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return BatchResponse::InternalError(id_error!(
+                "Error getting recipes from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut recipes = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(recipe) => recipes.push(recipe),
+                Err(err) => {
+                    return BatchResponse::InternalError(id_error!(
+                        "Error deserializing recipe: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return BatchResponse::InternalError(id_error!(
+                    "Error getting recipes from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    // Look up the weekly recipe once for the whole batch.
+    let weekly = weekly_cacher.get().await.ok().map(|weekly| weekly.uuid);
+
+    let items = request
+        .ids
+        .into_iter()
+        .map(|id| {
+            let recipe = find_requested(&recipes, &id);
+            let short = recipe.map(|recipe| recipe.short.clone());
+            let recipe = recipe.map(|recipe| {
+                let is_weekly = weekly == Some(recipe.uuid);
+                match request.detail {
+                    BatchDetail::Basic => {
+                        BatchRecipe::Basic(BasicRecipe::from_recipe_with_weekly(recipe, is_weekly))
+                    }
+                    BatchDetail::Full => {
+                        BatchRecipe::Full(Recipe::from_recipe_with_weekly(recipe, is_weekly))
+                    }
+                }
+            });
+            BatchItem {
+                id,
+                found: recipe.is_some(),
+                short,
+                recipe,
+            }
+        })
+        .collect();
+
+    BatchResponse::Recipes(items)
+}
+
+/// Finds the recipe asked for by `id`, a UUID or a short.
+///
+/// A recipe that has the short now is preferred over one that used to
+/// have it.
+fn find_requested<'a>(recipes: &'a [database::Recipe], id: &str) -> Option<&'a database::Recipe> {
+    match Uuid::try_from(id.to_string()) {
+        Ok(uuid) => recipes.iter().find(|recipe| recipe.uuid == uuid),
+        Err(_) => recipes
+            .iter()
+            .find(|recipe| recipe.short == id)
+            .or_else(|| {
+                recipes
+                    .iter()
+                    .find(|recipe| recipe.previous_shorts.iter().any(|short| short == id))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the recipe in `tests/golden/minimal.recipe.json`, with a
+    /// different UUID, short and previous shorts.
+    fn recipe(uuid: &str, short: &str, previous_shorts: &[&str]) -> database::Recipe {
+        database::fixture_recipe(|json| {
+            json["_id"] = uuid.into();
+            json["short"] = short.into();
+            json["previousShorts"] = previous_shorts.into();
+        })
+    }
+
+    #[test]
+    fn finds_recipes_by_uuid_or_short() {
+        let recipes = [
+            recipe("7a3e9c42-5b1d-4f08-8c6e-2d9b0a4f1e37", "toast", &[]),
+            recipe("0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f", "soup", &[]),
+        ];
+        let found = find_requested(&recipes, "0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f");
+        assert_eq!(found.unwrap().short, "soup");
+        assert_eq!(find_requested(&recipes, "toast").unwrap().short, "toast");
+        assert!(find_requested(&recipes, "cake").is_none());
+    }
+
+    #[test]
+    fn finds_renamed_recipes_by_previous_short() {
+        let recipes = [
+            recipe(
+                "7a3e9c42-5b1d-4f08-8c6e-2d9b0a4f1e37",
+                "buttered-toast",
+                &["toast"],
+            ),
+            recipe("0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f", "soup", &["broth"]),
+        ];
+        let found = find_requested(&recipes, "broth").unwrap();
+        assert_eq!(found.short, "soup");
+
+        // A recipe that has the short now is preferred.
+        let recipes = [
+            recipes[0].clone(),
+            recipe("1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f", "toast", &[]),
+        ];
+        let found = find_requested(&recipes, "toast").unwrap();
+        assert_eq!(found.short, "toast");
+    }
+}
@@ -0,0 +1,125 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{delete, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum DeleteResponse {
+    /// If the recipe was deleted, returns its UUID.
+    #[success(message = "Successfully deleted the recipe", json)]
+    Success(Uuid),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if there is no recipe with the UUID, or it is already deleted.
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// Returns if the recipe was modified while being deleted.
+    /// Contains the revision that was being modified.
+    #[failure(
+        message = "The recipe was modified while being deleted. Please try again.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(u32),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to delete a recipe.
+///
+/// The recipe is only marked as deleted, hiding it from every read path.
+/// It can be restored with `POST /recipe/id/{uuid}/restore` until it is
+/// purged with the `--purge-deleted` command.
+#[delete("/recipe/id/{uuid}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to delete data.
+    let author = request_author(&req);
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return DeleteResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to delete Recipe from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return DeleteResponse::InvalidUuid(path_uuid),
+    };
+
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let recipe = match db.find_one(doc! {"_id": uuid, "deleted": null}, None).await {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return DeleteResponse::NotFound(uuid),
+        Err(err) => {
+            return DeleteResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    // This counts as a modification, so the current version is kept so it
+    // can be rolled back to, and the revision is incremented.
+    let revision = recipe.revision;
+    if let Err(err) = archive_revision(&client, recipe).await {
+        return DeleteResponse::InternalError(id_error!(
+            "Error archiving recipe revision: {:?}",
+            err
+        ));
+    }
+    let result = db
+        .update_one(
+            revision_filter(uuid, revision),
+            doc! {
+                "$set": {
+                    "deleted": Date::now().ms() as i64,
+                    "lastModified": Date::now().ms() as i64,
+                    "lastModifiedBy": author,
+                },
+                "$inc": { "revision": 1 },
+            },
+            None,
+        )
+        .await;
+
+    match result {
+        Ok(result) if result.matched_count == 0 => {
+            trace!("Recipe {} was modified while deleting it.", uuid);
+            DeleteResponse::Conflict(revision)
+        }
+        Ok(_) => {
+            // The deleted recipe may have been the weekly recipe.
+            weekly_cacher.invalidate();
+            trace!("Successfully deleted recipe {}.", uuid);
+            DeleteResponse::Success(uuid)
+        }
+        Err(err) => DeleteResponse::InternalError(id_error!(
+            "Error deleting recipe from database: {:?}",
+            err
+        )),
+    }
+}
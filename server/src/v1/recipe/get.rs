@@ -1,3 +1,4 @@
+use crate::v1::recipe::RecipeQuery;
 use crate::v1::types::*;
 use crate::v1::utils::*;
 use actix_api_macros::*;
@@ -16,6 +17,10 @@ enum RecipeResponse {
     InvalidUuid(String),
     #[failure(message = "The specified UUID was not found.", json)]
     NotFound(Uuid),
+    /// Returns if the recipe was asked to be scaled to 0 servings.
+    #[failure(message = "Servings must be at least 1.")]
+    #[status_code(400)]
+    InvalidServings,
     #[failure(message = "Internal server error.", json)]
     InternalError(Uuid),
 }
@@ -25,7 +30,13 @@ pub async fn uuid(
     client: web::Data<mongodb::Client>,
     weekly_cacher: web::Data<std::sync::Arc<WeeklyRecipeGetter>>,
     path_uuid: web::Path<String>,
+    query: web::Query<PreviewQuery>,
+    options: web::Query<RecipeQuery>,
 ) -> impl Responder {
+    if options.servings == Some(0) {
+        return RecipeResponse::InvalidServings;
+    }
+
     // Get the UUID
     let path_uuid = path_uuid.into_inner();
     let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
@@ -39,8 +50,9 @@ pub async fn uuid(
     let db = client.get_collection::<database::Recipe>(Collections::Recipes);
     let recipe = db.find_one(doc! {"_id": uuid}, None).await;
     let recipe = match recipe {
-        Ok(Some(recipe)) => recipe,
-        Ok(None) => return RecipeResponse::NotFound(uuid),
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, query.preview.as_deref()) => recipe,
+        Ok(_) => return RecipeResponse::NotFound(uuid),
         Err(err) => {
             let err_id = Uuid::random();
             error!(
@@ -51,6 +63,8 @@ pub async fn uuid(
         }
     };
 
-    // Convert from db::Recipe to BasicRecipe and return.
-    RecipeResponse::Recipe(Recipe::from_recipe(&recipe, &weekly_cacher).await)
+    // Convert from db::Recipe to Recipe, scaling and converting it if
+    // asked, and return.
+    let recipe = Recipe::from_recipe(&recipe, &weekly_cacher).await;
+    RecipeResponse::Recipe(options.apply(recipe))
 }
@@ -27,6 +27,7 @@ pub async fn uuid(
     client: web::Data<mongodb::Client>,
     weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
     path_uuid: web::Path<String>,
+    query: web::Query<PreviewQuery>,
 ) -> impl Responder {
     // Get the UUID
     let path_uuid = path_uuid.into_inner();
@@ -41,8 +42,9 @@ pub async fn uuid(
     let db = client.get_collection::<database::Recipe>(Collections::Recipes);
     let recipe = db.find_one(doc! {"_id": uuid}, None).await;
     let recipe = match recipe {
-        Ok(Some(recipe)) => recipe,
-        Ok(None) => return BasicRecipeResponse::NotFound(uuid),
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, query.preview.as_deref()) => recipe,
+        Ok(_) => return BasicRecipeResponse::NotFound(uuid),
         Err(err) => {
             return BasicRecipeResponse::InternalError(id_error!(
                 "Error getting recipe from database: {}",
@@ -1,36 +1,113 @@
+use crate::id_error;
+use crate::v1::recipe::RecipeQuery;
 use crate::v1::types::*;
 use crate::v1::utils::*;
 use actix_api_macros::*;
-use actix_web::{get, web, Responder};
+use actix_web::{get, web, HttpRequest, Responder};
 use mongodb::bson::doc;
 use tracing::{error, trace};
 
+/// Where a recipe that has been renamed can now be found.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Moved {
+    /// The UUID of the recipe.
+    uuid: Uuid,
+    /// The current short of the recipe.
+    short: String,
+}
+
 #[derive(ActixApiEnum)]
 enum RecipeResponse {
     #[success(json)]
     Recipe(Recipe),
+    /// Returns if the short used to belong to a recipe that has since been
+    /// renamed. The `Location` header points at the recipe's current short.
+    #[success(message = "The recipe has moved to a new short.", json)]
+    #[status_code(301)]
+    Moved(Moved),
     #[failure(message = "The specified Short was not found.", json)]
     NotFound(String),
+    /// Returns if the recipe was asked to be scaled to 0 servings.
+    #[failure(message = "Servings must be at least 1.")]
+    #[status_code(400)]
+    InvalidServings,
     #[failure(message = "Internal server error.", json)]
     InternalError(Uuid),
 }
 
 #[get("/recipe/short/{uuid}")]
 pub async fn short(
+    req: HttpRequest,
     client: web::Data<mongodb::Client>,
     weekly_cacher: web::Data<std::sync::Arc<WeeklyRecipeGetter>>,
     path_short: web::Path<String>,
+    query: web::Query<PreviewQuery>,
+    options: web::Query<RecipeQuery>,
 ) -> impl Responder {
-    // Get the short
-    let short = path_short.into_inner();
-    trace!("Attempting to get Recipe from Short: {}", &short);
+    let response = match options.servings {
+        Some(0) => RecipeResponse::InvalidServings,
+        _ => {
+            find_short(
+                &client,
+                &weekly_cacher,
+                path_short.into_inner(),
+                query.preview.as_deref(),
+                &options,
+            )
+            .await
+        }
+    };
+
+    // Redirects need to point the client at the new short, which
+    // the response enum has no way of doing itself.
+    let location = match &response {
+        RecipeResponse::Moved(moved) => Some(moved_location(&req, &moved.short)),
+        _ => None,
+    };
+    let mut response = response.customize();
+    if let Some(location) = location {
+        response = response.insert_header(("Location", location));
+    }
+    response
+}
+
+/// Gets the recipe with the short, or where it has moved to.
+async fn find_short(
+    client: &mongodb::Client,
+    weekly_cacher: &WeeklyRecipeGetter,
+    path_short: String,
+    preview: Option<&str>,
+    options: &RecipeQuery,
+) -> RecipeResponse {
+    trace!("Attempting to get Recipe from Short: {}", &path_short);
 
     // Get the recipe from the database
     let db = client.get_collection::<database::Recipe>(Collections::Recipes);
-    let recipe = db.find_one(doc! {"short": &short}, None).await;
+    let recipe = db
+        .find_one(doc! {"short": &path_short, "deleted": null}, None)
+        .await;
     let recipe = match recipe {
-        Ok(Some(recipe)) => recipe,
-        Ok(None) => return RecipeResponse::NotFound(short),
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, preview) => recipe,
+        Ok(Some(_)) => return RecipeResponse::NotFound(path_short),
+        Ok(None) => {
+            // The recipe may have been renamed.
+            return match db
+                .find_one(doc! {"previousShorts": &path_short}, None)
+                .await
+            {
+                Ok(Some(recipe)) if can_view(&recipe, preview) => RecipeResponse::Moved(Moved {
+                    uuid: recipe.uuid,
+                    short: recipe.short.clone(),
+                }),
+                Ok(_) => RecipeResponse::NotFound(path_short),
+                Err(err) => RecipeResponse::InternalError(id_error!(
+                    "Error getting recipe from database: {}",
+                    err
+                )),
+            };
+        }
         Err(err) => {
             let err_id = Uuid::random();
             error!(
@@ -41,6 +118,21 @@ pub async fn short(
         }
     };
 
-    // Convert from db::Recipe to BasicRecipe and return.
-    RecipeResponse::Recipe(Recipe::from_recipe(&recipe, &weekly_cacher).await)
+    // Convert from db::Recipe to Recipe, scaling and converting it if
+    // asked, and return.
+    let recipe = Recipe::from_recipe(&recipe, weekly_cacher).await;
+    RecipeResponse::Recipe(options.apply(recipe))
+}
+
+/// Constructs the URL of the same request, but for the new short.
+///
+/// The query string is kept so preview tokens and other options
+/// carry over to the redirected request.
+fn moved_location(req: &HttpRequest, new_short: &str) -> String {
+    let path = req.path();
+    let base = path.rsplit_once('/').map_or("", |(base, _)| base);
+    match req.query_string() {
+        "" => format!("{}/{}", base, new_short),
+        query => format!("{}/{}?{}", base, new_short, query),
+    }
 }
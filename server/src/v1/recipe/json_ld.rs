@@ -0,0 +1,72 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Either, HttpResponse};
+use mongodb::bson::doc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum JsonLdResponse {
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    #[status_code(400)]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Gets a public recipe as schema.org `Recipe` JSON-LD, for the website to
+/// embed so search engines can show rich results.
+///
+/// Only public recipes are described, as search engines should never see
+/// unreleased recipes.
+#[get("/recipe/id/{uuid}/json-ld")]
+pub async fn uuid(
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> Either<JsonLdResponse, HttpResponse> {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get JSON-LD of Recipe from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return Either::Left(JsonLdResponse::InvalidUuid(path_uuid)),
+    };
+
+    let site_url = match site_url() {
+        Ok(site_url) => site_url,
+        Err(err) => return Either::Left(JsonLdResponse::InternalError(id_error!("{}", err))),
+    };
+
+    // Get the recipe from the database
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let recipe = match db.find_one(doc! {"_id": uuid}, None).await {
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, None) => recipe,
+        Ok(_) => return Either::Left(JsonLdResponse::NotFound(uuid)),
+        Err(err) => {
+            return Either::Left(JsonLdResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            )));
+        }
+    };
+
+    let url = recipe_url(&site_url, &recipe.short);
+    Either::Right(
+        HttpResponse::Ok()
+            .content_type("application/ld+json")
+            .json(RecipeJsonLd::from_recipe(&recipe, url)),
+    )
+}
@@ -1,32 +1,90 @@
 use crate::v1::types::*;
 use actix_web::Scope;
+use std::collections::HashMap;
 
+pub mod batch;
+pub mod delete;
 pub mod get;
 pub mod get_basic;
 pub mod get_short;
+pub mod json_ld;
+pub mod patch;
 pub mod post;
+pub mod preview;
+pub mod quiz;
+pub mod related;
+pub mod restore;
 pub mod weekly;
 
 pub fn init(scope: Scope) -> Scope {
     scope
         .service(post::insert)
+        .service(batch::batch)
+        .service(patch::uuid)
+        .service(delete::uuid)
+        .service(restore::uuid)
+        .service(preview::uuid)
         .service(get_basic::uuid)
         .service(get_short::short)
+        .service(related::uuid)
+        .service(json_ld::uuid)
+        .service(quiz::uuid)
         .service(get::uuid)
         .service(weekly::uuid)
 }
 
+/// The query parameters that change how a recipe is shown.
+#[derive(Debug, serde::Deserialize)]
+pub struct RecipeQuery {
+    /// The number of servings to scale the recipe to. If not set, the
+    /// recipe is returned as it was written.
+    pub servings: Option<u16>,
+    /// The units to convert the recipe to. If not set, the recipe is
+    /// returned in the units it was written in.
+    pub units: Option<UnitSystem>,
+    /// The format to also return the formattable text of the recipe in.
+    /// If not set, it is only returned as it was written.
+    pub markup: Option<MarkupFormat>,
+    /// The seed to shuffle the quiz options with. If not set, they are
+    /// shuffled randomly, and the seed used is returned with the quiz.
+    pub seed: Option<u64>,
+}
+
+impl RecipeQuery {
+    /// Scales, converts, shuffles and renders the recipe as asked.
+    pub fn apply(&self, mut recipe: Recipe) -> Recipe {
+        if let Some(servings) = self.servings {
+            recipe = recipe.scaled(servings);
+        }
+        if let Some(units) = self.units {
+            recipe = recipe.converted(units);
+        }
+        if let Some(seed) = self.seed {
+            recipe = recipe.with_quiz_seed(seed);
+        }
+        if let Some(markup) = self.markup {
+            recipe = recipe.with_markup(markup);
+        }
+        recipe
+    }
+}
+
 /// The type of recipe sent in the request body.
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestRecipe {
     /// The unique identifier of the recipe.
     /// If set, overwrites the recipe with the same id.
     uuid: Option<Uuid>,
+    /// The revision of the recipe this request was based on.
+    /// Required when overwriting a recipe, and must match the stored
+    /// revision or the write is rejected.
+    revision: Option<u32>,
     /// The date the recipe was added to the database.
     /// If not set, defaults to the time the request was made.
     date_added: Option<Date>,
-    /// The date the recipe will/went public. If not yet that date, can only be referred to by id.
+    /// The date the recipe will/went public. If not yet that date, can only be
+    /// viewed with a preview token.
     /// If not set, defaults to the time the request was made.
     becomes_public: Option<Date>,
     /// The staff who helped make this recipe.
@@ -40,7 +98,15 @@ pub struct RequestRecipe {
     /// The short title of the recipe.
     short: Option<String>,
     /// A list of common nutrients found in the recipe.
+    /// Ignored if `nutrition` is set, as they are derived from it.
     nutrients: Option<Vec<SerdeStringNutrient>>,
+    /// The amount of each nutrient in a single serving, keyed by the name
+    /// of the nutrient.
+    nutrition: Option<HashMap<String, f64>>,
+    /// The allergens the recipe contains.
+    allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for.
+    diets: Option<Vec<Diet>>,
     /// The time to cook the recipe, in minutes
     time_to_cook: Option<u16>,
     /// The servings of the recipe.
@@ -50,7 +116,7 @@ pub struct RequestRecipe {
     /// The gradient of the recipe.
     gradient: Option<Gradient>,
     /// The ingredients of the recipe.
-    ingredients: Option<Vec<String>>,
+    ingredients: Option<Vec<Ingredient>>,
     /// The recipe's method
     method: Option<database::Method>,
     /// The quiz information for the end of the recipe
@@ -59,7 +125,9 @@ pub struct RequestRecipe {
 
 impl RequestRecipe {
     /// Tries to convert a RequestRecipe into a [`Recipe`].
-    pub fn into_recipe(self) -> Result<database::Recipe, String> {
+    ///
+    /// Returns every field that breaks a limit of the recipe if it fails.
+    pub fn into_recipe(self) -> Result<database::Recipe, Vec<Violation>> {
         let mut builder = database::Recipe::builder();
 
         /// Expands to ```if let Some(value) = self.[field] {
@@ -87,6 +155,8 @@ impl RequestRecipe {
         if_some!(image, image);
         if_some!(gradient, gradient);
         if_some!(ingredients, ingredients);
+        if_some!(allergens, allergens);
+        if_some!(diets, diets);
         if_some!(method, method);
         if_some!(quiz, quiz);
 
@@ -99,7 +169,145 @@ impl RequestRecipe {
             );
         }
 
+        let mut violations = vec![];
+        if let Some(nutrition) = self.nutrition {
+            match NutritionFacts::from_names(nutrition, "nutrition") {
+                Ok(nutrition) => builder = builder.nutrition(nutrition),
+                Err(mut errors) => violations.append(&mut errors),
+            }
+        }
+
         // Set the remaining Vec fields, then build and return.
+        match builder.build() {
+            Ok(recipe) if violations.is_empty() => Ok(recipe),
+            Ok(_) => Err(violations),
+            Err(mut errors) => {
+                violations.append(&mut errors);
+                Err(violations)
+            }
+        }
+    }
+}
+
+/// The partial recipe sent in the body of a `PATCH` request.
+///
+/// Only the fields that are set are changed on the stored recipe. Fields that
+/// can be removed, such as `credits`, are removed when set to `null`.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchRecipe {
+    /// The revision of the recipe this patch was based on.
+    /// If set, the patch is rejected if the recipe has since been modified.
+    revision: Option<u32>,
+    /// The date the recipe will/went public.
+    becomes_public: Option<Date>,
+    /// The staff who helped make this recipe.
+    authors: Option<Vec<Uuid>>,
+    /// A short string crediting the creators of the recipe.
+    #[serde(default, deserialize_with = "database::double_option")]
+    credits: Option<Option<Formattable>>,
+    /// The date the recipe went weekly.
+    #[serde(default, deserialize_with = "database::double_option")]
+    weekly_timestamp: Option<Option<Date>>,
+    /// The title of the recipe.
+    title: Option<String>,
+    /// The short title of the recipe.
+    short: Option<String>,
+    /// A list of common nutrients found in the recipe.
+    /// Ignored if `nutrition` is set, as they are derived from it.
+    nutrients: Option<Vec<SerdeStringNutrient>>,
+    /// The amount of each nutrient in a single serving, keyed by the name
+    /// of the nutrient.
+    nutrition: Option<HashMap<String, f64>>,
+    /// The allergens the recipe contains.
+    allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for.
+    diets: Option<Vec<Diet>>,
+    /// The time to cook the recipe, in minutes
+    time_to_cook: Option<u16>,
+    /// The servings of the recipe.
+    servings: Option<u16>,
+    /// The URL to the recipe image. Should be on S3
+    image: Option<Url>,
+    /// The gradient of the recipe.
+    gradient: Option<Gradient>,
+    /// The ingredients of the recipe.
+    ingredients: Option<Vec<Ingredient>>,
+    /// The changes to the recipe's method.
+    method: Option<database::MethodPatch>,
+    /// The changes to the quiz at the end of the recipe.
+    quiz: Option<database::QuizPatch>,
+}
+
+impl PatchRecipe {
+    /// Applies the patch onto a stored recipe, validating the result
+    /// in the same way as a newly inserted recipe.
+    pub fn apply(self, recipe: database::Recipe) -> Result<database::Recipe, Vec<Violation>> {
+        use database::Patch;
+
+        let mut violations = vec![];
+        let mut method = recipe.method.clone();
+        if let Some(patch) = self.method {
+            patch.apply(&mut method, "method", &mut violations);
+        }
+        let mut quiz = recipe.quiz.clone();
+        if let Some(patch) = self.quiz {
+            patch.apply(&mut quiz, "quiz", &mut violations);
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        let mut builder = database::RecipeBuilder::from(recipe)
+            .method(method)
+            .quiz(quiz);
+
+        /// Expands to ```if let Some(value) = self.[field] {
+        ///     builder.[setter](value);
+        /// }```
+        macro_rules! if_some(
+            ($field:tt, $setter:ident) => {
+                if let Some(value) = self.$field {
+                    builder = builder.$setter(value);
+                }
+            };
+        );
+
+        if_some!(becomes_public, becomes_public);
+        if_some!(authors, set_authors);
+        if_some!(short, short);
+        if_some!(title, title);
+        if_some!(time_to_cook, time_to_cook);
+        if_some!(servings, servings);
+        if_some!(image, image);
+        if_some!(gradient, gradient);
+        if_some!(ingredients, ingredients);
+        if_some!(allergens, allergens);
+        if_some!(diets, diets);
+
+        builder = match self.credits {
+            Some(Some(credits)) => builder.credits(credits),
+            Some(None) => builder.remove_credits(),
+            None => builder,
+        };
+        builder = match self.weekly_timestamp {
+            Some(Some(weekly_timestamp)) => builder.weekly_timestamp(weekly_timestamp),
+            Some(None) => builder.remove_weekly_timestamp(),
+            None => builder,
+        };
+
+        if let Some(nutrients) = self.nutrients {
+            builder = builder.nutrients(
+                nutrients
+                    .iter()
+                    .map(|nutrient| nutrient.into_nutrient())
+                    .collect(),
+            );
+        }
+        if let Some(nutrition) = self.nutrition {
+            builder = builder.nutrition(NutritionFacts::from_names(nutrition, "nutrition")?);
+        }
+
         builder.build()
     }
 }
@@ -0,0 +1,180 @@
+use crate::id_error;
+use crate::v1::recipe::PatchRecipe;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{patch, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+#[allow(clippy::large_enum_variant)]
+enum RecipeResponse {
+    /// If the patch was successful, returns the newly updated [`Recipe`].
+    ///
+    /// [`Recipe`]: crate::v1::types::database::Recipe
+    #[success(message = "Successfully updated the recipe", json)]
+    Success(database::Recipe),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// Returns if the patch could not be applied or the patched recipe
+    /// is not valid.
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    /// Returns if the recipe was modified since the revision the patch
+    /// was based on. Contains the current revision of the recipe.
+    #[failure(
+        message = "The recipe has been modified since it was last fetched. Fetch the recipe again and retry with the current revision.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(u32),
+    /// Returns if another recipe took the recipe's short while it was being
+    /// written. Contains the short.
+    #[failure(
+        message = "The short `{}` was taken by another recipe while saving. Please try again."
+    )]
+    #[status_code(409)]
+    ShortTaken(String),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to update only some fields of a recipe.
+///
+/// The patch is applied onto the stored recipe, and the result is validated
+/// in the same way as a recipe sent to `POST /recipe`.
+#[patch("/recipe/id/{uuid}")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    body: web::Json<PatchRecipe>,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to modify data.
+    let author = request_author(&req);
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return RecipeResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to patch Recipe from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RecipeResponse::InvalidUuid(path_uuid),
+    };
+
+    // Get the stored recipe from the database
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let recipe = match db.find_one(doc! {"_id": uuid, "deleted": null}, None).await {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return RecipeResponse::NotFound(uuid),
+        Err(err) => {
+            return RecipeResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    let body = body.into_inner();
+    let stored_revision = recipe.revision;
+    if let Some(expected_revision) = body.revision {
+        if expected_revision != stored_revision {
+            trace!(
+                "Rejecting stale patch to recipe {}. Expected revision {}, found {}.",
+                uuid,
+                expected_revision,
+                stored_revision
+            );
+            return RecipeResponse::Conflict(stored_revision);
+        }
+    }
+
+    // Apply the patch and validate the result.
+    let previous = recipe.clone();
+    let mut recipe = match body.apply(recipe) {
+        Ok(recipe) => recipe,
+        Err(err) => {
+            trace!(
+                "Could not patch recipe due to invalid request body: {:?}",
+                err
+            );
+            return RecipeResponse::InvalidRequest(err);
+        }
+    };
+    recipe.set_revision(stored_revision + 1);
+    recipe.set_last_modified(author);
+    recipe.inherit_shorts(&previous);
+    match unique_short(&client, uuid, &recipe.short).await {
+        Ok(short) => recipe.set_short(short),
+        Err(err) => {
+            return RecipeResponse::InternalError(id_error!(
+                "Error finding a unique short: {:?}",
+                err
+            ));
+        }
+    }
+
+    // Keep the current version so it can be rolled back to.
+    if let Err(err) = archive_revision(&client, previous).await {
+        return RecipeResponse::InternalError(id_error!(
+            "Error archiving recipe revision: {:?}",
+            err
+        ));
+    }
+
+    // Only replace the recipe if it was not modified since it was fetched.
+    let result = db
+        .replace_one(revision_filter(uuid, stored_revision), &recipe, None)
+        .await;
+    match result {
+        Ok(result) if result.matched_count == 0 => {
+            trace!("Recipe {} was modified during the patch.", uuid);
+            match db.find_one(doc! {"_id": uuid}, None).await {
+                Ok(Some(current)) => RecipeResponse::Conflict(current.revision),
+                Ok(None) => RecipeResponse::NotFound(uuid),
+                Err(err) => RecipeResponse::InternalError(id_error!(
+                    "Error getting recipe from database: {}",
+                    err
+                )),
+            }
+        }
+        Ok(_) => {
+            // The patch may have changed the weekly timestamp.
+            weekly_cacher.invalidate();
+            trace!("Successfully patched recipe {}.", uuid);
+            RecipeResponse::Success(recipe)
+        }
+        // The only unique field other than the UUID is the short.
+        Err(err) if is_duplicate_key_error(&err) => {
+            RecipeResponse::ShortTaken(recipe.short.clone())
+        }
+        Err(err) => RecipeResponse::InternalError(id_error!(
+            "Error replacing recipe in database: {:?}",
+            err
+        )),
+    }
+}
@@ -1,8 +1,10 @@
+use crate::id_error;
 use crate::v1::recipe::RequestRecipe;
 use crate::v1::{types::*, utils::*};
 use actix_api_macros::*;
 use actix_web::{post, web, HttpRequest, Responder};
 use mongodb::bson::doc;
+use std::sync::Arc;
 use tracing::{error, trace};
 
 #[derive(ActixApiEnum)]
@@ -20,9 +22,30 @@ enum RecipeResponse {
     #[status_code(401)]
     InvalidAuth,
     /// Returns if the user provided an invalid request body.
-    #[failure(message = "Invalid request body: `{}`.")]
+    /// Contains every field that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
     #[status_code(400)]
-    InvalidRequest(String),
+    InvalidRequest(Vec<Violation>),
+    /// Returns if the recipe was modified since the revision the request
+    /// was based on. Contains the current revision of the recipe.
+    #[failure(
+        message = "The recipe has been modified since it was last fetched. Fetch the recipe again and retry with the current revision.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(u32),
+    /// Returns if another recipe took the recipe's short while it was being
+    /// written. Contains the short.
+    #[failure(
+        message = "The short `{}` was taken by another recipe while saving. Please try again."
+    )]
+    #[status_code(409)]
+    ShortTaken(String),
+    /// Returns if the recipe being overwritten has been deleted.
+    /// It must be restored before it can be overwritten.
+    #[failure(message = "The recipe `{}` has been deleted. Restore it before overwriting it.")]
+    #[status_code(409)]
+    Deleted(Uuid),
     /// In the event an issue in the server occured, returns this error.
     /// Contains a UUID that can be used to identify the issue.
     #[failure(message = "Internal server error. Error UUID: `{}`")]
@@ -31,26 +54,35 @@ enum RecipeResponse {
 }
 
 /// A request to insert or update a recipe.
+///
+/// If the UUID of an existing recipe is given, that recipe is replaced.
+/// The request must then contain the revision of the recipe it was based on,
+/// and is rejected with a `409` if the recipe has since been modified.
 #[post("/recipe")]
 pub async fn insert(
     req: HttpRequest,
     client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
     body: web::Json<RequestRecipe>,
 ) -> impl Responder {
     trace!("Attempting to insert recipe.");
     // Important endpoint. Check for authorization before allowing
     // access to insert data.
+    let author = request_author(&req);
     if check_user_auth(req).is_err() {
         trace!("Invalid authorization attempt.");
         return RecipeResponse::InvalidAuth;
     }
 
+    let body = body.into_inner();
+    let expected_revision = body.revision;
+
     // Convert the request recipe to a database recipe.
-    let recipe = match body.into_inner().into_recipe() {
+    let mut recipe = match body.into_recipe() {
         Ok(recipe) => recipe,
         Err(err) => {
             trace!(
-                "Could not insert recipe due to invalid request body: {}",
+                "Could not insert recipe due to invalid request body: {:?}",
                 err
             );
             return RecipeResponse::InvalidRequest(err);
@@ -59,25 +91,106 @@ pub async fn insert(
 
     // Get the UUID here so we can use it later to get the entry.
     let recipe_uuid = *recipe.uuid();
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+
+    // Check if we are overwriting an existing recipe.
+    let existing = match db.find_one(doc! {"_id": recipe_uuid}, None).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            return RecipeResponse::InternalError(id_error!(
+                "Error getting recipe from database: {:?}",
+                e
+            ));
+        }
+    };
+
+    let result = match existing {
+        Some(existing) if existing.deleted.is_some() => {
+            return RecipeResponse::Deleted(recipe_uuid);
+        }
+        Some(existing) => {
+            // Reject the write if it was not based on the stored revision.
+            if expected_revision != Some(existing.revision) {
+                trace!(
+                    "Rejecting stale write to recipe {}. Expected revision {:?}, found {}.",
+                    recipe_uuid,
+                    expected_revision,
+                    existing.revision
+                );
+                return RecipeResponse::Conflict(existing.revision);
+            }
+
+            recipe.inherit_shorts(&existing);
+            if let Err(e) = make_short_unique(&client, &mut recipe).await {
+                return RecipeResponse::InternalError(id_error!(
+                    "Error finding a unique short: {:?}",
+                    e
+                ));
+            }
 
-    // Insert into the database.
-    let result = client
-        .get_collection(Collections::Recipes)
-        .insert_one(recipe, None)
-        .await;
+            // Keep the current version so it can be rolled back to.
+            let existing_revision = existing.revision;
+            if let Err(e) = archive_revision(&client, existing).await {
+                return RecipeResponse::InternalError(id_error!(
+                    "Error archiving recipe revision: {:?}",
+                    e
+                ));
+            }
+
+            // Only replace the recipe if its revision is still the same.
+            // Another write may have happened since it was fetched above.
+            recipe.set_revision(existing_revision + 1);
+            recipe.set_last_modified(author);
+            let short = recipe.short.clone();
+            match db
+                .replace_one(
+                    revision_filter(recipe_uuid, existing_revision),
+                    recipe,
+                    None,
+                )
+                .await
+            {
+                Ok(result) if result.matched_count == 0 => {
+                    trace!("Recipe {} was modified during the write.", recipe_uuid);
+                    return conflict(&db, recipe_uuid, short).await;
+                }
+                // The only unique field other than the UUID is the short.
+                Err(e) if is_duplicate_key_error(&e) => {
+                    return RecipeResponse::ShortTaken(short);
+                }
+                result => result.map(|_| ()),
+            }
+        }
+        None => {
+            if let Err(e) = make_short_unique(&client, &mut recipe).await {
+                return RecipeResponse::InternalError(id_error!(
+                    "Error finding a unique short: {:?}",
+                    e
+                ));
+            }
+            recipe.set_last_modified(author);
+            let short = recipe.short.clone();
+            match db.insert_one(recipe, None).await {
+                // Another request inserted the recipe since we checked.
+                Err(e) if is_duplicate_key_error(&e) => {
+                    trace!("Recipe {} was inserted during the write.", recipe_uuid);
+                    return conflict(&db, recipe_uuid, short).await;
+                }
+                result => result.map(|_| ()),
+            }
+        }
+    };
 
     if let Err(e) = result {
         let error_uuid = Uuid::random();
         error!("Error UUID: {}\n{:?}", error_uuid, e);
         return RecipeResponse::InternalError(error_uuid);
     }
+    // The recipe may have a new weekly timestamp.
+    weekly_cacher.invalidate();
 
     // Get the recipe from the database.
-    let result = client
-        .database("recipe_db")
-        .collection("recipes")
-        .find_one(doc! {"_id": recipe_uuid}, None)
-        .await;
+    let result = db.find_one(doc! {"_id": recipe_uuid}, None).await;
 
     let recipe = match result {
         Ok(Some(recipe)) => recipe,
@@ -99,3 +212,33 @@ pub async fn insert(
     trace!("Successfully inserted/updated recipe {}.", recipe_uuid);
     RecipeResponse::Success(recipe)
 }
+
+/// Makes the short of the recipe unique between all recipes.
+async fn make_short_unique(
+    client: &mongodb::Client,
+    recipe: &mut database::Recipe,
+) -> Result<(), mongodb::error::Error> {
+    let short = unique_short(client, recipe.uuid, &recipe.short).await?;
+    recipe.set_short(short);
+    Ok(())
+}
+
+/// Constructs the response for a write that conflicted with another write
+/// made while this one was in progress.
+///
+/// If the recipe exists, it was written to by the other request, so a
+/// [`RecipeResponse::Conflict`] is returned. Otherwise, the other request took
+/// the recipe's short.
+async fn conflict(
+    db: &mongodb::Collection<database::Recipe>,
+    recipe_uuid: Uuid,
+    short: String,
+) -> RecipeResponse {
+    match db.find_one(doc! {"_id": recipe_uuid}, None).await {
+        Ok(Some(recipe)) => RecipeResponse::Conflict(recipe.revision),
+        Ok(None) => RecipeResponse::ShortTaken(short),
+        Err(e) => {
+            RecipeResponse::InternalError(id_error!("Error getting recipe from database: {:?}", e))
+        }
+    }
+}
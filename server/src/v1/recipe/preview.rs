@@ -0,0 +1,109 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use tracing::trace;
+
+/// The default number of hours a preview token is valid for.
+const DEFAULT_EXPIRY_HOURS: u64 = 72;
+/// The maximum number of hours a preview token can be valid for.
+const MAX_EXPIRY_HOURS: u64 = 24 * 30;
+
+/// The query parameters when creating a preview token.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewTokenQuery {
+    /// The number of hours the token is valid for. Defaults to 72.
+    expires_in: Option<u64>,
+}
+
+/// A newly created preview token.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedToken {
+    /// The token to pass as the `preview` query parameter.
+    token: String,
+    /// The date the token stops working.
+    expires: Date,
+}
+
+#[derive(ActixApiEnum)]
+enum PreviewResponse {
+    #[success(json)]
+    Token(CreatedToken),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Invalid `expiresIn`: Please limit to between 1 and {} hours.")]
+    InvalidExpiry(u64),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Creates a preview token that lets anyone with it view an unreleased recipe
+/// by passing it as the `preview` query parameter, until the token expires.
+#[post("/recipe/id/{uuid}/preview")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+    query: web::Query<PreviewTokenQuery>,
+) -> impl Responder {
+    // Important endpoint. Only staff may hand out access to
+    // unreleased recipes.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return PreviewResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to create preview token for Recipe: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return PreviewResponse::InvalidUuid(path_uuid),
+    };
+
+    let hours = query.expires_in.unwrap_or(DEFAULT_EXPIRY_HOURS);
+    if hours == 0 || hours > MAX_EXPIRY_HOURS {
+        return PreviewResponse::InvalidExpiry(MAX_EXPIRY_HOURS);
+    }
+
+    // Only create tokens for recipes that exist.
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    match db.find_one(doc! {"_id": uuid, "deleted": null}, None).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return PreviewResponse::NotFound(uuid),
+        Err(err) => {
+            return PreviewResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    }
+
+    let expires = Date::now() + Date::new(hours * 1000 * 60 * 60);
+    match PreviewToken::new(uuid, expires).sign() {
+        Ok(token) => PreviewResponse::Token(CreatedToken { token, expires }),
+        Err(err) => {
+            PreviewResponse::InternalError(id_error!("Error signing preview token: {}", err))
+        }
+    }
+}
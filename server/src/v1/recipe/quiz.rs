@@ -0,0 +1,191 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::{error, trace};
+
+/// The grade of a quiz attempt made by a learner.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordedQuiz {
+    /// The grade of each question and the reward earned.
+    #[serde(flatten)]
+    result: QuizResult,
+    /// The UUID of the recorded attempt.
+    attempt: Uuid,
+    /// The points given to the learner, for the rewards no earlier attempt
+    /// earned.
+    points: u32,
+    /// The achievements the attempt unlocked.
+    achievements: Vec<database::Achievement>,
+}
+
+#[derive(ActixApiEnum)]
+enum QuizResponse {
+    /// Returns the grade of each question and the reward earned, without
+    /// the solutions.
+    #[success(json)]
+    Graded(QuizResult),
+    /// Returns if the quiz was attempted by a learner, along with the
+    /// points they were given.
+    #[success(json)]
+    Recorded(RecordedQuiz),
+    /// Returns if the user provided an invalid authorization token for the
+    /// learner.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if the user provided an invalid request body.
+    /// Contains every answer that is invalid, with why it is invalid.
+    #[failure(message = "Invalid request body.", json)]
+    #[status_code(400)]
+    InvalidRequest(Vec<Violation>),
+    #[failure(message = "The specified UUID was not found.", json)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// The query parameters of a quiz attempt.
+#[derive(Debug, serde::Deserialize)]
+pub struct QuizQuery {
+    /// A preview token, to attempt the quiz of an unreleased recipe.
+    preview: Option<String>,
+    /// The learner attempting the quiz. If not set, the attempt is only
+    /// graded.
+    learner: Option<Uuid>,
+}
+
+/// Grades answers to the quiz of a recipe.
+///
+/// The option IDs are the ones sent with the recipe's quiz. A quiz can be
+/// retried as many times as wanted. If a learner attempts it, the attempt
+/// is recorded, and they are given each reward the first time they earn
+/// it, which needs their account's token. Any achievements the attempt
+/// unlocks are returned.
+///
+/// The option IDs never change, so the solutions are only returned once a
+/// learner has answered every question correctly, and has nothing left to
+/// earn with them.
+#[post("/recipe/id/{uuid}/quiz")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    query: web::Query<QuizQuery>,
+    body: web::Json<QuizSubmission>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to grade quiz of Recipe from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return QuizResponse::InvalidUuid(path_uuid),
+    };
+
+    // Only the learner's account can attempt quizzes for them.
+    if let Some(learner) = query.learner {
+        match learner_account(&client, learner).await {
+            Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+            Ok(Some(_)) => {
+                trace!("Invalid authorization attempt.");
+                return QuizResponse::InvalidAuth;
+            }
+            Ok(None) => return QuizResponse::NotFound(learner),
+            Err(err) => {
+                return QuizResponse::InternalError(id_error!(
+                    "Error getting learner's account from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    // Get the recipe from the database
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let recipe = match db.find_one(doc! {"_id": uuid}, None).await {
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, query.preview.as_deref()) => recipe,
+        Ok(_) => return QuizResponse::NotFound(uuid),
+        Err(err) => {
+            return QuizResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut result = match body.grade(&recipe.quiz, recipe.uuid) {
+        Ok(result) => result,
+        Err(violations) => return QuizResponse::InvalidRequest(violations),
+    };
+    let learner = match query.learner {
+        Some(learner) => learner,
+        None => {
+            result.hide_solutions();
+            return QuizResponse::Graded(result);
+        }
+    };
+
+    // Achievements can ask for the weekly recipe to be completed while it is
+    // the weekly recipe.
+    let weekly = match weekly_cacher.get().await {
+        Ok(weekly) if weekly.uuid == recipe.uuid => weekly.weekly_timestamp,
+        _ => None,
+    };
+    let attempt = match record_quiz_attempt(&client, learner, recipe.uuid, weekly, &result).await {
+        Ok(attempt) => attempt,
+        Err(err) => {
+            return QuizResponse::InternalError(id_error!("Error recording quiz attempt: {}", err));
+        }
+    };
+
+    match quiz_mastered(&client, learner, recipe.uuid).await {
+        Ok(true) => {}
+        Ok(false) => result.hide_solutions(),
+        Err(err) => {
+            error!(
+                "Error checking quiz attempts of learner {}: {}",
+                learner, err
+            );
+            result.hide_solutions();
+        }
+    }
+
+    // The attempt has already been recorded, so it is still returned if the
+    // achievements can not be checked. They are checked again on the
+    // learner's next attempt.
+    let event = database::LearnerEvent::QuizAttempted(&attempt);
+    let achievements = match unlock_achievements(&client, event).await {
+        Ok(achievements) => achievements,
+        Err(err) => {
+            error!(
+                "Error unlocking achievements of learner {}: {}",
+                learner, err
+            );
+            vec![]
+        }
+    };
+
+    QuizResponse::Recorded(RecordedQuiz {
+        result,
+        attempt: attempt.uuid,
+        points: attempt.points,
+        achievements,
+    })
+}
@@ -0,0 +1,149 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, Responder};
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+use std::sync::Arc;
+use tracing::trace;
+
+/// The most related recipes returned.
+const RELATED_LIMIT: usize = 6;
+
+#[derive(ActixApiEnum)]
+enum RelatedResponse {
+    #[success(json)]
+    Recipes(Vec<BasicRecipe>),
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    InternalError(Uuid),
+}
+
+/// Gets the public recipes most related to a recipe, most related first.
+///
+/// See [`rank_related`] for how recipes are ranked. The ranking only
+/// changes when recipes are written, so it can be cached.
+#[get("/recipe/id/{uuid}/related")]
+pub async fn uuid(
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+    query: web::Query<PreviewQuery>,
+) -> impl Responder {
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to get related Recipes from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RelatedResponse::InvalidUuid(path_uuid),
+    };
+
+    // Get the recipe from the database
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let source = match db.find_one(doc! {"_id": uuid}, None).await {
+        // Unreleased and deleted recipes are treated as if they do not exist.
+        Ok(Some(recipe)) if can_view(&recipe, query.preview.as_deref()) => recipe,
+        Ok(_) => return RelatedResponse::NotFound(uuid),
+        Err(err) => {
+            return RelatedResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    // Every other public recipe is a candidate, so only the fields needed
+    // to rank them are read.
+    let options = FindOptions::builder()
+        .projection(RelatedRecipe::projection())
+        .build();
+    let mut cursor = match client
+        .get_collection::<RelatedRecipe>(Collections::Recipes)
+        .find(only_public(doc! {"_id": {"$ne": uuid}}), options)
+        .await
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return RelatedResponse::InternalError(id_error!(
+                "Error getting recipes from database: {}",
+                err
+            ));
+        }
+    };
+    let mut candidates = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(recipe) => candidates.push(recipe),
+                Err(err) => {
+                    return RelatedResponse::InternalError(id_error!(
+                        "Error deserializing recipe: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return RelatedResponse::InternalError(id_error!(
+                    "Error getting recipes from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    let ranked: Vec<Uuid> = rank_related(&RelatedRecipe::from(&source), &candidates, RELATED_LIMIT)
+        .iter()
+        .map(|recipe| recipe.uuid)
+        .collect();
+
+    // Get the whole of only the recipes that are returned.
+    let mut cursor = match db.find(doc! {"_id": {"$in": ranked.clone()}}, None).await {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return RelatedResponse::InternalError(id_error!(
+                "Error getting recipes from database: {}",
+                err
+            ));
+        }
+    };
+    let mut recipes = vec![];
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(recipe) => recipes.push(recipe),
+                Err(err) => {
+                    return RelatedResponse::InternalError(id_error!(
+                        "Error deserializing recipe: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return RelatedResponse::InternalError(id_error!(
+                    "Error getting recipes from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    // Keep the order of the ranking.
+    let mut related = vec![];
+    for uuid in ranked {
+        if let Some(recipe) = recipes.iter().find(|recipe| recipe.uuid == uuid) {
+            related.push(BasicRecipe::from_recipe(recipe, &weekly_cacher).await);
+        }
+    }
+
+    RelatedResponse::Recipes(related)
+}
@@ -0,0 +1,124 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum RestoreResponse {
+    /// If the recipe was restored, returns its UUID.
+    #[success(message = "Successfully restored the recipe", json)]
+    Success(Uuid),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    /// Returns if there is no deleted recipe with the UUID.
+    #[failure(message = "No deleted recipe with the specified UUID was found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// Returns if the recipe was modified while being restored.
+    /// Contains the revision that was being modified.
+    #[failure(
+        message = "The recipe was modified while being restored. Please try again.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(u32),
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// A request to restore a deleted recipe that has not yet been purged.
+#[post("/recipe/id/{uuid}/restore")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to modify data.
+    let author = request_author(&req);
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return RestoreResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!("Attempting to restore Recipe from UUID: {}", path_uuid);
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RestoreResponse::InvalidUuid(path_uuid),
+    };
+
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let recipe = match db
+        .find_one(doc! {"_id": uuid, "deleted": { "$ne": null }}, None)
+        .await
+    {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return RestoreResponse::NotFound(uuid),
+        Err(err) => {
+            return RestoreResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    // This counts as a modification, so the current version is kept so it
+    // can be rolled back to, and the revision is incremented.
+    let revision = recipe.revision;
+    if let Err(err) = archive_revision(&client, recipe).await {
+        return RestoreResponse::InternalError(id_error!(
+            "Error archiving recipe revision: {:?}",
+            err
+        ));
+    }
+    let result = db
+        .update_one(
+            revision_filter(uuid, revision),
+            doc! {
+                "$set": {
+                    "deleted": null,
+                    "lastModified": Date::now().ms() as i64,
+                    "lastModifiedBy": author,
+                },
+                "$inc": { "revision": 1 },
+            },
+            None,
+        )
+        .await;
+
+    match result {
+        Ok(result) if result.matched_count == 0 => {
+            trace!("Recipe {} was modified while restoring it.", uuid);
+            RestoreResponse::Conflict(revision)
+        }
+        Ok(_) => {
+            // The restored recipe may be the weekly recipe.
+            weekly_cacher.invalidate();
+            trace!("Successfully restored recipe {}.", uuid);
+            RestoreResponse::Success(uuid)
+        }
+        Err(err) => RestoreResponse::InternalError(id_error!(
+            "Error restoring recipe in database: {:?}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,103 @@
+use crate::id_error;
+use crate::v1::revision::find_revision;
+use crate::v1::types::*;
+use crate::v1::utils::diff::{diff, FieldChange};
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use tracing::trace;
+
+/// The fields of a recipe that describe the revision rather than its
+/// content, so are left out of the diff.
+const REVISION_FIELDS: [&str; 5] = [
+    "revision",
+    "lastModified",
+    "lastModifiedBy",
+    "deleted",
+    "previousShorts",
+];
+
+#[derive(ActixApiEnum)]
+enum DiffResponse {
+    /// Returns every field that changed between the two revisions.
+    #[success(json)]
+    Diff(Vec<FieldChange>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Revision {} of the specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(u32, Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Compares two revisions of a recipe field by field.
+#[get("/recipe/id/{uuid}/revisions/{from}/diff/{to}")]
+pub async fn revisions(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path: web::Path<(String, u32, u32)>,
+) -> impl Responder {
+    // Revisions may contain unreleased content, so are only
+    // available to staff.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return DiffResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let (path_uuid, from, to) = path.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to diff revisions {} and {} of Recipe from UUID: {}",
+        from,
+        to,
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return DiffResponse::InvalidUuid(path_uuid),
+    };
+
+    // Get both revisions as JSON.
+    let mut values = vec![];
+    for revision in [from, to] {
+        let recipe = match find_revision(&client, uuid, revision).await {
+            Ok(Some(recipe)) => recipe,
+            Ok(None) => return DiffResponse::NotFound(revision, uuid),
+            Err(err) => {
+                return DiffResponse::InternalError(id_error!(
+                    "Error getting revision from database: {}",
+                    err
+                ));
+            }
+        };
+        let mut value = match serde_json::to_value(recipe) {
+            Ok(value) => value,
+            Err(err) => {
+                return DiffResponse::InternalError(id_error!(
+                    "Error serializing revision: {}",
+                    err
+                ));
+            }
+        };
+        if let Some(object) = value.as_object_mut() {
+            for field in REVISION_FIELDS {
+                object.remove(field);
+            }
+        }
+        values.push(value);
+    }
+
+    DiffResponse::Diff(diff(&values[0], &values[1]))
+}
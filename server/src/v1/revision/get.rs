@@ -0,0 +1,69 @@
+use crate::id_error;
+use crate::v1::revision::find_revision;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+#[allow(clippy::large_enum_variant)]
+enum RevisionResponse {
+    /// Returns the recipe as it was at the revision.
+    #[success(json)]
+    Revision(database::Recipe),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "Revision {} of the specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(u32, Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Gets a recipe as it was at a specific revision.
+#[get("/recipe/id/{uuid}/revisions/{revision}")]
+pub async fn revision(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path: web::Path<(String, u32)>,
+) -> impl Responder {
+    // Revisions may contain unreleased content, so are only
+    // available to staff.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return RevisionResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let (path_uuid, revision) = path.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to get revision {} of Recipe from UUID: {}",
+        revision,
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RevisionResponse::InvalidUuid(path_uuid),
+    };
+
+    match find_revision(&client, uuid, revision).await {
+        Ok(Some(recipe)) => RevisionResponse::Revision(recipe),
+        Ok(None) => RevisionResponse::NotFound(revision, uuid),
+        Err(err) => RevisionResponse::InternalError(id_error!(
+            "Error getting revision from database: {}",
+            err
+        )),
+    }
+}
@@ -0,0 +1,140 @@
+use crate::id_error;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{get, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use mongodb::options::FindOptions;
+use tracing::trace;
+
+/// Information about a single revision of a recipe, without its content.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RevisionSummary {
+    /// The revision number.
+    revision: u32,
+    /// The staff member who wrote the revision, if known.
+    author: Option<Uuid>,
+    /// The date the revision was written, if known.
+    timestamp: Option<Date>,
+    /// Whether this is the current revision of the recipe.
+    current: bool,
+}
+
+#[derive(ActixApiEnum)]
+enum RevisionsResponse {
+    /// Returns the revisions of the recipe, newest first.
+    #[success(json)]
+    Revisions(Vec<RevisionSummary>),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Lists every revision of a recipe, including the current one.
+#[get("/recipe/id/{uuid}/revisions")]
+pub async fn uuid(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    path_uuid: web::Path<String>,
+) -> impl Responder {
+    // Revisions may contain unreleased content, so are only
+    // available to staff.
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return RevisionsResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let path_uuid = path_uuid.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to list revisions of Recipe from UUID: {}",
+        path_uuid
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RevisionsResponse::InvalidUuid(path_uuid),
+    };
+
+    // The current revision is stored on the recipe itself.
+    let recipe = client
+        .get_collection::<database::Recipe>(Collections::Recipes)
+        .find_one(doc! {"_id": uuid}, None)
+        .await;
+    let recipe = match recipe {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return RevisionsResponse::NotFound(uuid),
+        Err(err) => {
+            return RevisionsResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    let mut revisions = vec![RevisionSummary {
+        revision: recipe.revision,
+        author: recipe.last_modified_by,
+        timestamp: recipe.last_modified,
+        current: true,
+    }];
+
+    // Get the previous revisions, newest first.
+    let find_options = FindOptions::builder().sort(doc! { "revision": -1 }).build();
+    let cursor = client
+        .get_collection::<database::RecipeRevision>(Collections::Revisions)
+        .find(doc! {"recipe": uuid}, find_options)
+        .await;
+    let mut cursor = match cursor {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return RevisionsResponse::InternalError(id_error!(
+                "Error getting revisions from database: {}",
+                err
+            ));
+        }
+    };
+
+    loop {
+        match cursor.advance().await {
+            Ok(true) => match cursor.deserialize_current() {
+                Ok(revision) => revisions.push(RevisionSummary {
+                    revision: revision.revision,
+                    author: revision.author,
+                    timestamp: revision.timestamp,
+                    current: false,
+                }),
+                Err(err) => {
+                    return RevisionsResponse::InternalError(id_error!(
+                        "Error deserializing revision: {}",
+                        err
+                    ));
+                }
+            },
+            Ok(false) => break,
+            Err(err) => {
+                return RevisionsResponse::InternalError(id_error!(
+                    "Error getting revisions from database: {}",
+                    err
+                ));
+            }
+        }
+    }
+
+    RevisionsResponse::Revisions(revisions)
+}
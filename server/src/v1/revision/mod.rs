@@ -0,0 +1,44 @@
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_web::Scope;
+use mongodb::bson::doc;
+
+pub mod diff;
+pub mod get;
+pub mod list;
+pub mod rollback;
+
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .service(list::uuid)
+        .service(get::revision)
+        .service(diff::revisions)
+        .service(rollback::revision)
+}
+
+/// Finds a recipe as it was at a specific revision.
+///
+/// The current revision is read from the recipes collection, and older
+/// revisions from the revisions collection. Returns `None` if the recipe
+/// or the revision does not exist.
+///
+/// Recipes stored before revisions were tracked are at revision 0.
+pub async fn find_revision(
+    client: &mongodb::Client,
+    uuid: Uuid,
+    revision: u32,
+) -> Result<Option<database::Recipe>, mongodb::error::Error> {
+    let current = client
+        .get_collection::<database::Recipe>(Collections::Recipes)
+        .find_one(revision_filter(uuid, revision), None)
+        .await?;
+    if current.is_some() {
+        return Ok(current);
+    }
+
+    let revision = client
+        .get_collection::<database::RecipeRevision>(Collections::Revisions)
+        .find_one(doc! {"recipe": uuid, "revision": revision as i64}, None)
+        .await?;
+    Ok(revision.map(|revision| revision.into_content()))
+}
@@ -0,0 +1,183 @@
+use crate::id_error;
+use crate::v1::revision::find_revision;
+use crate::v1::types::*;
+use crate::v1::utils::*;
+use actix_api_macros::*;
+use actix_web::{post, web, HttpRequest, Responder};
+use mongodb::bson::doc;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+#[allow(clippy::large_enum_variant)]
+enum RollbackResponse {
+    /// If the rollback was successful, returns the newly updated [`Recipe`].
+    ///
+    /// [`Recipe`]: crate::v1::types::database::Recipe
+    #[success(message = "Successfully rolled back the recipe", json)]
+    Success(database::Recipe),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    #[failure(
+        message = "The specified UUID was not valid. Expected UUIDv4 (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx).",
+        json
+    )]
+    InvalidUuid(String),
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    #[failure(message = "Revision {} of the specified UUID was not found.", json)]
+    #[status_code(404)]
+    RevisionNotFound(u32, Uuid),
+    /// Returns if the revision breaks a documented limit, such as one added
+    /// since it was written. Contains every violation.
+    #[failure(
+        message = "The revision can not be rolled back to, as it is no longer valid.",
+        json
+    )]
+    #[status_code(400)]
+    InvalidRevision(Vec<Violation>),
+    /// Returns if the recipe was modified while rolling back.
+    /// Contains the current revision of the recipe.
+    #[failure(
+        message = "The recipe was modified while rolling back. Check the changes and try again.",
+        json
+    )]
+    #[status_code(409)]
+    Conflict(u32),
+    /// Returns if another recipe took the recipe's short while it was being
+    /// written. Contains the short.
+    #[failure(
+        message = "The short `{}` was taken by another recipe while saving. Please try again."
+    )]
+    #[status_code(409)]
+    ShortTaken(String),
+    #[failure(message = "Internal server error.", json)]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+/// Restores the content of a recipe to a previous revision.
+///
+/// The rollback is written as a new revision, so it can itself be undone.
+/// The revision is checked against every documented limit again, so one
+/// that breaks limits added since it was written can not be rolled back to.
+#[post("/recipe/id/{uuid}/revisions/{revision}/rollback")]
+pub async fn revision(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    weekly_cacher: web::Data<Arc<WeeklyRecipeGetter>>,
+    path: web::Path<(String, u32)>,
+) -> impl Responder {
+    // Important endpoint. Check for authorization before allowing
+    // access to modify data.
+    let author = request_author(&req);
+    if check_user_auth(req).is_err() {
+        trace!("Invalid authorization attempt.");
+        return RollbackResponse::InvalidAuth;
+    }
+
+    // Get the UUID
+    let (path_uuid, revision) = path.into_inner();
+    let uuid: Result<Uuid, _> = path_uuid.clone().try_into();
+    trace!(
+        "Attempting to roll back Recipe from UUID {} to revision {}",
+        path_uuid,
+        revision
+    );
+    let uuid = match uuid {
+        Ok(uuid) => uuid,
+        Err(_) => return RollbackResponse::InvalidUuid(path_uuid),
+    };
+
+    // Get the current recipe. Deleted recipes must be restored first.
+    let db = client.get_collection::<database::Recipe>(Collections::Recipes);
+    let current = match db.find_one(doc! {"_id": uuid, "deleted": null}, None).await {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return RollbackResponse::NotFound(uuid),
+        Err(err) => {
+            return RollbackResponse::InternalError(id_error!(
+                "Error getting recipe from database: {}",
+                err
+            ));
+        }
+    };
+
+    // Get the content to roll back to.
+    let content = match find_revision(&client, uuid, revision).await {
+        Ok(Some(recipe)) => recipe,
+        Ok(None) => return RollbackResponse::RevisionNotFound(revision, uuid),
+        Err(err) => {
+            return RollbackResponse::InternalError(id_error!(
+                "Error getting revision from database: {}",
+                err
+            ));
+        }
+    };
+    let mut recipe = match current.rolled_back_to(content, author) {
+        Ok(recipe) => recipe,
+        Err(violations) => {
+            trace!(
+                "Could not roll back recipe {} to invalid revision {}: {:?}",
+                uuid,
+                revision,
+                violations
+            );
+            return RollbackResponse::InvalidRevision(violations);
+        }
+    };
+    match unique_short(&client, uuid, &recipe.short).await {
+        Ok(short) => recipe.set_short(short),
+        Err(err) => {
+            return RollbackResponse::InternalError(id_error!(
+                "Error finding a unique short: {:?}",
+                err
+            ));
+        }
+    }
+
+    // Keep the current version so the rollback can be undone.
+    let current_revision = current.revision;
+    if let Err(err) = archive_revision(&client, current).await {
+        return RollbackResponse::InternalError(id_error!(
+            "Error archiving recipe revision: {:?}",
+            err
+        ));
+    }
+
+    // Only replace the recipe if it was not modified since it was fetched.
+    let result = db
+        .replace_one(revision_filter(uuid, current_revision), &recipe, None)
+        .await;
+    match result {
+        Ok(result) if result.matched_count == 0 => {
+            trace!("Recipe {} was modified during the rollback.", uuid);
+            match db.find_one(doc! {"_id": uuid}, None).await {
+                Ok(Some(current)) => RollbackResponse::Conflict(current.revision),
+                Ok(None) => RollbackResponse::NotFound(uuid),
+                Err(err) => RollbackResponse::InternalError(id_error!(
+                    "Error getting recipe from database: {}",
+                    err
+                )),
+            }
+        }
+        Ok(_) => {
+            // The rollback may have changed the weekly timestamp.
+            weekly_cacher.invalidate();
+            trace!("Successfully rolled back recipe {} to {}.", uuid, revision);
+            RollbackResponse::Success(recipe)
+        }
+        // The only unique field other than the UUID is the short.
+        Err(err) if is_duplicate_key_error(&err) => {
+            RollbackResponse::ShortTaken(recipe.short.clone())
+        }
+        Err(err) => RollbackResponse::InternalError(id_error!(
+            "Error replacing recipe in database: {:?}",
+            err
+        )),
+    }
+}
@@ -4,7 +4,6 @@ use crate::v1::utils::*;
 use actix_api_macros::*;
 use actix_web::{post, web, Responder};
 use mongodb::bson::doc;
-use mongodb::options::FindOptions;
 use tracing::{error, trace};
 
 #[derive(ActixApiEnum)]
@@ -28,12 +27,9 @@ pub struct SearchRequest {
     ///
     /// If `None`, do not filter the results.
     pub whitelist: Option<Vec<Uuid>>,
-    /// The amount of results per page. Defaults to 10.
-    #[serde(default = "page_limit_default")]
-    pub page_limit: u8,
-    /// The page number. Default to 1.
-    #[serde(default = "page_number_default")]
-    pub page_number: u32,
+    /// The page of results to return.
+    #[serde(flatten)]
+    pub page: PageQuery,
     /// The nutrients to search for. If specified, the search will only return
     /// recipes that contain *any* of the specified nutrients.
     /// If you prefer, you can think of it as an "a *or* b" rather than an
@@ -41,14 +37,25 @@ pub struct SearchRequest {
     ///
     /// If `None`, do not search for any nutrients.
     pub nutrients: Option<Vec<SerdeStringNutrient>>,
-}
-
-fn page_limit_default() -> u8 {
-    10
-}
-
-fn page_number_default() -> u32 {
-    1
+    /// Only return recipes that contain *all* of these allergens.
+    ///
+    /// If `None`, do not filter by the allergens recipes contain.
+    pub include_allergens: Option<Vec<Allergen>>,
+    /// Only return recipes that contain *none* of these allergens.
+    ///
+    /// Recipes whose allergens are not known are never returned, as they
+    /// may contain any of them.
+    ///
+    /// If `None`, do not filter by the allergens recipes contain.
+    pub exclude_allergens: Option<Vec<Allergen>>,
+    /// Only return recipes that are suitable for *all* of these diets.
+    ///
+    /// If `None`, do not filter by diet.
+    pub include_diets: Option<Vec<Diet>>,
+    /// Only return recipes that are suitable for *none* of these diets.
+    ///
+    /// If `None`, do not filter by diet.
+    pub exclude_diets: Option<Vec<Diet>>,
 }
 
 #[post("/search")]
@@ -73,15 +80,13 @@ pub async fn search(
 
     // Sort by newest released, skipping to the right page,
     // taking only the right amount of results.
-    let find_options = FindOptions::builder()
-        .sort(doc! { "becomesPublic": -1 })
-        .skip(Some(
-            (search_request.page_number as u64 - 1) * (search_request.page_limit as u64),
-        ))
-        .limit(Some(search_request.page_limit as i64));
+    let find_options = search_request
+        .page
+        .find_options(doc! { "becomesPublic": -1 });
 
-    // Stores the query information.
-    let mut query_object = mongodb::bson::Document::new();
+    // Stores the query information. Deleted and unreleased recipes
+    // are never returned.
+    let mut query_object = only_public(doc! {});
 
     // Add the search query, if provided.
     if let Some(query) = search_request.query {
@@ -145,7 +150,42 @@ pub async fn search(
         );
     }
 
-    let cursor = db.find(query_object, find_options.build()).await;
+    // Add the allergens, if provided. An empty list filters nothing, rather
+    // than matching every recipe with `$all` or none with `$nin`.
+    let mut allergens_filter = doc! {};
+    if let Some(allergens) = search_request.include_allergens {
+        if !allergens.is_empty() {
+            allergens_filter.insert("$all", allergens);
+        }
+    }
+    if let Some(allergens) = search_request.exclude_allergens {
+        if !allergens.is_empty() {
+            // Recipes without a list of allergens may contain any of them.
+            allergens_filter.insert("$type", "array");
+            allergens_filter.insert("$nin", allergens);
+        }
+    }
+    if !allergens_filter.is_empty() {
+        query_object.insert("allergens", allergens_filter);
+    }
+
+    // Add the diets, if provided.
+    let mut diets_filter = doc! {};
+    if let Some(diets) = search_request.include_diets {
+        if !diets.is_empty() {
+            diets_filter.insert("$all", diets);
+        }
+    }
+    if let Some(diets) = search_request.exclude_diets {
+        if !diets.is_empty() {
+            diets_filter.insert("$nin", diets);
+        }
+    }
+    if !diets_filter.is_empty() {
+        query_object.insert("diets", diets_filter);
+    }
+
+    let cursor = db.find(query_object, find_options).await;
 
     let mut cursor = match cursor {
         Err(err) => {
@@ -196,15 +236,22 @@ fn validate_query(search_request: &SearchRequest) -> Result<(), String> {
         }
     }
 
-    if search_request.page_limit == 0 || search_request.page_limit > 20 {
-        return Err(
-            "Invalid `pageLimit`: Not within bounds. Please limit to between 1 and 20.".to_string(),
-        );
-    }
+    search_request.page.validate()
+}
 
-    if search_request.page_number == 0 {
-        return Err("Invalid `pageNumber`: Must be greater than 0.".to_string());
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_page_of_request() {
+        let request: SearchRequest = serde_json::from_str(r#"{"query": "toast"}"#).unwrap();
+        assert_eq!((request.page.page_limit, request.page.page_number), (10, 1));
+        assert!(validate_query(&request).is_ok());
 
-    Ok(())
+        let request: SearchRequest =
+            serde_json::from_str(r#"{"pageLimit": 21, "pageNumber": 2}"#).unwrap();
+        assert_eq!((request.page.page_limit, request.page.page_number), (21, 2));
+        assert!(validate_query(&request).is_err());
+    }
 }
@@ -22,6 +22,10 @@ pub struct BasicRecipe {
     pub title: String,
     /// The nutrients found in the recipe. Should be 1 - 3 long.
     pub nutrients: Vec<SerdeStringNutrient>,
+    /// The allergens the recipe contains. None if they are not known.
+    pub allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for.
+    pub diets: Vec<Diet>,
     /// The amount of time, in minutes, to cook the recipe.
     pub time_to_cook: u16,
     /// The number of servings the recipe makes.
@@ -46,6 +50,8 @@ impl BasicRecipe {
             // Convert Nutrient to SerdeStringNutrient so when sent to the
             // client it will be serialized as a string.
             nutrients: recipe.nutrients.iter().map(|&n| n.into()).collect(),
+            allergens: recipe.allergens.clone(),
+            diets: recipe.diets.clone(),
             time_to_cook: recipe.time_to_cook,
             servings: recipe.servings,
             image: recipe.image.clone(),
@@ -67,6 +73,8 @@ impl BasicRecipe {
             // Convert Nutrient to SerdeStringNutrient so when sent to the
             // client it will be serialized as a string.
             nutrients: recipe.nutrients.iter().map(|&n| n.into()).collect(),
+            allergens: recipe.allergens.clone(),
+            diets: recipe.diets.clone(),
             time_to_cook: recipe.time_to_cook,
             servings: recipe.servings,
             image: recipe.image.clone(),
@@ -0,0 +1,76 @@
+use crate::v1::types::database::Cookbook as DatabaseCookbook;
+use crate::v1::types::*;
+
+/// A cookbook, along with the recipes in it, that is sent to the client.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cookbook {
+    /// The unique identifier of the cookbook.
+    pub uuid: Uuid,
+    /// The title of the cookbook.
+    pub title: String,
+    /// A description of the cookbook.
+    pub description: Formattable,
+    /// The URL to the image of the cookbook.
+    pub image: Url,
+    /// The gradient of the cookbook.
+    pub gradient: Gradient,
+    /// The recipes in the cookbook, in the order they are shown.
+    ///
+    /// Unreleased and deleted recipes are left out.
+    pub recipes: Vec<BasicRecipe>,
+    /// The description in the format that was asked for, if it is valid
+    /// markup. Not sent unless asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_markup: Option<RenderedMarkup>,
+}
+
+impl Cookbook {
+    /// Creates a new `Cookbook` from a [`database::Cookbook`] and the
+    /// recipes in it.
+    pub fn from_cookbook(cookbook: &DatabaseCookbook, recipes: Vec<BasicRecipe>) -> Self {
+        Cookbook {
+            uuid: cookbook.uuid,
+            title: cookbook.title.clone(),
+            description: cookbook.description.clone(),
+            image: cookbook.image.clone(),
+            gradient: cookbook.gradient.clone(),
+            recipes,
+            description_markup: None,
+        }
+    }
+
+    /// Renders the description in `format`.
+    pub fn with_markup(mut self, format: MarkupFormat) -> Self {
+        self.description_markup = format.render(&self.description, &[]);
+        self
+    }
+}
+
+/// A cookbook without its recipes, used when listing every cookbook.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicCookbook {
+    /// The unique identifier of the cookbook.
+    pub uuid: Uuid,
+    /// The title of the cookbook.
+    pub title: String,
+    /// A description of the cookbook.
+    pub description: Formattable,
+    /// The URL to the image of the cookbook.
+    pub image: Url,
+    /// The gradient of the cookbook.
+    pub gradient: Gradient,
+}
+
+impl From<&DatabaseCookbook> for BasicCookbook {
+    fn from(cookbook: &DatabaseCookbook) -> Self {
+        BasicCookbook {
+            uuid: cookbook.uuid,
+            title: cookbook.title.clone(),
+            description: cookbook.description.clone(),
+            image: cookbook.image.clone(),
+            gradient: cookbook.gradient.clone(),
+        }
+    }
+}
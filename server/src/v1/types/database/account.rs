@@ -0,0 +1,119 @@
+use crate::v1::types::*;
+
+/// The maximum length of an account's or learner's name, in characters.
+const MAX_NAME_CHARS: usize = 40;
+/// The most learners an account can have.
+const MAX_LEARNERS: usize = 10;
+
+/// A parent's account, which the kids learning from recipes belong to.
+///
+/// Accounts are authorized with an [`AccountToken`], which can act for the
+/// account and every one of its learners.
+///
+/// [`AccountToken`]: crate::v1::utils::AccountToken
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Account {
+    /// The unique identifier of the account.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The date the account was created.
+    pub date_added: Date,
+    /// The name of the account, shown to the parent. Max 40 chars.
+    pub name: String,
+    /// The kids learning under the account. Max 10 learners.
+    pub learners: Vec<Learner>,
+    /// The version of the account's tokens. Incremented to revoke every
+    /// token issued before.
+    #[serde(default)]
+    pub token_version: u32,
+}
+
+/// A kid learning under an [`Account`], who earns points from quizzes.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Learner {
+    /// The unique identifier of the learner.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The date the learner was added to the account.
+    pub date_added: Date,
+    /// The name the learner goes by. Max 40 chars.
+    pub name: String,
+}
+
+impl Account {
+    /// Constructs a new account without any learners, checking its name is
+    /// within every documented limit.
+    pub fn new(name: String) -> Result<Self, Vec<Violation>> {
+        let mut violations = vec![];
+        check_name(&mut violations, "name", &name);
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        Ok(Self {
+            uuid: Uuid::random(),
+            date_added: Date::now(),
+            name,
+            learners: vec![],
+            token_version: 0,
+        })
+    }
+
+    /// Adds a new learner to the account, returning them.
+    ///
+    /// Fails if the name is not within every documented limit, or the
+    /// account already has the most learners it can have.
+    pub fn add_learner(&mut self, name: String) -> Result<&Learner, Vec<Violation>> {
+        let mut violations = vec![];
+        check_name(&mut violations, "name", &name);
+        if self.learners.len() >= MAX_LEARNERS {
+            violations.push(Violation::new(
+                "name",
+                format!("An account can have at most {} learners.", MAX_LEARNERS),
+            ));
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        self.learners.push(Learner {
+            uuid: Uuid::random(),
+            date_added: Date::now(),
+            name,
+        });
+        Ok(&self.learners[self.learners.len() - 1])
+    }
+}
+
+/// Checks a name is not empty and at most [`MAX_NAME_CHARS`].
+fn check_name(violations: &mut Vec<Violation>, path: &str, name: &str) {
+    if name.trim().is_empty() {
+        violations.push(Violation::new(path, "Must not be empty."));
+    }
+    check_max_chars(violations, path, name, MAX_NAME_CHARS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_learners() {
+        assert!(Account::new(" ".to_string()).is_err());
+        let mut account = Account::new("The Smiths".to_string()).unwrap();
+        assert!(account.add_learner("".to_string()).is_err());
+        assert!(account.add_learner("x".repeat(41)).is_err());
+
+        for i in 0..MAX_LEARNERS {
+            let name = &account.add_learner(format!("Kid {}", i)).unwrap().name;
+            assert_eq!(*name, format!("Kid {}", i));
+        }
+        let violations = account.add_learner("One too many".to_string()).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(account.learners.len(), MAX_LEARNERS);
+    }
+}
@@ -0,0 +1,438 @@
+use crate::v1::types::database::{CookLogEntry, QuizAttempt};
+use crate::v1::types::*;
+use std::collections::BTreeSet;
+
+/// The maximum length of an achievement's title, in characters.
+const MAX_TITLE_CHARS: usize = 80;
+/// The maximum length of an achievement's description, in characters.
+const MAX_DESCRIPTION_CHARS: usize = 200;
+/// The most times a rule can ask for something to be done.
+const MAX_RULE_COUNT: u32 = 1000;
+/// The most nutrients a rule can filter recipes by.
+const MAX_RULE_NUTRIENTS: usize = 10;
+
+/// An achievement learners can unlock as a badge, such as "Completed 5
+/// recipes rich in Iron".
+///
+/// What unlocks it is described by its [`AchievementRule`], so staff can add
+/// new achievements without any change to the server.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Achievement {
+    /// The unique identifier of the achievement.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The date the achievement was added to the database.
+    pub date_added: Date,
+    /// The title of the achievement. Max 80 chars.
+    pub title: String,
+    /// What must be done to unlock the achievement. Max 200 chars.
+    pub description: String,
+    /// The URL to the badge image.
+    pub image: Url,
+    /// The rule that unlocks the achievement.
+    pub rule: AchievementRule,
+}
+
+/// What a learner must do to unlock an [`Achievement`].
+///
+/// A recipe is completed by passing its quiz or cooking it. Each rule
+/// only counts a recipe once, so retrying a quiz or cooking a recipe again
+/// does not count again.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AchievementRule {
+    /// Complete `count` different recipes.
+    ///
+    /// If any `nutrients` are given, only recipes tagged with at least one
+    /// of them are counted.
+    RecipesCompleted {
+        count: u32,
+        #[serde(default)]
+        nutrients: Vec<Nutrient>,
+    },
+    /// Complete the weekly recipe, while it is the weekly recipe, for
+    /// `weeks` weekly recipes in a row.
+    WeeklyStreak { weeks: u32 },
+    /// Get every question correct in the quizzes of `count` different
+    /// recipes.
+    PerfectQuizzes { count: u32 },
+}
+
+/// Something a learner did, which can unlock achievements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearnerEvent<'a> {
+    /// The learner attempted a recipe's quiz.
+    QuizAttempted(&'a QuizAttempt),
+    /// The learner cooked a recipe.
+    RecipeCooked(&'a CookLogEntry),
+}
+
+impl LearnerEvent<'_> {
+    /// Returns the learner who did it.
+    pub fn learner(&self) -> Uuid {
+        match self {
+            LearnerEvent::QuizAttempted(attempt) => attempt.learner,
+            LearnerEvent::RecipeCooked(entry) => entry.learner,
+        }
+    }
+}
+
+/// Everything a learner has done that achievements are unlocked by.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LearnerHistory {
+    /// Every time the learner completed a recipe, in any order.
+    pub completions: Vec<Completion>,
+    /// The date every weekly recipe so far went weekly, from the first to
+    /// the current one, so streaks can tell which follow each other.
+    pub weekly_timestamps: Vec<Date>,
+}
+
+/// A single time a learner completed a recipe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The recipe that was completed.
+    pub recipe: Uuid,
+    /// The nutrients the recipe is tagged with.
+    pub nutrients: Vec<Nutrient>,
+    /// The date the recipe was completed.
+    pub date: Date,
+    /// The date the recipe went weekly, if it was the weekly recipe when it
+    /// was completed.
+    pub weekly: Option<Date>,
+    /// Whether every question of the quiz was answered correctly. Always
+    /// false if the recipe was cooked.
+    pub perfect: bool,
+}
+
+impl Achievement {
+    /// Constructs a new achievement, checking it is within every documented
+    /// limit.
+    ///
+    /// If any are broken, every violation is returned rather than just the
+    /// first.
+    pub fn new(
+        uuid: Option<Uuid>,
+        title: String,
+        description: String,
+        image: Url,
+        rule: AchievementRule,
+    ) -> Result<Self, Vec<Violation>> {
+        let mut violations = vec![];
+        if title.is_empty() {
+            violations.push(Violation::new("title", "Must not be empty."));
+        }
+        check_max_chars(&mut violations, "title", &title, MAX_TITLE_CHARS);
+        check_max_chars(
+            &mut violations,
+            "description",
+            &description,
+            MAX_DESCRIPTION_CHARS,
+        );
+        image.validate("image", &mut violations);
+        rule.validate("rule", &mut violations);
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        Ok(Self {
+            uuid: uuid.unwrap_or_else(Uuid::random),
+            date_added: Date::now(),
+            title,
+            description,
+            image,
+            rule,
+        })
+    }
+
+    /// Carries over the date the achievement was added from the stored
+    /// version it overwrites.
+    pub fn inherit(&mut self, previous: &Achievement) {
+        self.date_added = previous.date_added;
+    }
+}
+
+impl AchievementRule {
+    /// Checks the rule is within every documented limit, adding every
+    /// violation to `violations`.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        let (field, count) = match self {
+            AchievementRule::RecipesCompleted { count, .. } => ("count", *count),
+            AchievementRule::WeeklyStreak { weeks } => ("weeks", *weeks),
+            AchievementRule::PerfectQuizzes { count } => ("count", *count),
+        };
+        if count == 0 || count > MAX_RULE_COUNT {
+            violations.push(Violation::new(
+                format!("{}.{}", path, field),
+                format!("Must be between 1 and {}.", MAX_RULE_COUNT),
+            ));
+        }
+
+        if let AchievementRule::RecipesCompleted { nutrients, .. } = self {
+            if nutrients.len() > MAX_RULE_NUTRIENTS {
+                violations.push(Violation::new(
+                    format!("{}.nutrients", path),
+                    format!("Must have at most {} nutrients.", MAX_RULE_NUTRIENTS),
+                ));
+            }
+            for (i, nutrient) in nutrients.iter().enumerate() {
+                if !NUTRIENTS.contains_key(&u16::from(*nutrient)) {
+                    violations.push(Violation::new(
+                        format!("{}.nutrients[{}]", path, i),
+                        "No nutrient has this ID.",
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Returns whether `event` can make progress towards the rule, so it
+    /// only needs to be checked after those events.
+    pub fn evaluated_on(&self, event: &LearnerEvent) -> bool {
+        match (self, event) {
+            (AchievementRule::RecipesCompleted { .. }, LearnerEvent::QuizAttempted(attempt)) => {
+                attempt.passed()
+            }
+            (AchievementRule::RecipesCompleted { .. }, LearnerEvent::RecipeCooked(_)) => true,
+            (AchievementRule::WeeklyStreak { .. }, LearnerEvent::QuizAttempted(attempt)) => {
+                attempt.weekly && attempt.passed()
+            }
+            (AchievementRule::WeeklyStreak { .. }, LearnerEvent::RecipeCooked(entry)) => {
+                entry.weekly
+            }
+            (AchievementRule::PerfectQuizzes { .. }, LearnerEvent::QuizAttempted(attempt)) => {
+                attempt.all_correct
+            }
+            (AchievementRule::PerfectQuizzes { .. }, LearnerEvent::RecipeCooked(_)) => false,
+        }
+    }
+
+    /// Returns how far a learner with `history` is towards the rule, and how
+    /// far they need to be to meet it.
+    pub fn progress(&self, history: &LearnerHistory) -> (u32, u32) {
+        match self {
+            AchievementRule::RecipesCompleted { count, nutrients } => {
+                let recipes = distinct_recipes(history.completions.iter().filter(|completion| {
+                    nutrients.is_empty()
+                        || completion
+                            .nutrients
+                            .iter()
+                            .any(|nutrient| nutrients.contains(nutrient))
+                }));
+                (recipes, *count)
+            }
+            AchievementRule::WeeklyStreak { weeks } => {
+                // Weekly recipes are numbered in the order they went weekly,
+                // so a recipe completed either side of midnight counts once.
+                let completed: BTreeSet<usize> = history
+                    .completions
+                    .iter()
+                    .filter_map(|completion| completion.weekly)
+                    .filter_map(|timestamp| {
+                        history.weekly_timestamps.binary_search(&timestamp).ok()
+                    })
+                    .collect();
+                // The longest run of weekly recipes, each straight after the
+                // last.
+                let mut longest = 0;
+                let mut run = 0;
+                let mut previous = None;
+                for week in completed {
+                    run = match previous {
+                        Some(previous) if week == previous + 1 => run + 1,
+                        _ => 1,
+                    };
+                    longest = longest.max(run);
+                    previous = Some(week);
+                }
+                (longest, *weeks)
+            }
+            AchievementRule::PerfectQuizzes { count } => {
+                let recipes = distinct_recipes(
+                    history
+                        .completions
+                        .iter()
+                        .filter(|completion| completion.perfect),
+                );
+                (recipes, *count)
+            }
+        }
+    }
+
+    /// Returns whether a learner with `history` has met the rule.
+    pub fn is_met(&self, history: &LearnerHistory) -> bool {
+        let (progress, target) = self.progress(history);
+        progress >= target
+    }
+}
+
+/// Returns the number of different recipes that were completed.
+fn distinct_recipes<'a>(completions: impl Iterator<Item = &'a Completion>) -> u32 {
+    completions
+        .map(|completion| u128::from(completion.recipe))
+        .collect::<BTreeSet<_>>()
+        .len() as u32
+}
+
+/// An achievement unlocked by a learner.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Badge {
+    /// The unique identifier of the badge.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The learner who unlocked the achievement.
+    pub learner: Uuid,
+    /// The achievement that was unlocked.
+    pub achievement: Uuid,
+    /// The date the achievement was unlocked.
+    pub date: Date,
+}
+
+impl Badge {
+    /// Constructs a new badge for an achievement unlocked now.
+    pub fn new(learner: Uuid, achievement: Uuid) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            learner,
+            achievement,
+            date: Date::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The length of a week, in milliseconds.
+    const WEEK_MS: u64 = 1000 * 60 * 60 * 24 * 7;
+
+    /// Returns a completion of `recipe` during `week`.
+    fn completion(recipe: u128, week: u64) -> Completion {
+        Completion {
+            recipe: Uuid::from_u128(recipe),
+            nutrients: vec![],
+            date: Date::new(week * WEEK_MS),
+            weekly: None,
+            perfect: false,
+        }
+    }
+
+    #[test]
+    fn counts_recipes_once() {
+        let iron = Nutrient::from_str("Iron").unwrap();
+        let rule = AchievementRule::RecipesCompleted {
+            count: 2,
+            nutrients: vec![iron],
+        };
+        let mut history = LearnerHistory {
+            completions: vec![
+                Completion {
+                    nutrients: vec![iron],
+                    ..completion(1, 0)
+                },
+                Completion {
+                    nutrients: vec![iron],
+                    ..completion(1, 1)
+                },
+                completion(2, 1),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(rule.progress(&history), (1, 2));
+        history.completions.push(Completion {
+            nutrients: vec![Nutrient::from_str("Zinc").unwrap(), iron],
+            ..completion(3, 2)
+        });
+        assert!(rule.is_met(&history));
+
+        let perfect = AchievementRule::PerfectQuizzes { count: 1 };
+        assert!(!perfect.is_met(&history));
+        history.completions[1].perfect = true;
+        assert!(perfect.is_met(&history));
+    }
+
+    #[test]
+    fn finds_longest_streak() {
+        // A weekly recipe that went weekly in `week`, completed `days_later`.
+        let weekly = |week: u64, days_later: u64| Completion {
+            weekly: Some(Date::new(week * WEEK_MS)),
+            date: Date::new(week * WEEK_MS + days_later * WEEK_MS / 7),
+            ..completion(week as u128, week)
+        };
+        let history = LearnerHistory {
+            // Nothing was completed in week 4, and week 3's recipe was not
+            // the weekly recipe, so the streak starts again at week 5.
+            completions: vec![
+                weekly(1, 0),
+                weekly(2, 0),
+                completion(3, 3),
+                weekly(5, 0),
+                weekly(6, 0),
+                weekly(7, 0),
+                weekly(7, 8),
+            ],
+            weekly_timestamps: (1..=7).map(|week| Date::new(week * WEEK_MS)).collect(),
+        };
+        let rule = AchievementRule::WeeklyStreak { weeks: 4 };
+        assert_eq!(rule.progress(&history), (3, 4));
+
+        // Completing one weekly recipe on both sides of a week boundary only
+        // counts it once.
+        let history = LearnerHistory {
+            completions: vec![weekly(1, 6), weekly(1, 7)],
+            ..history
+        };
+        assert_eq!(rule.progress(&history), (1, 4));
+
+        // Weekly recipes follow each other even if weeks were skipped
+        // between them.
+        let history = LearnerHistory {
+            completions: vec![weekly(1, 0), weekly(3, 0), weekly(9, 0)],
+            weekly_timestamps: [1, 3, 9].map(|week| Date::new(week * WEEK_MS)).to_vec(),
+        };
+        assert_eq!(rule.progress(&history), (3, 4));
+    }
+
+    #[test]
+    fn only_counts_passed_attempts() {
+        let attempt = |correct, total| {
+            let result = QuizResult {
+                questions: vec![],
+                correct,
+                total,
+                all_correct: correct == total,
+                reward: 0,
+            };
+            QuizAttempt::new(Uuid::random(), Uuid::random(), None, &result)
+        };
+        let rule = AchievementRule::RecipesCompleted {
+            count: 1,
+            nutrients: vec![],
+        };
+        for (correct, total, passed) in [(0, 4, false), (1, 4, false), (2, 4, true), (4, 4, true)] {
+            let attempt = attempt(correct, total);
+            assert_eq!(attempt.passed(), passed);
+            assert_eq!(
+                rule.evaluated_on(&LearnerEvent::QuizAttempted(&attempt)),
+                passed
+            );
+        }
+        assert!(!attempt(0, 0).passed());
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let mut violations = vec![];
+        AchievementRule::RecipesCompleted {
+            count: 0,
+            nutrients: vec![Nutrient::from(0)],
+        }
+        .validate("rule", &mut violations);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, vec!["rule.count", "rule.nutrients[0]"]);
+    }
+}
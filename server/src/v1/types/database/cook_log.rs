@@ -0,0 +1,131 @@
+use crate::v1::types::*;
+
+/// The lowest rating a learner can give a recipe they cooked.
+const MIN_RATING: u8 = 1;
+/// The highest rating a learner can give a recipe they cooked.
+const MAX_RATING: u8 = 5;
+
+/// A time a learner cooked a recipe.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct CookLogEntry {
+    /// The unique identifier of the entry.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The learner who cooked the recipe.
+    pub learner: Uuid,
+    /// The account the learner belongs to, so each family is only counted
+    /// once for a recipe.
+    pub account: Uuid,
+    /// The recipe that was cooked.
+    pub recipe: Uuid,
+    /// The date the recipe was cooked.
+    pub date: Date,
+    /// Whether the recipe was the weekly recipe when it was cooked.
+    pub weekly: bool,
+    /// The date the recipe went weekly, if it was the weekly recipe when it
+    /// was cooked.
+    #[serde(default)]
+    pub weekly_timestamp: Option<Date>,
+    /// The URL to a photo of what was cooked.
+    pub photo: Option<Url>,
+    /// How much the learner liked the recipe, from 1 to 5.
+    pub rating: Option<u8>,
+}
+
+impl CookLogEntry {
+    /// Constructs a new entry for a recipe cooked now, checking it is within
+    /// every documented limit. `weekly` is the date the recipe went weekly,
+    /// if it is the weekly recipe.
+    pub fn new(
+        learner: Uuid,
+        account: Uuid,
+        recipe: Uuid,
+        weekly: Option<Date>,
+        photo: Option<Url>,
+        rating: Option<u8>,
+    ) -> Result<Self, Vec<Violation>> {
+        let mut violations = vec![];
+        if let Some(photo) = &photo {
+            photo.validate("photo", &mut violations);
+        }
+        if let Some(rating) = rating {
+            if !(MIN_RATING..=MAX_RATING).contains(&rating) {
+                violations.push(Violation::new(
+                    "rating",
+                    format!("Must be between {} and {}.", MIN_RATING, MAX_RATING),
+                ));
+            }
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        Ok(Self {
+            uuid: Uuid::random(),
+            learner,
+            account,
+            recipe,
+            date: Date::now(),
+            weekly: weekly.is_some(),
+            weekly_timestamp: weekly,
+            photo,
+            rating,
+        })
+    }
+}
+
+/// Marks that a family has cooked a recipe, so it is only counted once
+/// towards the number of families that have cooked it.
+///
+/// A unique index on the recipe and account means only the first time a
+/// family cooks a recipe can be stored.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct FamilyCook {
+    /// The unique identifier of the mark.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The recipe that was cooked.
+    pub recipe: Uuid,
+    /// The account of the family that cooked it.
+    pub account: Uuid,
+}
+
+impl FamilyCook {
+    /// Constructs a new mark from the entry of the first time a family
+    /// cooked a recipe.
+    pub fn new(entry: &CookLogEntry) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            recipe: entry.recipe,
+            account: entry.account,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_rating() {
+        let entry = |rating| {
+            CookLogEntry::new(
+                Uuid::random(),
+                Uuid::random(),
+                Uuid::random(),
+                None,
+                None,
+                rating,
+            )
+        };
+        assert!(entry(None).is_ok());
+        assert!(entry(Some(1)).is_ok());
+        assert!(entry(Some(5)).is_ok());
+        assert_eq!(entry(Some(0)).unwrap_err()[0].path, "rating");
+        assert!(entry(Some(6)).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::v1::types::*;
+
+/// The maximum length of a cookbook's title, in characters.
+const MAX_TITLE_CHARS: usize = 80;
+/// The maximum length of a cookbook's description, in characters.
+const MAX_DESCRIPTION_CHARS: usize = 400;
+/// The most recipes a cookbook can contain.
+const MAX_RECIPES: usize = 100;
+
+/// A themed set of recipes curated by staff, such as "School lunches".
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Cookbook {
+    /// The unique identifier of the cookbook.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The date the cookbook was added to the database.
+    pub date_added: Date,
+    /// The date the cookbook was last written. None if it has never been
+    /// overwritten.
+    #[serde(default)]
+    pub last_modified: Option<Date>,
+    /// The title of the cookbook. Max 80 chars.
+    pub title: String,
+    /// A description of the cookbook. Max 400 chars.
+    pub description: Formattable,
+    /// The URL to the cookbook image. Should be on S3
+    pub image: Url,
+    /// The gradient of the cookbook.
+    pub gradient: Gradient,
+    /// The UUIDs of the recipes in the cookbook, in the order they are shown.
+    /// Max 100 recipes, without duplicates.
+    pub recipes: Vec<Uuid>,
+}
+
+impl Cookbook {
+    /// Constructs a new cookbook, checking it is within every documented
+    /// limit.
+    ///
+    /// If any are broken, every violation is returned rather than just the
+    /// first.
+    pub fn new(
+        uuid: Option<Uuid>,
+        title: String,
+        description: Formattable,
+        image: Url,
+        gradient: Gradient,
+        recipes: Vec<Uuid>,
+    ) -> Result<Self, Vec<Violation>> {
+        let cookbook = Self {
+            uuid: uuid.unwrap_or_else(Uuid::random),
+            date_added: Date::now(),
+            last_modified: None,
+            title,
+            description,
+            image,
+            gradient,
+            recipes,
+        };
+        cookbook.validate()?;
+        Ok(cookbook)
+    }
+
+    /// Checks the cookbook is within every documented limit.
+    pub fn validate(&self) -> Result<(), Vec<Violation>> {
+        let mut violations = vec![];
+
+        if self.title.is_empty() {
+            violations.push(Violation::new("title", "Must not be empty."));
+        }
+        check_max_chars(&mut violations, "title", &self.title, MAX_TITLE_CHARS);
+        check_max_chars(
+            &mut violations,
+            "description",
+            self.description.as_str(),
+            MAX_DESCRIPTION_CHARS,
+        );
+        // Cookbooks hold many recipes, so there is no single list of
+        // ingredients to check references against.
+        self.description
+            .validate_markup("description", &mut violations);
+        self.image.validate("image", &mut violations);
+        self.gradient.validate("gradient", &mut violations);
+
+        if self.recipes.len() > MAX_RECIPES {
+            violations.push(Violation::new(
+                "recipes",
+                format!(
+                    "Must have at most {} recipes, but has {}.",
+                    MAX_RECIPES,
+                    self.recipes.len()
+                ),
+            ));
+        }
+        for (i, uuid) in self.recipes.iter().enumerate() {
+            if self.recipes[..i].contains(uuid) {
+                violations.push(Violation::new(
+                    format!("recipes[{}]", i),
+                    "The recipe is already in the cookbook.",
+                ));
+            }
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+
+    /// Carries over the date the cookbook was added from the stored version
+    /// it overwrites, and marks it as modified now.
+    pub fn inherit(&mut self, previous: &Cookbook) {
+        self.date_added = previous.date_added;
+        self.last_modified = Some(Date::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a new cookbook with `title` and `recipes`, and a valid
+    /// description, image and gradient.
+    fn cookbook(title: &str, recipes: Vec<Uuid>) -> Result<Cookbook, Vec<Violation>> {
+        Cookbook::new(
+            None,
+            title.to_string(),
+            Formattable::new("Quick meals for **busy** nights."),
+            Url::from("https://example.com/images/lunches.png"),
+            serde_json::from_str(r##"["#ffffff", "#000000"]"##).unwrap(),
+            recipes,
+        )
+    }
+
+    /// Returns the paths of every violation.
+    fn paths(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.path.as_str()).collect()
+    }
+
+    #[test]
+    fn accepts_valid_cookbooks() {
+        let recipes: Vec<Uuid> = (0..MAX_RECIPES).map(|_| Uuid::random()).collect();
+        assert!(cookbook("School lunches", recipes).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_titles() {
+        let violations = cookbook("", vec![]).unwrap_err();
+        assert_eq!(paths(&violations), ["title"]);
+        assert_eq!(violations[0].message, "Must not be empty.");
+    }
+
+    #[test]
+    fn rejects_too_many_recipes() {
+        let recipes: Vec<Uuid> = (0..=MAX_RECIPES).map(|_| Uuid::random()).collect();
+        let violations = cookbook("School lunches", recipes).unwrap_err();
+        assert_eq!(paths(&violations), ["recipes"]);
+        assert_eq!(
+            violations[0].message,
+            "Must have at most 100 recipes, but has 101."
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_recipes() {
+        let (a, b) = (Uuid::random(), Uuid::random());
+        let violations = cookbook("School lunches", vec![a, b, a, b, a]).unwrap_err();
+        assert_eq!(
+            paths(&violations),
+            ["recipes[2]", "recipes[3]", "recipes[4]"]
+        );
+    }
+
+    #[test]
+    fn allows_ingredient_references_in_descriptions() {
+        let mut cookbook = cookbook("School lunches", vec![]).unwrap();
+        cookbook.description = Formattable::new("Uses {ingredient:1}.");
+        assert!(cookbook.validate().is_ok());
+        cookbook.description = Formattable::new("Unclosed **bold");
+        assert_eq!(paths(&cookbook.validate().unwrap_err()), ["description"]);
+    }
+}
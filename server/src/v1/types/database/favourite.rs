@@ -0,0 +1,29 @@
+use crate::v1::types::*;
+
+/// A recipe a learner has saved to come back to.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Favourite {
+    /// The unique identifier of the favourite.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The learner who saved the recipe.
+    pub learner: Uuid,
+    /// The recipe that was saved.
+    pub recipe: Uuid,
+    /// The date the recipe was saved.
+    pub date: Date,
+}
+
+impl Favourite {
+    /// Constructs a new favourite of a recipe, saved now.
+    pub fn new(learner: Uuid, recipe: Uuid) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            learner,
+            recipe,
+            date: Date::now(),
+        }
+    }
+}
@@ -9,11 +9,31 @@ pub struct Method {
 }
 
 impl Method {
+    /// Returns the method with every temperature in it written in the
+    /// temperature scale of `system`.
+    pub fn convert_temperatures(&self, system: UnitSystem) -> Self {
+        let mut method = self.clone();
+        for substep in method
+            .steps
+            .iter_mut()
+            .flat_map(|step| step.substeps.iter_mut())
+        {
+            substep.content =
+                Formattable::new(convert_temperatures(substep.content.as_str(), system));
+        }
+        method
+    }
+
     /// Contructs a new empty method.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Returns the steps of the method.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
     /// Adds a new [`Step`] to the method.
     ///
     /// [`Step`]: crate::v1::types::database::Step
@@ -21,6 +41,34 @@ impl Method {
         self.steps.push(step);
         self
     }
+
+    /// Checks every step of the method is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (i, step) in self.steps.iter().enumerate() {
+            step.validate(&format!("{}.steps[{}]", path, i), violations);
+        }
+    }
+
+    /// Returns every formattable in the method, along with its path.
+    pub fn formattables(&self, path: &str) -> Vec<(String, &Formattable)> {
+        let mut formattables = vec![];
+        for (i, step) in self.steps.iter().enumerate() {
+            for (j, substep) in step.substeps.iter().enumerate() {
+                let path = format!("{}.steps[{}].substeps[{}]", path, i, j);
+                formattables.push((format!("{}.content", path), &substep.content));
+                for (k, warning) in substep.warnings.iter().enumerate() {
+                    formattables.push((
+                        format!("{}.warnings[{}].content", path, k),
+                        warning.content(),
+                    ));
+                }
+                for (k, info) in substep.infos.iter().enumerate() {
+                    formattables.push((format!("{}.infos[{}].content", path, k), info.content()));
+                }
+            }
+        }
+        formattables
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
@@ -46,11 +94,28 @@ impl Step {
         Self { title, substeps }
     }
 
+    /// Returns the title of the step.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the substeps of the step.
+    pub fn substeps(&self) -> &[SubStep] {
+        &self.substeps
+    }
+
     /// Adds a new substep to the step
     pub fn add_substep(mut self, substep: SubStep) -> Self {
         self.substeps.push(substep);
         self
     }
+
+    /// Checks every substep of the step is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (i, substep) in self.substeps.iter().enumerate() {
+            substep.validate(&format!("{}.substeps[{}]", path, i), violations);
+        }
+    }
 }
 
 /// A SubStep is part of a larger [`Step`], which is part of the collection
@@ -72,3 +137,110 @@ pub struct SubStep {
     #[serde(default)]
     infos: Vec<Info>,
 }
+
+impl SubStep {
+    /// Returns the content of the substep.
+    pub fn content(&self) -> &Formattable {
+        &self.content
+    }
+
+    /// Returns the image accompanying the substep, if it has one.
+    pub fn image(&self) -> Option<&Url> {
+        self.image.as_ref()
+    }
+
+    /// Returns the warnings present with the substep.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Returns the information present with the substep.
+    pub fn infos(&self) -> &[Info] {
+        &self.infos
+    }
+
+    /// Checks the substep is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(image) = &self.image {
+            image.validate(&format!("{}.image", path), violations);
+        }
+    }
+}
+
+/// A partial update to a [`Method`], used when patching a recipe.
+///
+/// See [`ListPatch`] for how the steps are merged.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodPatch {
+    /// The changes to the steps of the recipe.
+    steps: Option<ListPatch<Step, StepPatch>>,
+}
+
+impl Patch<Method> for MethodPatch {
+    fn apply(self, target: &mut Method, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(steps) = self.steps {
+            steps.apply(&mut target.steps, &format!("{}.steps", path), violations);
+        }
+    }
+}
+
+/// A partial update to a [`Step`].
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StepPatch {
+    /// The new title of the step.
+    title: Option<String>,
+    /// The changes to the substeps in the step.
+    substeps: Option<ListPatch<SubStep, SubStepPatch>>,
+}
+
+impl Patch<Step> for StepPatch {
+    fn apply(self, target: &mut Step, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(title) = self.title {
+            target.title = title;
+        }
+        if let Some(substeps) = self.substeps {
+            substeps.apply(
+                &mut target.substeps,
+                &format!("{}.substeps", path),
+                violations,
+            );
+        }
+    }
+}
+
+/// A partial update to a [`SubStep`].
+///
+/// The warnings and infos are small enough that they are always replaced
+/// as a whole.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubStepPatch {
+    /// The new content of the substep.
+    content: Option<Formattable>,
+    /// The new image of the substep. `null` removes the image.
+    #[serde(default, deserialize_with = "double_option")]
+    image: Option<Option<Url>>,
+    /// The new warnings of the substep.
+    warnings: Option<Vec<Warning>>,
+    /// The new information of the substep.
+    infos: Option<Vec<Info>>,
+}
+
+impl Patch<SubStep> for SubStepPatch {
+    fn apply(self, target: &mut SubStep, _path: &str, _violations: &mut Vec<Violation>) {
+        if let Some(content) = self.content {
+            target.content = content;
+        }
+        if let Some(image) = self.image {
+            target.image = image;
+        }
+        if let Some(warnings) = self.warnings {
+            target.warnings = warnings;
+        }
+        if let Some(infos) = self.infos {
+            target.infos = infos;
+        }
+    }
+}
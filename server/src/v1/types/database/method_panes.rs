@@ -27,6 +27,16 @@ impl Warning {
         Self::default()
     }
 
+    /// Returns the title of the warning.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the content of the warning.
+    pub fn content(&self) -> &Formattable {
+        &self.content
+    }
+
     /// Sets the title on the Warning.
     pub fn set_title(mut self, title: String) -> Self {
         self.title = title;
@@ -64,6 +74,16 @@ impl Info {
         Self::default()
     }
 
+    /// Returns the title of the info.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the content of the info.
+    pub fn content(&self) -> &Formattable {
+        &self.content
+    }
+
     /// Sets the title on the Info.
     pub fn set_title(mut self, title: String) -> Self {
         self.title = title;
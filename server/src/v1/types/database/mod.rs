@@ -1,9 +1,27 @@
+pub mod account;
+pub mod achievement;
+pub mod cook_log;
+pub mod cookbook;
+pub mod favourite;
 pub mod method;
 pub mod method_panes;
+pub mod patch;
+pub mod points;
 pub mod quiz;
 pub mod recipe;
+pub mod revision;
+pub mod upload;
 
+pub use self::account::*;
+pub use self::achievement::*;
+pub use self::cook_log::*;
+pub use self::cookbook::*;
+pub use self::favourite::*;
 pub use self::method::*;
 pub use self::method_panes::*;
+pub use self::patch::*;
+pub use self::points::*;
 pub use self::quiz::*;
 pub use self::recipe::*;
+pub use self::revision::*;
+pub use self::upload::*;
@@ -0,0 +1,140 @@
+use crate::v1::types::Violation;
+use std::collections::BTreeMap;
+
+/// A partial update to a value of type `T`.
+///
+/// Used by the `PATCH` endpoints to update only the fields that were sent,
+/// leaving the rest of the stored value untouched.
+pub trait Patch<T> {
+    /// Applies the patch onto `target`, adding every part that could not
+    /// be applied to `violations`.
+    ///
+    /// `path` is the location of `target` in the recipe, such as
+    /// `method.steps[2]`, and is used to make error messages more helpful.
+    fn apply(self, target: &mut T, path: &str, violations: &mut Vec<Violation>);
+}
+
+/// A partial update to a list of `T`, where `P` is the patch for a single item.
+///
+/// Lists are merged as follows:
+///
+/// - A JSON array replaces the entire list. This is the only way to add,
+///   remove or reorder items.
+/// - A JSON object patches individual items, keyed by their index. Items
+///   not mentioned are left untouched. For example,
+///   `{ "2": { "title": "Bake" } }` only changes the title of the third item.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ListPatch<T, P> {
+    /// Replaces the entire list.
+    Replace(Vec<T>),
+    /// Patches individual items of the list, keyed by their index.
+    // Keys are Strings as untagged enums cannot deserialize map keys
+    // into integers.
+    Update(BTreeMap<String, P>),
+}
+
+impl<T, P: Patch<T>> Patch<Vec<T>> for ListPatch<T, P> {
+    fn apply(self, target: &mut Vec<T>, path: &str, violations: &mut Vec<Violation>) {
+        match self {
+            ListPatch::Replace(list) => *target = list,
+            ListPatch::Update(patches) => {
+                let len = target.len();
+                for (index, patch) in patches {
+                    let item_path = format!("{}[{}]", path, index);
+                    match index
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| target.get_mut(index))
+                    {
+                        Some(item) => patch.apply(item, &item_path, violations),
+                        None => violations.push(Violation::new(
+                            item_path,
+                            format!("Invalid index. There are only {} items.", len),
+                        )),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Deserializes a field that can be either absent, `null` or a value.
+///
+/// Use with `#[serde(default, deserialize_with = "double_option")]` on an
+/// `Option<Option<T>>`. An absent field is `None`, `null` is `Some(None)` and
+/// a value is `Some(Some(value))`. This lets patches remove optional values.
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sets an item to a new value, which can not be zero.
+    #[derive(Debug, serde::Deserialize)]
+    struct Set(u32);
+
+    impl Patch<u32> for Set {
+        fn apply(self, target: &mut u32, path: &str, violations: &mut Vec<Violation>) {
+            match self.0 {
+                0 => violations.push(Violation::new(path, "Must not be zero.")),
+                value => *target = value,
+            }
+        }
+    }
+
+    /// Applies the JSON `patch` onto `[1, 2, 3]`.
+    fn apply(patch: serde_json::Value) -> (Vec<u32>, Vec<Violation>) {
+        let patch: ListPatch<u32, Set> = serde_json::from_value(patch).unwrap();
+        let mut list = vec![1, 2, 3];
+        let mut violations = vec![];
+        patch.apply(&mut list, "list", &mut violations);
+        (list, violations)
+    }
+
+    #[test]
+    fn replaces_with_array() {
+        let (list, violations) = apply(serde_json::json!([7, 8]));
+        assert_eq!(list, [7, 8]);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn updates_by_index() {
+        let (list, violations) = apply(serde_json::json!({ "0": 9, "2": 4 }));
+        assert_eq!(list, [9, 2, 4]);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let (list, violations) = apply(serde_json::json!({ "1": 0, "3": 5, "x": 5, "2": 6 }));
+        // Valid items are still applied, so every violation can be found.
+        assert_eq!(list, [1, 2, 6]);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, ["list[1]", "list[3]", "list[x]"]);
+        assert_eq!(
+            violations[1].message,
+            "Invalid index. There are only 3 items."
+        );
+    }
+
+    #[test]
+    fn tells_null_from_absent() {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            #[serde(default, deserialize_with = "double_option")]
+            image: Option<Option<u32>>,
+        }
+        let image = |json| serde_json::from_value::<Fields>(json).unwrap().image;
+        assert_eq!(image(serde_json::json!({})), None);
+        assert_eq!(image(serde_json::json!({ "image": null })), Some(None));
+        assert_eq!(image(serde_json::json!({ "image": 3 })), Some(Some(3)));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::v1::types::*;
+
+/// The maximum length of the reason for an adjustment, in characters.
+const MAX_REASON_CHARS: usize = 200;
+
+/// An entry in a learner's points ledger.
+///
+/// Entries are only ever added, never changed or removed, so a learner's
+/// balance is the sum of every entry's amount. Points are taken away by
+/// adding an entry with a negative amount.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct PointsEntry {
+    /// The unique identifier of the entry.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The learner the points were given to.
+    pub learner: Uuid,
+    /// The date the points were given.
+    pub date: Date,
+    /// The number of points given. Negative if points were taken away.
+    pub amount: i32,
+    /// Why the points were given.
+    pub source: PointsSource,
+}
+
+/// Why a [`PointsEntry`] was added to the ledger.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PointsSource {
+    /// A reward of a recipe's quiz, earned by a [`QuizAttempt`].
+    ///
+    /// A learner can only earn each reward of a recipe once, which is
+    /// enforced by a unique index on the learner, recipe and reward.
+    Quiz {
+        attempt: Uuid,
+        recipe: Uuid,
+        reward: QuizReward,
+    },
+    /// An adjustment made by staff, such as to correct a mistake.
+    Adjustment {
+        reason: String,
+        /// The staff member who made the adjustment, if known.
+        author: Option<Uuid>,
+    },
+}
+
+/// Which reward of a recipe's quiz a [`PointsSource::Quiz`] is for.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum QuizReward {
+    /// The reward for answering the question with this index correctly.
+    Question(u32),
+    /// The reward for answering every question correctly.
+    AllCorrect,
+}
+
+impl PointsEntry {
+    /// Constructs a new entry giving a reward earned by a quiz attempt.
+    pub fn quiz(attempt: &QuizAttempt, reward: QuizReward, amount: u32) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            learner: attempt.learner,
+            date: attempt.date,
+            amount: amount.min(i32::MAX as u32) as i32,
+            source: PointsSource::Quiz {
+                attempt: attempt.uuid,
+                recipe: attempt.recipe,
+                reward,
+            },
+        }
+    }
+
+    /// Constructs a new adjustment by staff, checking it is within every
+    /// documented limit.
+    pub fn adjustment(
+        learner: Uuid,
+        amount: i32,
+        reason: String,
+        author: Option<Uuid>,
+    ) -> Result<Self, Vec<Violation>> {
+        let mut violations = vec![];
+        if amount == 0 {
+            violations.push(Violation::new("amount", "Must not be 0."));
+        }
+        if reason.trim().is_empty() {
+            violations.push(Violation::new("reason", "Must not be empty."));
+        }
+        check_max_chars(&mut violations, "reason", &reason, MAX_REASON_CHARS);
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        Ok(Self {
+            uuid: Uuid::random(),
+            learner,
+            date: Date::now(),
+            amount,
+            source: PointsSource::Adjustment { reason, author },
+        })
+    }
+}
+
+/// A graded attempt by a learner at a recipe's quiz.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct QuizAttempt {
+    /// The unique identifier of the attempt.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The learner who made the attempt.
+    pub learner: Uuid,
+    /// The recipe whose quiz was attempted.
+    pub recipe: Uuid,
+    /// The date the attempt was graded.
+    pub date: Date,
+    /// The number of questions answered correctly.
+    pub correct: u32,
+    /// The number of questions in the quiz.
+    pub total: u32,
+    /// Whether every question was answered correctly.
+    pub all_correct: bool,
+    /// Whether the recipe was the weekly recipe when it was attempted.
+    #[serde(default)]
+    pub weekly: bool,
+    /// The date the recipe went weekly, if it was the weekly recipe when it
+    /// was attempted.
+    #[serde(default)]
+    pub weekly_timestamp: Option<Date>,
+    /// The points the attempt earned. Rewards already earned by an earlier
+    /// attempt are not earned again.
+    pub points: u32,
+}
+
+impl QuizAttempt {
+    /// Constructs a new attempt from the grade of a quiz submission, which
+    /// has not earned any points yet. `weekly` is the date the recipe went
+    /// weekly, if it is the weekly recipe.
+    pub fn new(learner: Uuid, recipe: Uuid, weekly: Option<Date>, result: &QuizResult) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            learner,
+            recipe,
+            date: Date::now(),
+            correct: result.correct as u32,
+            total: result.total as u32,
+            all_correct: result.all_correct,
+            weekly: weekly.is_some(),
+            weekly_timestamp: weekly,
+            points: 0,
+        }
+    }
+
+    /// Returns whether enough questions were answered correctly for the
+    /// attempt to complete the recipe, which is at least half of them.
+    pub fn passed(&self) -> bool {
+        self.correct > 0 && self.correct * 2 >= self.total
+    }
+
+    /// Records the points the attempt earned.
+    pub fn earned(&mut self, points: u32) {
+        self.points = points;
+    }
+}
@@ -1,8 +1,14 @@
+use crate::v1::types::database::*;
 use crate::v1::types::*;
 
+/// The maximum length of an answer, in characters.
+const MAX_ANSWER_CHARS: usize = 400;
+/// The maximum length of the unit of a numeric answer, in characters.
+const MAX_UNIT_CHARS: usize = 40;
+
 /// A Quiz stores information about the quizzes at the end of recipes.
 /// They are used by the [`Recipe`] type.
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Quiz {
     /// The questions present in the quiz
@@ -25,12 +31,43 @@ impl Quiz {
         self.questions.push(question);
         self
     }
+
+    /// Returns the questions of the quiz.
+    pub fn questions(&self) -> &[Question] {
+        &self.questions
+    }
+
+    /// Returns the reward for getting every question correct.
+    pub fn all_correct_reward(&self) -> u16 {
+        self.all_correct_reward
+    }
+
+    /// Checks every question of the quiz is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (i, question) in self.questions.iter().enumerate() {
+            question.validate(&format!("{}.questions[{}]", path, i), violations);
+        }
+    }
+
+    /// Returns every formattable in the quiz, along with its path.
+    pub fn formattables(&self, path: &str) -> Vec<(String, &Formattable)> {
+        let mut formattables = vec![];
+        for (i, question) in self.questions.iter().enumerate() {
+            let path = format!("{}.questions[{}]", path, i);
+            formattables.push((format!("{}.question", path), &question.question));
+            if let Some(description) = &question.description {
+                formattables.push((format!("{}.description", path), description));
+            }
+            formattables.append(&mut question.answers.formattables(&format!("{}.answers", path)));
+        }
+        formattables
+    }
 }
 
 /// A Question stores information about a single question presented
 /// at the end of recipes. They are stored in a [`Quiz`].
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", try_from = "QuestionRepr")]
 pub struct Question {
     /// The question title.
     question: Formattable,
@@ -38,10 +75,326 @@ pub struct Question {
     description: Option<Formattable>,
     /// An image associated with the question.
     image: Option<Url>,
-    /// The correct answer(s). Max 400 chars.
-    correct_answers: Vec<Formattable>,
-    /// The incorrect answer(s). Max 400 chars.
-    wrong_answers: Vec<Formattable>,
+    /// The type of question, along with its answers.
+    answers: Answers,
     /// The reward for getting this question correct.
     reward: u16,
 }
+
+impl Question {
+    /// Returns the question title.
+    pub fn question(&self) -> &Formattable {
+        &self.question
+    }
+
+    /// Returns the question description.
+    pub fn description(&self) -> Option<&Formattable> {
+        self.description.as_ref()
+    }
+
+    /// Returns the image associated with the question.
+    pub fn image(&self) -> Option<&Url> {
+        self.image.as_ref()
+    }
+
+    /// Returns the type of question, along with its answers.
+    pub fn answers(&self) -> &Answers {
+        &self.answers
+    }
+
+    /// Returns the reward for getting this question correct.
+    pub fn reward(&self) -> u16 {
+        self.reward
+    }
+
+    /// Checks the question is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(image) = &self.image {
+            image.validate(&format!("{}.image", path), violations);
+        }
+        self.answers
+            .validate(&format!("{}.answers", path), violations);
+    }
+}
+
+/// The type of a [`Question`], along with its answers. Each type is
+/// answered and graded differently.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Answers {
+    /// One of the options must be chosen. Max 400 chars each.
+    #[serde(rename_all = "camelCase")]
+    SingleChoice {
+        /// The correct answer.
+        correct_answer: Formattable,
+        /// The incorrect answers. There must be at least one.
+        wrong_answers: Vec<Formattable>,
+    },
+    /// Every correct option, and none of the wrong ones, must be chosen.
+    /// Max 400 chars each.
+    #[serde(rename_all = "camelCase")]
+    MultiSelect {
+        /// The correct answers. There must be at least one.
+        correct_answers: Vec<Formattable>,
+        /// The incorrect answers.
+        wrong_answers: Vec<Formattable>,
+    },
+    /// The question is a statement that is either true or false.
+    TrueFalse {
+        /// Whether the statement is true.
+        answer: bool,
+    },
+    /// The items must be put in order, such as the steps of the method.
+    /// Max 400 chars each.
+    Ordering {
+        /// The items, in the correct order. There must be at least two.
+        items: Vec<Formattable>,
+    },
+    /// One of the images must be chosen.
+    #[serde(rename_all = "camelCase")]
+    ImageChoice {
+        /// The correct image.
+        correct_image: Url,
+        /// The incorrect images. There must be at least one.
+        wrong_images: Vec<Url>,
+    },
+    /// A number must be given, which is correct if it is close enough to
+    /// the answer.
+    Numeric {
+        /// The correct number.
+        answer: f64,
+        /// How far from the answer a number can be and still be correct.
+        tolerance: f64,
+        /// The unit the number is in, such as `cups`.
+        unit: Option<String>,
+    },
+}
+
+// Numbers are validated to be finite, so are never NaN.
+impl Eq for Answers {}
+
+impl Answers {
+    /// Checks the answers are within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        let mut check_answers = |field: &str, answers: &[Formattable], min: usize| {
+            if answers.len() < min {
+                violations.push(Violation::new(
+                    format!("{}.{}", path, field),
+                    format!("Too few answers. There must be at least {}.", min),
+                ));
+            }
+            for (i, answer) in answers.iter().enumerate() {
+                check_max_chars(
+                    violations,
+                    &format!("{}.{}[{}]", path, field, i),
+                    answer.as_str(),
+                    MAX_ANSWER_CHARS,
+                );
+            }
+        };
+        match self {
+            Answers::SingleChoice {
+                correct_answer,
+                wrong_answers,
+            } => {
+                check_answers("correctAnswer", std::slice::from_ref(correct_answer), 1);
+                check_answers("wrongAnswers", wrong_answers, 1);
+            }
+            Answers::MultiSelect {
+                correct_answers,
+                wrong_answers,
+            } => {
+                check_answers("correctAnswers", correct_answers, 1);
+                check_answers("wrongAnswers", wrong_answers, 0);
+            }
+            Answers::TrueFalse { .. } => {}
+            Answers::Ordering { items } => check_answers("items", items, 2),
+            Answers::ImageChoice {
+                correct_image,
+                wrong_images,
+            } => {
+                correct_image.validate(&format!("{}.correctImage", path), violations);
+                if wrong_images.is_empty() {
+                    violations.push(Violation::new(
+                        format!("{}.wrongImages", path),
+                        "Too few images. There must be at least 1.",
+                    ));
+                }
+                for (i, image) in wrong_images.iter().enumerate() {
+                    image.validate(&format!("{}.wrongImages[{}]", path, i), violations);
+                }
+            }
+            Answers::Numeric {
+                answer,
+                tolerance,
+                unit,
+            } => {
+                if !answer.is_finite() {
+                    violations.push(Violation::new(
+                        format!("{}.answer", path),
+                        "The answer must be a finite number.",
+                    ));
+                }
+                if !(tolerance.is_finite() && *tolerance >= 0.0) {
+                    violations.push(Violation::new(
+                        format!("{}.tolerance", path),
+                        "The tolerance must be a finite number of at least 0.",
+                    ));
+                }
+                if let Some(unit) = unit {
+                    check_max_chars(violations, &format!("{}.unit", path), unit, MAX_UNIT_CHARS);
+                }
+            }
+        }
+    }
+
+    /// Returns every formattable answer, along with its path.
+    pub fn formattables<'a>(&'a self, path: &str) -> Vec<(String, &'a Formattable)> {
+        let list = |field: &str, answers: &'a [Formattable]| -> Vec<(String, &'a Formattable)> {
+            answers
+                .iter()
+                .enumerate()
+                .map(|(i, answer)| (format!("{}.{}[{}]", path, field, i), answer))
+                .collect()
+        };
+        match self {
+            Answers::SingleChoice {
+                correct_answer,
+                wrong_answers,
+            } => {
+                let mut formattables = vec![(format!("{}.correctAnswer", path), correct_answer)];
+                formattables.append(&mut list("wrongAnswers", wrong_answers));
+                formattables
+            }
+            Answers::MultiSelect {
+                correct_answers,
+                wrong_answers,
+            } => {
+                let mut formattables = list("correctAnswers", correct_answers);
+                formattables.append(&mut list("wrongAnswers", wrong_answers));
+                formattables
+            }
+            Answers::Ordering { items } => list("items", items),
+            Answers::TrueFalse { .. } | Answers::ImageChoice { .. } | Answers::Numeric { .. } => {
+                vec![]
+            }
+        }
+    }
+}
+
+/// The forms a [`Question`] can be deserialized from.
+///
+/// Questions written before there were types of question have their
+/// answers in `correctAnswers` and `wrongAnswers` instead of `answers`.
+/// They are single choice if they have one correct answer, and multi-select
+/// if they have more.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuestionRepr {
+    question: Formattable,
+    description: Option<Formattable>,
+    image: Option<Url>,
+    answers: Option<Answers>,
+    correct_answers: Option<Vec<Formattable>>,
+    wrong_answers: Option<Vec<Formattable>>,
+    reward: u16,
+}
+
+impl TryFrom<QuestionRepr> for Question {
+    type Error = String;
+
+    fn try_from(repr: QuestionRepr) -> Result<Self, Self::Error> {
+        let answers = match (repr.answers, repr.correct_answers) {
+            (Some(answers), _) => answers,
+            (None, Some(mut correct_answers)) => {
+                let wrong_answers = repr.wrong_answers.unwrap_or_default();
+                if correct_answers.len() == 1 {
+                    Answers::SingleChoice {
+                        correct_answer: correct_answers.remove(0),
+                        wrong_answers,
+                    }
+                } else {
+                    Answers::MultiSelect {
+                        correct_answers,
+                        wrong_answers,
+                    }
+                }
+            }
+            (None, None) => return Err("missing field `answers`".to_string()),
+        };
+        Ok(Self {
+            question: repr.question,
+            description: repr.description,
+            image: repr.image,
+            answers,
+            reward: repr.reward,
+        })
+    }
+}
+
+/// A partial update to a [`Quiz`], used when patching a recipe.
+///
+/// See [`ListPatch`] for how the questions are merged.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizPatch {
+    /// The changes to the questions in the quiz.
+    questions: Option<ListPatch<Question, QuestionPatch>>,
+    /// The new reward for getting all questions correct.
+    all_correct_reward: Option<u16>,
+}
+
+impl Patch<Quiz> for QuizPatch {
+    fn apply(self, target: &mut Quiz, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(questions) = self.questions {
+            questions.apply(
+                &mut target.questions,
+                &format!("{}.questions", path),
+                violations,
+            );
+        }
+        if let Some(all_correct_reward) = self.all_correct_reward {
+            target.all_correct_reward = all_correct_reward;
+        }
+    }
+}
+
+/// A partial update to a [`Question`].
+///
+/// The answers are always replaced as a whole, along with the type.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionPatch {
+    /// The new question title.
+    question: Option<Formattable>,
+    /// The new question description. `null` removes the description.
+    #[serde(default, deserialize_with = "double_option")]
+    description: Option<Option<Formattable>>,
+    /// The new image of the question. `null` removes the image.
+    #[serde(default, deserialize_with = "double_option")]
+    image: Option<Option<Url>>,
+    /// The new type of question and answers.
+    answers: Option<Answers>,
+    /// The new reward for getting this question correct.
+    reward: Option<u16>,
+}
+
+impl Patch<Question> for QuestionPatch {
+    fn apply(self, target: &mut Question, _path: &str, _violations: &mut Vec<Violation>) {
+        if let Some(question) = self.question {
+            target.question = question;
+        }
+        if let Some(description) = self.description {
+            target.description = description;
+        }
+        if let Some(image) = self.image {
+            target.image = image;
+        }
+        if let Some(answers) = self.answers {
+            target.answers = answers;
+        }
+        if let Some(reward) = self.reward {
+            target.reward = reward;
+        }
+    }
+}
@@ -1,8 +1,13 @@
-use crate::v1::types::database::*;
+use crate::v1::types::database::{Quiz, *};
 use crate::v1::types::*;
 use crate::WeeklyRecipeGetter;
 use heck::ToKebabCase;
 
+/// The maximum length of a recipe's title, in characters.
+const MAX_TITLE_CHARS: usize = 80;
+/// The maximum length of a recipe's credits, in characters.
+const MAX_CREDITS_CHARS: usize = 400;
+
 /// The database Recipe type that is sent to/used by the database.
 ///
 /// Contains more information than found in the [`BasicRecipe`] type.
@@ -15,9 +20,23 @@ pub struct Recipe {
     /// The unique identifier of the recipe
     #[serde(rename = "_id")]
     pub uuid: Uuid,
+    /// The revision of the recipe. Starts at 1 and is incremented every time
+    /// the recipe is overwritten, so writes made against an outdated copy
+    /// can be rejected. Recipes written before revisions were tracked have
+    /// no revision, which is read as 0.
+    #[serde(default)]
+    pub revision: u32,
+    /// The date this revision of the recipe was written.
+    /// None if the recipe was written before revisions were tracked.
+    #[serde(default)]
+    pub last_modified: Option<Date>,
+    /// The staff member who wrote this revision of the recipe, if known.
+    #[serde(default)]
+    pub last_modified_by: Option<Uuid>,
     /// The date the recipe was added to the database
     pub date_added: Date,
-    /// The date the recipe will/went public. If not yet that date, can only be referred to by id
+    /// The date the recipe will/went public. If not yet that date, it is hidden
+    /// from every read path unless a preview token for it is given.
     pub becomes_public: Date,
     /// The staff who helped make this recipe.
     pub authors: Vec<Uuid>,
@@ -25,12 +44,31 @@ pub struct Recipe {
     pub credits: Option<Formattable>,
     /// The date the recipe went weekly. None if never was weekly.
     pub weekly_timestamp: Option<Date>,
-    /// The short title of the recipe, kebab-cased.
+    /// The short title of the recipe, kebab-cased. Unique between recipes.
     pub short: String,
+    /// The shorts the recipe used to have, so old links can be redirected
+    /// to the current short.
+    #[serde(default)]
+    pub previous_shorts: Vec<String>,
     /// The title of the recipe. Max 80 chars.
     pub title: String,
     /// A list of common nutrients found in the recipe. Should be 1-3 long
+    ///
+    /// If the nutrition facts of the recipe are known, these are derived
+    /// from them.
     pub nutrients: Vec<Nutrient>,
+    /// The amount of each nutrient in a single serving of the recipe.
+    #[serde(default)]
+    pub nutrition: NutritionFacts,
+    /// The allergens the recipe contains, sorted and without duplicates.
+    ///
+    /// None if the recipe was written before allergens were tracked, in
+    /// which case its allergens are not known.
+    #[serde(default)]
+    pub allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for, sorted and without duplicates.
+    #[serde(default)]
+    pub diets: Vec<Diet>,
     /// The time to cook the recipe, in minutes
     pub time_to_cook: u16,
     /// The servings of the recipe.
@@ -40,11 +78,17 @@ pub struct Recipe {
     /// The gradient of the recipe.
     pub gradient: Gradient,
     /// The ingredients of the recipe. Max 80chars per ingredient.
-    pub ingredients: Vec<String>,
+    pub ingredients: Vec<Ingredient>,
     /// The recipe's method
     pub method: Method,
     /// The quiz information for the end of the recipe
     pub quiz: Quiz,
+    /// The date the recipe was deleted. None if it has not been deleted.
+    ///
+    /// Deleted recipes are hidden from every read path but are kept in the
+    /// database so they can be restored, until they are purged.
+    #[serde(default)]
+    pub deleted: Option<Date>,
 }
 
 impl Recipe {
@@ -58,6 +102,74 @@ impl Recipe {
         &self.uuid
     }
 
+    /// Sets the revision of the recipe.
+    ///
+    /// This is set by the server when writing the recipe, and should not be
+    /// taken from the request.
+    pub fn set_revision(&mut self, revision: u32) {
+        self.revision = revision;
+    }
+
+    /// Marks the recipe as modified now by `author`.
+    ///
+    /// This is set by the server when writing the recipe, and should not be
+    /// taken from the request body.
+    pub fn set_last_modified(&mut self, author: Option<Uuid>) {
+        self.last_modified = Some(Date::now());
+        self.last_modified_by = author;
+    }
+
+    /// Sets the short of the recipe.
+    ///
+    /// Used by the server to make the short unique before writing the recipe.
+    pub fn set_short(&mut self, short: String) {
+        self.short = short;
+    }
+
+    /// Carries over the short history from the previous version of the recipe.
+    ///
+    /// If the short has changed, the previous short is added to the history
+    /// so links to it can be redirected here.
+    pub fn inherit_shorts(&mut self, previous: &Recipe) {
+        let mut previous_shorts = previous.previous_shorts.clone();
+        if previous.short != self.short && !previous_shorts.contains(&previous.short) {
+            previous_shorts.push(previous.short.clone());
+        }
+        // The recipe may have been renamed back to an old short.
+        previous_shorts.retain(|short| short != &self.short);
+        self.previous_shorts = previous_shorts;
+    }
+
+    /// Returns `content`, an earlier revision of the recipe, as the next
+    /// revision of it, written by `author`.
+    ///
+    /// The content is checked again, as limits may have been added since it
+    /// was written. The short history and whether the recipe is deleted are
+    /// not rolled back, so old links keep working.
+    pub fn rolled_back_to(
+        &self,
+        mut content: Recipe,
+        author: Option<Uuid>,
+    ) -> Result<Recipe, Vec<Violation>> {
+        content.validate()?;
+        content.inherit_shorts(self);
+        content.deleted = self.deleted;
+        content.set_revision(self.revision + 1);
+        content.set_last_modified(author);
+        Ok(content)
+    }
+
+    /// Checks the recipe is within every documented limit, in the same way
+    /// as when it is built with a [`RecipeBuilder`].
+    pub fn validate(&self) -> Result<(), Vec<Violation>> {
+        RecipeBuilder::from(self.clone()).build().map(|_| ())
+    }
+
+    /// Returns if the recipe has been released to the public.
+    pub fn is_public(&self) -> bool {
+        self.becomes_public <= Date::now()
+    }
+
     /// Returns if the recipe is currently weekly or not.
     ///
     /// Currently checks if the Recipe has a weekly timestamp and that the
@@ -92,6 +204,12 @@ pub struct RecipeBuilder {
     title: Option<String>,
     /// A list of common nutrients found in the recipe. Should be 1-3 long
     nutrients: Vec<Nutrient>,
+    /// The amount of each nutrient in a single serving of the recipe.
+    nutrition: NutritionFacts,
+    /// The allergens the recipe contains. None if not known.
+    allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for.
+    diets: Vec<Diet>,
     /// The time to cook the recipe, in minutes
     time_to_cook: Option<u16>,
     /// The servings of the recipe.
@@ -101,61 +219,194 @@ pub struct RecipeBuilder {
     /// The gradient of the recipe.
     gradient: Option<Gradient>,
     /// The ingredients of the recipe. Max 80chars per ingredient.
-    ingredients: Vec<String>,
+    ingredients: Vec<Ingredient>,
     /// The recipe's method
     method: Option<Method>,
     /// The quiz information for the end of the recipe
     quiz: Option<Quiz>,
 }
 
+// Converts a stored Recipe back into a builder, so it can be modified
+// and validated again.
+impl From<Recipe> for RecipeBuilder {
+    fn from(recipe: Recipe) -> Self {
+        Self {
+            uuid: Some(recipe.uuid),
+            date_added: Some(recipe.date_added),
+            becomes_public: Some(recipe.becomes_public),
+            authors: recipe.authors,
+            credits: recipe.credits,
+            weekly_timestamp: recipe.weekly_timestamp,
+            short: Some(recipe.short),
+            title: Some(recipe.title),
+            nutrients: recipe.nutrients,
+            nutrition: recipe.nutrition,
+            allergens: recipe.allergens,
+            diets: recipe.diets,
+            time_to_cook: Some(recipe.time_to_cook),
+            servings: Some(recipe.servings),
+            image: Some(recipe.image),
+            gradient: Some(recipe.gradient),
+            ingredients: recipe.ingredients,
+            method: Some(recipe.method),
+            quiz: Some(recipe.quiz),
+        }
+    }
+}
+
 impl RecipeBuilder {
+    /// Returns every formattable set on the recipe, along with its path.
+    fn formattables(&self) -> Vec<(String, &Formattable)> {
+        let mut formattables = vec![];
+        if let Some(credits) = &self.credits {
+            formattables.push(("credits".to_string(), credits));
+        }
+        if let Some(method) = &self.method {
+            formattables.append(&mut method.formattables("method"));
+        }
+        if let Some(quiz) = &self.quiz {
+            formattables.append(&mut quiz.formattables("quiz"));
+        }
+        formattables
+    }
+
     /// Builds the RecipeBuilder into a [`Recipe`]
     ///
+    /// The recipe is checked against every documented limit, such as the
+    /// title being at most 80 characters. If any are broken, every
+    /// violation is returned rather than just the first.
+    ///
     /// [`Recipe`]: crate::v1::types::database::Recipe
-    pub fn build(self) -> Result<Recipe, String> {
-        if self.nutrients.is_empty() || self.nutrients.len() > 3 {
-            return Err("Recipe must have 1-3 nutrients".to_string());
+    pub fn build(self) -> Result<Recipe, Vec<Violation>> {
+        let mut violations = vec![];
+
+        self.nutrition.validate("nutrition", &mut violations);
+        // Tags are derived from the nutrition facts if they are known.
+        let nutrients = match self.nutrition.is_empty() {
+            true => self.nutrients.clone(),
+            false => self.nutrition.tags(),
+        };
+        if nutrients.is_empty() || nutrients.len() > 3 {
+            violations.push(Violation::new(
+                "nutrients",
+                "Recipe must have 1-3 nutrients",
+            ));
         }
+        validate_diets(
+            &self.diets,
+            self.allergens.as_deref(),
+            "diets",
+            &mut violations,
+        );
         if self.ingredients.is_empty() {
-            return Err("Recipe must have at least one ingredient".to_string());
+            violations.push(Violation::new(
+                "ingredients",
+                "Recipe must have at least one ingredient",
+            ));
+        }
+        for (i, ingredient) in self.ingredients.iter().enumerate() {
+            ingredient.validate(&format!("ingredients[{}]", i), &mut violations);
+        }
+        if let Some(title) = &self.title {
+            check_max_chars(&mut violations, "title", title, MAX_TITLE_CHARS);
+        }
+        if let Some(credits) = &self.credits {
+            check_max_chars(
+                &mut violations,
+                "credits",
+                credits.as_str(),
+                MAX_CREDITS_CHARS,
+            );
+        }
+        if let Some(image) = &self.image {
+            image.validate("image", &mut violations);
+        }
+        if let Some(gradient) = &self.gradient {
+            gradient.validate("gradient", &mut violations);
+        }
+        if let Some(method) = &self.method {
+            method.validate("method", &mut violations);
+        }
+        if let Some(quiz) = &self.quiz {
+            quiz.validate("quiz", &mut violations);
+        }
+        for (path, formattable) in self.formattables() {
+            formattable.validate(&path, self.ingredients.len(), &mut violations);
         }
 
-        let short = self
-            .get_short()
-            .ok_or_else(|| "No short set for recipe.".to_string())?;
-
-        Ok(Recipe {
+        let short = required(
+            self.get_short(),
+            "short",
+            "No short set for recipe.",
+            &mut violations,
+        );
+        let recipe = Recipe {
             uuid: self.uuid.unwrap_or_else(Uuid::random),
+            revision: 1,
+            last_modified: None,
+            last_modified_by: None,
             date_added: self.date_added.unwrap_or_else(Date::now),
             becomes_public: self.becomes_public.unwrap_or_else(Date::now),
             authors: self.authors,
             credits: self.credits,
             weekly_timestamp: self.weekly_timestamp,
-            title: self
-                .title
-                .ok_or_else(|| "No title set for recipe.".to_string())?,
+            title: required(
+                self.title,
+                "title",
+                "No title set for recipe.",
+                &mut violations,
+            ),
             short,
-            nutrients: self.nutrients,
-            time_to_cook: self
-                .time_to_cook
-                .ok_or_else(|| "No time to cook set for recipe.".to_string())?,
-            servings: self
-                .servings
-                .ok_or_else(|| "No servings set for recipe.".to_string())?,
-            image: self
-                .image
-                .ok_or_else(|| "No image set for recipe.".to_string())?,
-            gradient: self
-                .gradient
-                .ok_or_else(|| "No gradient set for recipe.".to_string())?,
+            previous_shorts: vec![],
+            nutrients,
+            nutrition: self.nutrition,
+            allergens: self.allergens,
+            diets: self.diets,
+            time_to_cook: required(
+                self.time_to_cook,
+                "timeToCook",
+                "No time to cook set for recipe.",
+                &mut violations,
+            ),
+            servings: required(
+                self.servings,
+                "servings",
+                "No servings set for recipe.",
+                &mut violations,
+            ),
+            image: required(
+                self.image,
+                "image",
+                "No image set for recipe.",
+                &mut violations,
+            ),
+            gradient: required(
+                self.gradient,
+                "gradient",
+                "No gradient set for recipe.",
+                &mut violations,
+            ),
             ingredients: self.ingredients,
-            method: self
-                .method
-                .ok_or_else(|| "No method set for recipe.".to_string())?,
-            quiz: self
-                .quiz
-                .ok_or_else(|| "No quiz set for recipe.".to_string())?,
-        })
+            method: required(
+                self.method,
+                "method",
+                "No method set for recipe.",
+                &mut violations,
+            ),
+            quiz: required(
+                self.quiz,
+                "quiz",
+                "No quiz set for recipe.",
+                &mut violations,
+            ),
+            deleted: None,
+        };
+
+        if violations.is_empty() {
+            Ok(recipe)
+        } else {
+            Err(violations)
+        }
     }
 
     /// Returns the current Short of the recipe.
@@ -201,6 +452,12 @@ impl RecipeBuilder {
         self.credits = Some(credits);
         self
     }
+
+    /// Removes the credits of the recipe.
+    pub fn remove_credits(mut self) -> Self {
+        self.credits = None;
+        self
+    }
     /// Sets the timestamp this becomes the weekly recipe.
     /// If not set, will never become the weekly recipe.
     pub fn weekly_timestamp(mut self, weekly_timestamp: Date) -> Self {
@@ -208,6 +465,13 @@ impl RecipeBuilder {
         self
     }
 
+    /// Removes the weekly timestamp, so the recipe will never become
+    /// the weekly recipe.
+    pub fn remove_weekly_timestamp(mut self) -> Self {
+        self.weekly_timestamp = None;
+        self
+    }
+
     /// Sets the title of the recipe.
     pub fn title(mut self, title: String) -> Self {
         self.title = Some(title);
@@ -226,6 +490,30 @@ impl RecipeBuilder {
         self
     }
 
+    /// Sets the amount of each nutrient in a single serving of the recipe.
+    ///
+    /// The list of nutrients is derived from these when built.
+    pub fn nutrition(mut self, nutrition: NutritionFacts) -> Self {
+        self.nutrition = nutrition;
+        self
+    }
+
+    /// Sets the allergens the recipe contains.
+    pub fn allergens(mut self, mut allergens: Vec<Allergen>) -> Self {
+        allergens.sort();
+        allergens.dedup();
+        self.allergens = Some(allergens);
+        self
+    }
+
+    /// Sets the diets the recipe is suitable for.
+    pub fn diets(mut self, mut diets: Vec<Diet>) -> Self {
+        diets.sort();
+        diets.dedup();
+        self.diets = diets;
+        self
+    }
+
     /// Adds a nutrient to the list of nutrients in the recipe.
     pub fn add_nutrient(mut self, nutrient: Nutrient) -> Self {
         self.nutrients.push(nutrient);
@@ -254,13 +542,13 @@ impl RecipeBuilder {
     }
 
     /// Sets the ingredients of the recipe.
-    pub fn ingredients(mut self, ingredients: Vec<String>) -> Self {
+    pub fn ingredients(mut self, ingredients: Vec<Ingredient>) -> Self {
         self.ingredients = ingredients;
         self
     }
 
     /// Adds an ingredient to the recipe.
-    pub fn add_ingredient(mut self, ingredient: String) -> Self {
+    pub fn add_ingredient(mut self, ingredient: Ingredient) -> Self {
         self.ingredients.push(ingredient);
         self
     }
@@ -277,3 +565,123 @@ impl RecipeBuilder {
         self
     }
 }
+
+/// Returns the value of a required field of the recipe, adding a violation
+/// if it was not set.
+///
+/// The returned default is only a placeholder, as the recipe will not be
+/// built if there are any violations.
+fn required<T: Default>(
+    value: Option<T>,
+    path: &str,
+    message: &str,
+    violations: &mut Vec<Violation>,
+) -> T {
+    value.unwrap_or_else(|| {
+        violations.push(Violation::new(path, message));
+        T::default()
+    })
+}
+
+/// Returns the recipe in `tests/golden/minimal.recipe.json`, changed by
+/// `change`, for tests to build recipes from.
+#[cfg(test)]
+pub fn fixture_recipe(change: impl FnOnce(&mut serde_json::Value)) -> Recipe {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/golden/minimal.recipe.json"
+    );
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    change(&mut json);
+    serde_json::from_value(json).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_back_as_next_revision() {
+        let old = fixture_recipe(|json| json["revision"] = 2.into());
+        let current = fixture_recipe(|json| {
+            json["revision"] = 5.into();
+            json["short"] = "buttered-toast".into();
+            json["previousShorts"] = serde_json::json!(["toast"]);
+            json["title"] = "Buttered toast".into();
+        });
+        let author = Uuid::random();
+
+        let rolled_back = current.rolled_back_to(old, Some(author)).unwrap();
+        assert_eq!(rolled_back.title, "Toast");
+        assert_eq!(rolled_back.revision, 6);
+        assert_eq!(rolled_back.last_modified_by, Some(author));
+        assert!(rolled_back.last_modified.is_some());
+        // Renaming back to the old short keeps the newer one redirecting.
+        assert_eq!(rolled_back.short, "toast");
+        assert_eq!(rolled_back.previous_shorts, vec!["buttered-toast"]);
+    }
+
+    #[test]
+    fn rejects_invalid_rollbacks() {
+        // A revision written before a recipe needed an ingredient.
+        let old = fixture_recipe(|json| json["ingredients"] = serde_json::json!([]));
+        let current = fixture_recipe(|_| {});
+        let violations = current.rolled_back_to(old, None).unwrap_err();
+        assert_eq!(violations[0].path, "ingredients");
+
+        // Whether the recipe is deleted is not rolled back.
+        let deleted = fixture_recipe(|json| json["deleted"] = 1655000000000_u64.into());
+        let rolled_back = current.rolled_back_to(deleted, None).unwrap();
+        assert_eq!(rolled_back.deleted, None);
+    }
+
+    #[test]
+    fn keeps_previous_shorts_for_redirects() {
+        let previous = fixture_recipe(|json| json["previousShorts"] = vec!["bread"].into());
+        let mut renamed = fixture_recipe(|json| json["short"] = "jam-toast".into());
+        renamed.inherit_shorts(&previous);
+        assert_eq!(renamed.previous_shorts, ["bread", "toast"]);
+
+        // Keeping the short does not add it to the history.
+        let mut kept = fixture_recipe(|_| {});
+        kept.inherit_shorts(&previous);
+        assert_eq!(kept.previous_shorts, ["bread"]);
+    }
+
+    #[test]
+    fn forgets_previous_short_when_renamed_back() {
+        let previous = fixture_recipe(|json| {
+            json["short"] = "jam-toast".into();
+            json["previousShorts"] = vec!["toast", "bread"].into();
+        });
+        let mut renamed = fixture_recipe(|_| {});
+        renamed.inherit_shorts(&previous);
+        assert_eq!(renamed.previous_shorts, ["bread", "jam-toast"]);
+    }
+
+    #[test]
+    fn reports_every_broken_limit() {
+        fn build(change: impl FnOnce(&mut serde_json::Value)) -> Result<Recipe, Vec<Violation>> {
+            RecipeBuilder::from(fixture_recipe(change)).build()
+        }
+        assert!(build(|json| json["title"] = "a".repeat(80).into()).is_ok());
+
+        let violations = build(|json| {
+            json["title"] = "a".repeat(81).into();
+            json["gradient"] = serde_json::json!(["#FFFFFF", "#00000g"]);
+            json["image"] = format!("https://example.com/{}", "a".repeat(381)).into();
+        })
+        .unwrap_err();
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, ["title", "image", "gradient[0]", "gradient[1]"]);
+        assert_eq!(
+            violations[0].message,
+            "Must be at most 80 characters, but is 81."
+        );
+        assert_eq!(
+            violations[1].message,
+            "Must be at most 400 characters, but is 401."
+        );
+    }
+}
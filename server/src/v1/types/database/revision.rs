@@ -0,0 +1,64 @@
+use crate::v1::types::database::Recipe;
+use crate::v1::types::{Date, Uuid};
+
+/// A previous version of a [`Recipe`], stored in the revisions collection
+/// whenever the recipe is overwritten.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct RecipeRevision {
+    /// The unique identifier of the revision.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The UUID of the recipe this is a revision of.
+    pub recipe: Uuid,
+    /// The revision number of the recipe.
+    pub revision: u32,
+    /// The staff member who wrote the revision, if known.
+    pub author: Option<Uuid>,
+    /// The date the revision was written. None if the revision was written
+    /// before revisions were tracked.
+    pub timestamp: Option<Date>,
+    /// The recipe as it was at this revision.
+    pub content: Recipe,
+}
+
+impl RecipeRevision {
+    /// Constructs a new revision from the current state of a recipe.
+    pub fn new(recipe: Recipe) -> Self {
+        Self {
+            uuid: Uuid::random(),
+            recipe: recipe.uuid,
+            revision: recipe.revision,
+            author: recipe.last_modified_by,
+            timestamp: recipe.last_modified,
+            content: recipe,
+        }
+    }
+
+    /// Returns the recipe as it was at this revision.
+    pub fn into_content(self) -> Recipe {
+        self.content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+
+    #[test]
+    fn archives_recipe_with_its_author() {
+        let mut recipe = fixture_recipe(|_| {});
+        let author = Uuid::random();
+        recipe.set_revision(3);
+        recipe.set_last_modified(Some(author));
+
+        let revision = RecipeRevision::new(recipe.clone());
+        assert_eq!(revision.recipe, recipe.uuid);
+        assert_eq!(revision.revision, 3);
+        assert_eq!(revision.author, Some(author));
+        assert_eq!(revision.timestamp, recipe.last_modified);
+        assert_eq!(revision.into_content(), recipe);
+    }
+}
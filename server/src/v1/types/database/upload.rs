@@ -0,0 +1,29 @@
+use crate::v1::types::*;
+
+/// An image that was uploaded, so it can be removed if it is never used.
+///
+/// The variants of the image are stored under `images/{uuid}/`, so anything
+/// that uses the image has a URL containing that path.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[readonly::make]
+pub struct Upload {
+    /// The unique identifier of the image.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The date the image was uploaded.
+    pub date: Date,
+    /// The key every variant of the image is stored under.
+    pub keys: Vec<String>,
+}
+
+impl Upload {
+    /// Constructs a new record of an image uploaded now.
+    pub fn new(uuid: Uuid, keys: Vec<String>) -> Self {
+        Self {
+            uuid,
+            date: Date::now(),
+            keys,
+        }
+    }
+}
@@ -32,6 +32,19 @@ impl Date {
     pub fn ms(&self) -> u64 {
         self.0
     }
+
+    /// Formats the date as in RFC 2822, such as
+    /// `Tue, 1 Jul 2003 10:52:37 +0000`. Used by RSS feeds.
+    pub fn to_rfc2822(self) -> String {
+        chrono::DateTime::<chrono::Utc>::from(self).to_rfc2822()
+    }
+
+    /// Formats the date as in RFC 3339, such as `2003-07-01T10:52:37Z`.
+    /// Used by Atom feeds and sitemaps.
+    pub fn to_rfc3339(self) -> String {
+        chrono::DateTime::<chrono::Utc>::from(self)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    }
 }
 
 impl std::ops::Add for Date {
@@ -88,9 +101,7 @@ impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Date {
 // Date -> DateTime<Utc>
 impl From<Date> for chrono::DateTime<chrono::Utc> {
     fn from(date: Date) -> Self {
-        chrono::DateTime::<chrono::Utc>::from_utc(
-            chrono::NaiveDateTime::from_timestamp(date.0 as i64, 0),
-            chrono::Utc,
-        )
+        // The date is in milliseconds, not seconds.
+        chrono::DateTime::<chrono::Utc>::from_timestamp_millis(date.0 as i64).unwrap_or_default()
     }
 }
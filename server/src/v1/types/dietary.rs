@@ -0,0 +1,150 @@
+use crate::v1::types::validation::*;
+
+/// An allergen that must be declared on food in Australia and New Zealand.
+///
+/// Follows the FSANZ list of mandatory allergen declarations. Wheat and
+/// the other cereals are covered by `Gluten`, and crustaceans and molluscs
+/// by `Shellfish`.
+#[derive(
+    Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Allergen {
+    Gluten,
+    Peanut,
+    TreeNut,
+    Egg,
+    Milk,
+    Sesame,
+    Soy,
+    Fish,
+    Shellfish,
+    Lupin,
+}
+
+impl From<Allergen> for mongodb::bson::Bson {
+    fn from(allergen: Allergen) -> mongodb::bson::Bson {
+        // Stored the same way it is sent, so the serde name is the only one.
+        mongodb::bson::to_bson(&allergen).expect("allergens serialize as strings")
+    }
+}
+
+/// A diet that a recipe is suitable for.
+#[derive(
+    Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Diet {
+    Vegetarian,
+    Vegan,
+    Halal,
+}
+
+impl Diet {
+    /// Returns the allergens a recipe suitable for the diet can not contain.
+    pub fn excluded_allergens(&self) -> &'static [Allergen] {
+        match self {
+            Diet::Vegetarian => &[Allergen::Fish, Allergen::Shellfish],
+            Diet::Vegan => &[
+                Allergen::Egg,
+                Allergen::Milk,
+                Allergen::Fish,
+                Allergen::Shellfish,
+            ],
+            Diet::Halal => &[],
+        }
+    }
+}
+
+impl From<Diet> for mongodb::bson::Bson {
+    fn from(diet: Diet) -> mongodb::bson::Bson {
+        mongodb::bson::to_bson(&diet).expect("diets serialize as strings")
+    }
+}
+
+/// Returns the name something is stored and sent as.
+fn name(value: impl Into<mongodb::bson::Bson>) -> String {
+    match value.into() {
+        mongodb::bson::Bson::String(name) => name,
+        other => other.to_string(),
+    }
+}
+
+/// Checks no diet is tagged on a recipe that contains an allergen the
+/// diet excludes, such as a vegan recipe that contains egg.
+///
+/// If the allergens are not known, only diets that exclude no allergens
+/// can be tagged, as the recipe may contain any of them.
+pub fn validate_diets(
+    diets: &[Diet],
+    allergens: Option<&[Allergen]>,
+    path: &str,
+    violations: &mut Vec<Violation>,
+) {
+    for (i, diet) in diets.iter().enumerate() {
+        let excluded = diet.excluded_allergens();
+        let allergens = match allergens {
+            Some(allergens) => allergens,
+            None => {
+                if !excluded.is_empty() {
+                    violations.push(Violation::new(
+                        format!("{}[{}]", path, i),
+                        format!(
+                            "A {} recipe must list the allergens it contains.",
+                            name(*diet)
+                        ),
+                    ));
+                }
+                continue;
+            }
+        };
+        for allergen in excluded {
+            if allergens.contains(allergen) {
+                violations.push(Violation::new(
+                    format!("{}[{}]", path, i),
+                    format!(
+                        "A {} recipe can not contain {}.",
+                        name(*diet),
+                        name(*allergen)
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_serde_names() {
+        assert_eq!(name(Allergen::TreeNut), "treeNut");
+        assert_eq!(name(Diet::Vegan), "vegan");
+    }
+
+    #[test]
+    fn checks_diets_against_allergens() {
+        let check = |allergens: Option<&[Allergen]>| {
+            let mut violations = vec![];
+            validate_diets(
+                &[Diet::Halal, Diet::Vegan],
+                allergens,
+                "diets",
+                &mut violations,
+            );
+            violations
+        };
+        assert!(check(Some(&[Allergen::Gluten])).is_empty());
+
+        let violations = check(Some(&[Allergen::Egg, Allergen::Milk]));
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].path, "diets[1]");
+        assert!(violations[0].message.contains("vegan"));
+
+        // Halal excludes nothing, so only the vegan tag needs allergens.
+        let violations = check(None);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "diets[1]");
+    }
+}
@@ -1,9 +1,14 @@
+use crate::v1::types::markup::{self, Block};
+use crate::v1::types::validation::*;
+
 /// A formattable is a markdown-like string that can be formatted by the client.
-/// Its markdown implementation has not yet been added to the front-end
-/// and its definition still not in place.
+/// The markup it is written in is defined in [`markup`], which can also parse
+/// it and render it as HTML or plain text.
 ///
-/// Its purpose as a seperate type in the backend is purely to make it obvious
+/// Its purpose as a seperate type in the backend is to make it obvious
 /// that some fields are formattable and some are simply strings.
+///
+/// [`markup`]: crate::v1::types::markup
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct Formattable(String);
 
@@ -17,6 +22,54 @@ impl Formattable {
     pub fn new_empty() -> Self {
         Self::default()
     }
+
+    /// Returns the raw, unformatted content.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parses the content into the blocks of its document.
+    pub fn parse(&self) -> Result<Vec<Block>, markup::MarkupError> {
+        markup::parse(&self.0)
+    }
+
+    /// Checks the content is valid markup, returning its blocks if it is.
+    ///
+    /// This does not check what the content refers to, so is for text that
+    /// does not belong to a single recipe.
+    pub fn validate_markup(
+        &self,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) -> Option<Vec<Block>> {
+        match self.parse() {
+            Ok(blocks) => Some(blocks),
+            Err(err) => {
+                violations.push(Violation::new(path, err));
+                None
+            }
+        }
+    }
+
+    /// Checks the content is valid markup, and only refers to ingredients
+    /// that exist, where the recipe has `ingredients` ingredients.
+    pub fn validate(&self, path: &str, ingredients: usize, violations: &mut Vec<Violation>) {
+        let blocks = match self.validate_markup(path, violations) {
+            Some(blocks) => blocks,
+            None => return,
+        };
+        for index in markup::ingredient_references(&blocks) {
+            if index > ingredients {
+                violations.push(Violation::new(
+                    path,
+                    format!(
+                        "Refers to ingredient {}, but there are only {} ingredients.",
+                        index, ingredients
+                    ),
+                ));
+            }
+        }
+    }
 }
 
 // Implements ToString on Formattable.
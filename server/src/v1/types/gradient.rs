@@ -1,6 +1,38 @@
+use crate::v1::types::validation::Violation;
+
 /// Holds the gradient information for a Recipe.
 ///
 /// Colors should match the regex `#[0-9a-f]{6}`.
 #[derive(Default, Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
 #[readonly::make]
 pub struct Gradient(String, String);
+
+impl Gradient {
+    /// Checks both colours of the gradient match `#[0-9a-f]{6}`.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (i, colour) in [&self.0, &self.1].into_iter().enumerate() {
+            if !is_hex_colour(colour) {
+                violations.push(Violation::new(
+                    format!("{}[{}]", path, i),
+                    format!(
+                        "`{}` is not a valid colour. Expected `#` followed by 6 lowercase hex digits.",
+                        colour
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Returns whether `colour` matches `#[0-9a-f]{6}`.
+fn is_hex_colour(colour: &str) -> bool {
+    match colour.strip_prefix('#') {
+        Some(hex) => {
+            hex.len() == 6
+                && hex
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,910 @@
+use crate::v1::types::units::UnitSystem;
+use crate::v1::types::validation::*;
+
+/// The maximum length of an ingredient's text, in characters.
+const MAX_INGREDIENT_CHARS: usize = 80;
+
+/// A single ingredient of a recipe, such as `2 1/2 cups plain flour, sifted`.
+///
+/// The text the ingredient was written as is always kept in `raw`, so it can
+/// be shown exactly as written. The other fields are parsed from it, which
+/// allows the ingredient to be scaled, converted and aggregated.
+///
+/// Ingredients can be deserialized from either a plain string or an object
+/// as they are serialized. Either way only `raw` is read, and every other
+/// field is parsed from it, so they can never contradict it. Ingredients
+/// stored before they were structured are plain strings, and are upgraded
+/// when read.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", from = "IngredientRepr")]
+#[readonly::make]
+pub struct Ingredient {
+    /// The ingredient exactly as it was written. Max 80 chars.
+    pub raw: String,
+    /// How much of the ingredient is needed, if given.
+    pub quantity: Option<Quantity>,
+    /// The unit of the quantity, if it is a known unit.
+    pub unit: Option<Unit>,
+    /// The name of the ingredient, such as `plain flour`.
+    pub name: String,
+    /// How the ingredient should be prepared, such as `sifted`.
+    pub note: Option<String>,
+    /// Whether the ingredient can be left out.
+    pub optional: bool,
+}
+
+impl Ingredient {
+    /// Parses an ingredient from the text it was written as.
+    ///
+    /// Parsing never fails. Anything that cannot be understood is kept as
+    /// part of the name.
+    pub fn parse(raw: impl ToString) -> Self {
+        let raw = raw.to_string();
+        let mut rest = raw.trim();
+
+        let quantity = Quantity::parse(&mut rest);
+        // Units are only looked for after a quantity, as `cup` in
+        // `cup mushrooms` is part of the name.
+        let unit = match quantity {
+            Some(_) => Unit::parse(&mut rest),
+            None => None,
+        };
+        if unit.is_some() {
+            rest = rest.strip_prefix("of ").unwrap_or(rest).trim_start();
+        }
+
+        // `(optional)` can appear anywhere, but is usually at the end.
+        let mut optional = false;
+        let mut text = rest.to_string();
+        if let Some(start) = text.to_ascii_lowercase().find("(optional)") {
+            optional = true;
+            text.replace_range(start..start + "(optional)".len(), "");
+        }
+
+        // Everything after the first comma is the preparation note.
+        let (name, note) = match text.split_once(',') {
+            Some((name, note)) => (name, Some(note)),
+            None => (text.as_str(), None),
+        };
+        let mut notes = vec![];
+        for part in note.into_iter().flat_map(|note| note.split(',')) {
+            let part = part.trim();
+            if part.eq_ignore_ascii_case("optional") {
+                optional = true;
+            } else if !part.is_empty() {
+                notes.push(part);
+            }
+        }
+        let note = match notes.is_empty() {
+            true => None,
+            false => Some(notes.join(", ")),
+        };
+
+        Self {
+            name: name.split_whitespace().collect::<Vec<_>>().join(" "),
+            raw,
+            quantity,
+            unit,
+            note,
+            optional,
+        }
+    }
+
+    /// Returns the ingredient with its quantity multiplied by `factor`,
+    /// rounded to an amount that is easy to measure.
+    ///
+    /// The quantity in `raw` is rewritten to match, so the ingredient can
+    /// still be shown as text. Ingredients without a quantity, such as
+    /// `salt, to taste`, are unchanged.
+    pub fn scale(&self, factor: Fraction) -> Self {
+        let quantity = match self
+            .quantity
+            .and_then(|quantity| quantity.scale(factor, self.unit))
+        {
+            Some(quantity) => quantity,
+            None => return self.clone(),
+        };
+
+        self.with_measure(quantity, self.unit)
+    }
+
+    /// Returns the ingredient converted to the units used by `system`.
+    ///
+    /// The quantity and unit in `raw` are rewritten to match. Ingredients
+    /// that are already in the right units, or can't be converted, are
+    /// unchanged.
+    pub fn convert(&self, system: UnitSystem) -> Self {
+        match self.quantity.zip(self.unit) {
+            Some((quantity, unit)) => match system.convert(quantity, unit, &self.name) {
+                Some((quantity, unit)) => self.with_measure(quantity, Some(unit)),
+                None => self.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// Returns the ingredient with a new quantity and unit, rewriting them
+    /// at the start of `raw` and keeping the rest of it as it was written.
+    fn with_measure(&self, quantity: Quantity, unit: Option<Unit>) -> Self {
+        let text = self.raw.trim_start();
+        let mut rest = text;
+        let raw = match Quantity::parse(&mut rest) {
+            // The unit is kept as written if it has not changed.
+            Some(_) if unit == self.unit => {
+                let written = text[..text.len() - rest.len()].trim_end();
+                format!("{}{}", quantity, &text[written.len()..])
+            }
+            Some(_) if Unit::parse(&mut rest) == self.unit => match unit {
+                Some(unit) => format!("{} {} {}", quantity, unit.name(quantity.is_plural()), rest)
+                    .trim_end()
+                    .to_string(),
+                None => format!("{} {}", quantity, rest).trim_end().to_string(),
+            },
+            // The text does not start with the measure, so it can't be
+            // rewritten.
+            _ => self.raw.clone(),
+        };
+
+        Self {
+            raw,
+            quantity: Some(quantity),
+            unit,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the ingredient as it was written.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Checks the ingredient is within the documented limits.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_max_chars(violations, path, &self.raw, MAX_INGREDIENT_CHARS);
+        if let Some(quantity) = &self.quantity {
+            quantity.validate(&format!("{}.quantity", path), violations);
+        }
+    }
+}
+
+impl std::fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl From<String> for Ingredient {
+    fn from(raw: String) -> Self {
+        Self::parse(raw)
+    }
+}
+
+impl From<&str> for Ingredient {
+    fn from(raw: &str) -> Self {
+        Self::parse(raw)
+    }
+}
+
+/// The forms an [`Ingredient`] can be deserialized from.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum IngredientRepr {
+    /// The text of the ingredient.
+    Raw(String),
+    /// An ingredient as it is serialized. Only its text is read.
+    Structured(StructuredIngredient),
+}
+
+/// An [`Ingredient`] as it is serialized. Every field other than `raw` is
+/// ignored, as it is parsed from `raw` again.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StructuredIngredient {
+    raw: String,
+}
+
+impl From<IngredientRepr> for Ingredient {
+    fn from(repr: IngredientRepr) -> Self {
+        match repr {
+            IngredientRepr::Raw(raw) => Self::parse(raw),
+            IngredientRepr::Structured(ingredient) => Self::parse(ingredient.raw),
+        }
+    }
+}
+
+/// How much of an ingredient is needed. Either an exact amount such as `2`,
+/// or a range such as `1-2`.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Quantity {
+    /// The amount, or the smallest amount if this is a range.
+    pub min: Fraction,
+    /// The largest amount if this is a range.
+    pub max: Option<Fraction>,
+}
+
+impl Quantity {
+    /// Parses a quantity from the start of `text`, such as `2 1/2`, `½`,
+    /// `1.5`, `1-2` or `1 to 2`.
+    ///
+    /// If a quantity is found, `text` is advanced past it.
+    pub fn parse(text: &mut &str) -> Option<Self> {
+        let mut rest = *text;
+        let min = Fraction::parse(&mut rest)?;
+
+        // Check if this is a range.
+        let mut range = rest.trim_start();
+        let max = match range.strip_prefix(['-', '–']) {
+            Some(after) => Some(after),
+            None => range.strip_prefix("to "),
+        }
+        .and_then(|after| {
+            range = after.trim_start();
+            Fraction::parse(&mut range)
+        });
+        if max.is_some() {
+            rest = range;
+        }
+
+        *text = rest.trim_start();
+        Some(Self { min, max })
+    }
+
+    /// Returns the quantity multiplied by `factor`, rounded to an amount
+    /// that is easy to measure in `unit`.
+    ///
+    /// Returns None if the quantity is too large to scale.
+    pub fn scale(&self, factor: Fraction, unit: Option<Unit>) -> Option<Self> {
+        let scale = |amount: Fraction| amount.checked_mul(factor).map(|amount| amount.round(unit));
+        Some(Self {
+            min: scale(self.min)?,
+            max: match self.max {
+                Some(max) => Some(scale(max)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Returns whether the quantity is more than one, so its unit should be
+    /// plural.
+    pub fn is_plural(&self) -> bool {
+        self.max.unwrap_or(self.min).as_f64() > 1.0
+    }
+
+    /// Checks the quantity is a valid amount.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.min.validate(&format!("{}.min", path), violations);
+        if let Some(max) = &self.max {
+            max.validate(&format!("{}.max", path), violations);
+        }
+    }
+}
+
+impl std::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.max {
+            Some(max) => write!(f, "{}-{}", self.min, max),
+            None => write!(f, "{}", self.min),
+        }
+    }
+}
+
+/// The fractions that can be written as a single character, such as `½`.
+const UNICODE_FRACTIONS: [(char, u32, u32); 15] = [
+    ('½', 1, 2),
+    ('⅓', 1, 3),
+    ('⅔', 2, 3),
+    ('¼', 1, 4),
+    ('¾', 3, 4),
+    ('⅕', 1, 5),
+    ('⅖', 2, 5),
+    ('⅗', 3, 5),
+    ('⅘', 4, 5),
+    ('⅙', 1, 6),
+    ('⅚', 5, 6),
+    ('⅛', 1, 8),
+    ('⅜', 3, 8),
+    ('⅝', 5, 8),
+    ('⅞', 7, 8),
+];
+
+/// An exact, non-negative amount, such as `5/2`.
+///
+/// Amounts are kept as fractions so quantities like `1/3` can be scaled
+/// without rounding errors.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Fraction {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl Fraction {
+    /// Constructs a new Fraction in its simplest form.
+    ///
+    /// Returns None if the denominator is 0.
+    pub fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator);
+        Some(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Constructs a new Fraction of a whole number.
+    pub fn whole(number: u32) -> Self {
+        Self {
+            numerator: number,
+            denominator: 1,
+        }
+    }
+
+    /// Returns the fraction as a decimal.
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Multiplies two fractions together, returning None on overflow.
+    pub fn checked_mul(self, other: Fraction) -> Option<Fraction> {
+        let numerator = self.numerator as u64 * other.numerator as u64;
+        let denominator = self.denominator as u64 * other.denominator as u64;
+        Self::from_u64(numerator, denominator)
+    }
+
+    /// Rounds the fraction to an amount that is easy to measure in `unit`.
+    ///
+    /// Grams and millilitres are rounded to whole numbers, or multiples of 5
+    /// from 100. Anything else is rounded to a whole number from 10, and
+    /// below that to the nearest half, third or quarter, or eighth if less
+    /// than 1. Amounts are never rounded down to 0.
+    pub fn round(self, unit: Option<Unit>) -> Fraction {
+        Self::approximate(self.as_f64(), unit)
+    }
+
+    /// Returns the fraction closest to `value` that is easy to measure in
+    /// `unit`, following the same rules as [`Fraction::round`].
+    pub fn approximate(value: f64, unit: Option<Unit>) -> Fraction {
+        if value <= 0.0 || !value.is_finite() {
+            return Fraction::whole(0);
+        }
+
+        let steps: &[u32] = match (unit, value) {
+            (Some(Unit::Gram | Unit::Millilitre), value) if value >= 100.0 => {
+                let rounded = ((value / 5.0).round() * 5.0) as u32;
+                return Fraction::whole(rounded);
+            }
+            (Some(Unit::Gram | Unit::Millilitre), _) => &[1],
+            (_, value) if value >= 10.0 => &[1],
+            (_, value) if value >= 1.0 => &[1, 2, 3, 4],
+            _ => &[1, 2, 3, 4, 8],
+        };
+
+        // Finds the closest fraction, preferring simpler denominators.
+        let mut closest = Fraction::whole(value.round() as u32);
+        for &denominator in steps {
+            let numerator = (value * denominator as f64).round() as u32;
+            if let Some(candidate) = Fraction::new(numerator, denominator) {
+                if (candidate.as_f64() - value).abs() < (closest.as_f64() - value).abs() {
+                    closest = candidate;
+                }
+            }
+        }
+
+        match closest.numerator {
+            0 => Fraction::new(1, *steps.last().unwrap_or(&1)).unwrap_or(closest),
+            _ => closest,
+        }
+    }
+
+    /// Adds two fractions together, returning None on overflow.
+    pub fn checked_add(self, other: Fraction) -> Option<Fraction> {
+        let numerator = (self.numerator as u64 * other.denominator as u64)
+            + (other.numerator as u64 * self.denominator as u64);
+        let denominator = self.denominator as u64 * other.denominator as u64;
+        Self::from_u64(numerator, denominator)
+    }
+
+    /// Simplifies a fraction that may not fit in a u32 until simplified.
+    fn from_u64(numerator: u64, denominator: u64) -> Option<Fraction> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd_u64(numerator, denominator);
+        Some(Self {
+            numerator: (numerator / divisor).try_into().ok()?,
+            denominator: (denominator / divisor).try_into().ok()?,
+        })
+    }
+
+    /// Parses an amount from the start of `text`, such as `2`, `1.5`,
+    /// `1/2`, `½`, `2½`, `2 ½` or `2 1/2`.
+    ///
+    /// If an amount is found, `text` is advanced past it.
+    pub fn parse(text: &mut &str) -> Option<Self> {
+        let mut rest = *text;
+        let amount = match parse_unicode_fraction(&mut rest) {
+            Some(fraction) => fraction,
+            None => {
+                let whole = parse_number(&mut rest)?;
+                if let Some(numerator) = whole.as_integer() {
+                    // Check for `1/2`.
+                    if let Some(fraction) = rest
+                        .strip_prefix('/')
+                        .and_then(|after| parse_denominator(numerator, after))
+                    {
+                        *text = fraction.1;
+                        return Some(fraction.0);
+                    }
+                }
+
+                // Check for the fraction of a mixed number, like `2½`, `2 ½`
+                // or `2 1/2`.
+                let mut after = rest.strip_prefix(' ').unwrap_or(rest);
+                if let Some(fraction) = parse_unicode_fraction(&mut after) {
+                    rest = after;
+                    whole.checked_add(fraction)?
+                } else if let Some((fraction, after)) = parse_mixed_fraction(rest) {
+                    rest = after;
+                    whole.checked_add(fraction)?
+                } else {
+                    whole
+                }
+            }
+        };
+
+        *text = rest;
+        Some(amount)
+    }
+
+    /// Returns the fraction as a whole number, if it is one.
+    fn as_integer(&self) -> Option<u32> {
+        match self.denominator {
+            1 => Some(self.numerator),
+            _ => None,
+        }
+    }
+
+    /// Checks the fraction does not divide by 0.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.denominator == 0 {
+            violations.push(Violation::new(
+                format!("{}.denominator", path),
+                "The denominator of a fraction cannot be 0.",
+            ));
+        }
+    }
+}
+
+impl std::fmt::Display for Fraction {
+    /// Writes the fraction as a mixed number, such as `2 1/2`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 0 {
+            return write!(f, "{}/0", self.numerator);
+        }
+        let whole = self.numerator / self.denominator;
+        let remainder = self.numerator % self.denominator;
+        match (whole, remainder) {
+            (whole, 0) => write!(f, "{}", whole),
+            (0, remainder) => write!(f, "{}/{}", remainder, self.denominator),
+            (whole, remainder) => write!(f, "{} {}/{}", whole, remainder, self.denominator),
+        }
+    }
+}
+
+/// Parses a whole or decimal number from the start of `text`.
+fn parse_number(text: &mut &str) -> Option<Fraction> {
+    let digits = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    if digits == 0 {
+        return None;
+    }
+    let whole: u32 = text[..digits].parse().ok()?;
+    let mut rest = &text[digits..];
+
+    let mut number = Fraction::whole(whole);
+    if let Some(after) = rest.strip_prefix('.') {
+        let decimals = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        // More than 6 decimal places won't be used in a recipe.
+        if decimals > 0 && decimals <= 6 {
+            let numerator: u32 = after[..decimals].parse().ok()?;
+            let decimal = Fraction::new(numerator, 10u32.pow(decimals as u32))?;
+            number = number.checked_add(decimal)?;
+            rest = &after[decimals..];
+        }
+    }
+
+    *text = rest;
+    Some(number)
+}
+
+/// Parses the denominator of a fraction whose numerator and `/` have been
+/// parsed, returning the fraction and the text after it.
+fn parse_denominator(numerator: u32, text: &str) -> Option<(Fraction, &str)> {
+    let digits = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let denominator = text[..digits].parse().ok()?;
+    Some((Fraction::new(numerator, denominator)?, &text[digits..]))
+}
+
+/// Parses the `1/2` of a mixed number such as `2 1/2`, returning the fraction
+/// and the text after it.
+fn parse_mixed_fraction(text: &str) -> Option<(Fraction, &str)> {
+    let after = text.strip_prefix(' ')?.trim_start();
+    let digits = after
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after.len());
+    let numerator = after[..digits].parse().ok()?;
+    let (fraction, rest) = parse_denominator(numerator, after[digits..].strip_prefix('/')?)?;
+    // `2 3/2` is not a mixed number.
+    match fraction.numerator < fraction.denominator {
+        true => Some((fraction, rest)),
+        false => None,
+    }
+}
+
+/// Parses a fraction written as a single character, such as `½`, from the
+/// start of `text`.
+fn parse_unicode_fraction(text: &mut &str) -> Option<Fraction> {
+    let first = text.chars().next()?;
+    let (_, numerator, denominator) = UNICODE_FRACTIONS
+        .iter()
+        .find(|(character, _, _)| *character == first)?;
+    *text = &text[first.len_utf8()..];
+    Fraction::new(*numerator, *denominator)
+}
+
+/// Returns the greatest common divisor of two numbers.
+fn gcd(a: u32, b: u32) -> u32 {
+    gcd_u64(a as u64, b as u64) as u32
+}
+
+/// Returns the greatest common divisor of two numbers.
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    // gcd(0, 0) is 0, but is only used as a divisor.
+    a.max(1)
+}
+
+/// A unit that an ingredient can be measured in.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    #[serde(rename = "tsp")]
+    Teaspoon,
+    #[serde(rename = "tbsp")]
+    Tablespoon,
+    #[serde(rename = "cup")]
+    Cup,
+    #[serde(rename = "ml")]
+    Millilitre,
+    #[serde(rename = "l")]
+    Litre,
+    #[serde(rename = "floz")]
+    FluidOunce,
+    #[serde(rename = "g")]
+    Gram,
+    #[serde(rename = "kg")]
+    Kilogram,
+    #[serde(rename = "oz")]
+    Ounce,
+    #[serde(rename = "lb")]
+    Pound,
+    #[serde(rename = "pinch")]
+    Pinch,
+}
+
+/// The ways each unit can be written. Matched case-insensitively, and
+/// ignoring a trailing `.`.
+const UNIT_NAMES: [(Unit, &[&str]); 11] = [
+    (Unit::Teaspoon, &["tsp", "tsps", "teaspoon", "teaspoons"]),
+    (
+        Unit::Tablespoon,
+        &["tbsp", "tbsps", "tbs", "tablespoon", "tablespoons"],
+    ),
+    (Unit::Cup, &["cup", "cups"]),
+    (
+        Unit::Millilitre,
+        &[
+            "ml",
+            "millilitre",
+            "millilitres",
+            "milliliter",
+            "milliliters",
+        ],
+    ),
+    (Unit::Litre, &["l", "litre", "litres", "liter", "liters"]),
+    (Unit::FluidOunce, &["floz"]),
+    (Unit::Gram, &["g", "gram", "grams", "gramme", "grammes"]),
+    (
+        Unit::Kilogram,
+        &["kg", "kgs", "kilogram", "kilograms", "kilo", "kilos"],
+    ),
+    (Unit::Ounce, &["oz", "ounce", "ounces"]),
+    (Unit::Pound, &["lb", "lbs", "pound", "pounds"]),
+    (Unit::Pinch, &["pinch", "pinches"]),
+];
+
+impl Unit {
+    /// Parses a unit from the start of `text`, such as `cups` or `tbsp.`.
+    ///
+    /// If a unit is found, `text` is advanced past it.
+    pub fn parse(text: &mut &str) -> Option<Self> {
+        let (word, rest) = next_word(text);
+        // Fluid ounces are written as two words.
+        let (word, rest) = if word.eq_ignore_ascii_case("fl") || word.eq_ignore_ascii_case("fl.") {
+            let (oz, rest) = next_word(rest);
+            (format!("fl{}", oz), rest)
+        } else {
+            (word.to_string(), rest)
+        };
+
+        let word = word.to_lowercase();
+        let word = word.strip_suffix('.').unwrap_or(&word);
+        let unit = UNIT_NAMES
+            .iter()
+            .find(|(_, names)| names.contains(&word))
+            .map(|(unit, _)| *unit)?;
+        *text = rest;
+        Some(unit)
+    }
+
+    /// Returns the name of the unit as it is written after a quantity, such
+    /// as `tbsp` or `cups`.
+    pub fn name(&self, plural: bool) -> &'static str {
+        match (self, plural) {
+            (Unit::Cup, true) => "cups",
+            (Unit::Pinch, true) => "pinches",
+            (unit, _) => unit.as_str(),
+        }
+    }
+
+    /// Returns the short name of the unit, such as `tbsp`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Unit::Teaspoon => "tsp",
+            Unit::Tablespoon => "tbsp",
+            Unit::Cup => "cup",
+            Unit::Millilitre => "ml",
+            Unit::Litre => "l",
+            Unit::FluidOunce => "fl oz",
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Ounce => "oz",
+            Unit::Pound => "lb",
+            Unit::Pinch => "pinch",
+        }
+    }
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Splits the first word off `text`, returning the word and the text after
+/// it with leading whitespace removed.
+fn next_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| c.is_whitespace() || c == ',' || c == '(')
+        .unwrap_or(text.len());
+    (&text[..end], text[end..].trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fraction(numerator: u32, denominator: u32) -> Fraction {
+        Fraction::new(numerator, denominator).unwrap()
+    }
+
+    fn exactly(numerator: u32, denominator: u32) -> Option<Quantity> {
+        Some(Quantity {
+            min: fraction(numerator, denominator),
+            max: None,
+        })
+    }
+
+    #[test]
+    fn parses_mixed_fractions() {
+        let ingredient = Ingredient::parse("2 1/2 cups plain flour, sifted");
+        assert_eq!(ingredient.quantity, exactly(5, 2));
+        assert_eq!(ingredient.unit, Some(Unit::Cup));
+        assert_eq!(ingredient.name, "plain flour");
+        assert_eq!(ingredient.note.as_deref(), Some("sifted"));
+        assert!(!ingredient.optional);
+
+        assert_eq!(Ingredient::parse("3/4 cup sugar").quantity, exactly(3, 4));
+        assert_eq!(Ingredient::parse("1.5 kg potatoes").quantity, exactly(3, 2));
+        // `3/2` can not follow a whole number, so only the `2` is a quantity.
+        let improper = Ingredient::parse("2 3/2 eggs");
+        assert_eq!(improper.quantity, exactly(2, 1));
+        assert_eq!(improper.name, "3/2 eggs");
+    }
+
+    #[test]
+    fn parses_unicode_fractions() {
+        assert_eq!(Ingredient::parse("½ tsp salt").quantity, exactly(1, 2));
+        assert_eq!(Ingredient::parse("2½ cups milk").quantity, exactly(5, 2));
+        assert_eq!(Ingredient::parse("1 ⅓ cups oats").quantity, exactly(4, 3));
+        assert_eq!(Ingredient::parse("⅞ cup water").unit, Some(Unit::Cup));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let range = Some(Quantity {
+            min: Fraction::whole(1),
+            max: Some(Fraction::whole(2)),
+        });
+        for raw in ["1-2 tbsp oil", "1 - 2 tbsp oil", "1–2 tbsp oil"] {
+            let ingredient = Ingredient::parse(raw);
+            assert_eq!(ingredient.quantity, range, "{}", raw);
+            assert_eq!(ingredient.unit, Some(Unit::Tablespoon), "{}", raw);
+            assert_eq!(ingredient.name, "oil", "{}", raw);
+        }
+
+        let cloves = Ingredient::parse("1 to 2 cloves garlic");
+        assert_eq!(cloves.quantity, range);
+        assert_eq!(cloves.unit, None);
+        assert_eq!(cloves.name, "cloves garlic");
+
+        let halves = Ingredient::parse("½-1 cup stock");
+        assert_eq!(halves.quantity.unwrap().min, fraction(1, 2));
+        assert_eq!(halves.quantity.unwrap().max, Some(Fraction::whole(1)));
+    }
+
+    #[test]
+    fn parses_unit_aliases() {
+        let units = [
+            ("3 Tbsp. butter", Unit::Tablespoon),
+            ("3 tablespoons butter", Unit::Tablespoon),
+            ("1 TSP vanilla", Unit::Teaspoon),
+            ("2 fl oz cream", Unit::FluidOunce),
+            ("2 fl. oz cream", Unit::FluidOunce),
+            ("1 kilo potatoes", Unit::Kilogram),
+            ("500 grams mince", Unit::Gram),
+            ("250 millilitres milk", Unit::Millilitre),
+            ("1 liter water", Unit::Litre),
+            ("2 lbs chicken", Unit::Pound),
+            ("1 pinch of salt", Unit::Pinch),
+        ];
+        for (raw, unit) in units {
+            assert_eq!(Ingredient::parse(raw).unit, Some(unit), "{}", raw);
+        }
+        assert_eq!(Ingredient::parse("1 pinch of salt").name, "salt");
+
+        // Units are only looked for after a quantity.
+        let mushrooms = Ingredient::parse("cup mushrooms");
+        assert_eq!(mushrooms.unit, None);
+        assert_eq!(mushrooms.name, "cup mushrooms");
+        assert_eq!(Ingredient::parse("2 large eggs").unit, None);
+    }
+
+    #[test]
+    fn parses_prep_notes() {
+        let onion = Ingredient::parse("1 onion, finely chopped, optional");
+        assert_eq!(onion.name, "onion");
+        assert_eq!(onion.note.as_deref(), Some("finely chopped"));
+        assert!(onion.optional);
+
+        let eggs = Ingredient::parse("2 eggs (optional), beaten");
+        assert_eq!(eggs.name, "eggs");
+        assert_eq!(eggs.note.as_deref(), Some("beaten"));
+        assert!(eggs.optional);
+
+        let salt = Ingredient::parse("salt, to taste");
+        assert_eq!(salt.quantity, None);
+        assert_eq!(salt.name, "salt");
+        assert_eq!(salt.note.as_deref(), Some("to taste"));
+
+        let butter = Ingredient::parse("100 g butter, softened, cubed");
+        assert_eq!(butter.note.as_deref(), Some("softened, cubed"));
+    }
+
+    #[test]
+    fn round_trips_raw() {
+        let raws = [
+            "2 1/2 cups plain flour, sifted",
+            "  2½ cups milk ",
+            "1–2 tbsp oil",
+            "2 eggs (optional), beaten",
+            "salt, to taste",
+            "3 Tbsp. butter",
+            "cup mushrooms",
+            "",
+        ];
+        for raw in raws {
+            let ingredient = Ingredient::parse(raw);
+            assert_eq!(ingredient.to_string(), raw);
+            assert_eq!(ingredient.as_str(), raw);
+
+            // Serialized ingredients are read back the same, as are plain
+            // strings.
+            let json = serde_json::to_value(&ingredient).unwrap();
+            let read: Ingredient = serde_json::from_value(json).unwrap();
+            assert_eq!(read, ingredient);
+            let read: Ingredient = serde_json::from_value(serde_json::json!(raw)).unwrap();
+            assert_eq!(read, ingredient);
+        }
+    }
+
+    #[test]
+    fn rederives_structured_fields() {
+        let read: Ingredient = serde_json::from_value(serde_json::json!({
+            "raw": "2 cups flour",
+            "quantity": {"min": {"numerator": 7, "denominator": 1}, "max": null},
+            "unit": "g",
+            "name": "sugar",
+            "note": "melted",
+            "optional": true,
+        }))
+        .unwrap();
+        assert_eq!(read, Ingredient::parse("2 cups flour"));
+    }
+
+    #[test]
+    fn rounds_to_kitchen_fractions() {
+        let cup = Some(Unit::Cup);
+        assert_eq!(
+            fraction(1, 3)
+                .checked_mul(Fraction::whole(2))
+                .unwrap()
+                .round(cup),
+            fraction(2, 3)
+        );
+        assert_eq!(Fraction::approximate(0.26, cup), fraction(1, 4));
+        assert_eq!(Fraction::approximate(0.1, cup), fraction(1, 8));
+        // Small amounts are never rounded away.
+        assert_eq!(Fraction::approximate(0.01, cup), fraction(1, 8));
+        assert_eq!(Fraction::approximate(1.3, cup), fraction(4, 3));
+        assert_eq!(Fraction::approximate(12.4, cup), Fraction::whole(12));
+        assert_eq!(
+            Fraction::approximate(0.4, Some(Unit::Gram)),
+            Fraction::whole(1)
+        );
+        assert_eq!(
+            Fraction::approximate(123.0, Some(Unit::Gram)),
+            Fraction::whole(125)
+        );
+    }
+
+    #[test]
+    fn scales_ingredients() {
+        let doubled = |raw| Ingredient::parse(raw).scale(Fraction::whole(2));
+        let flour = doubled("2 1/2 cups plain flour, sifted");
+        assert_eq!(flour.quantity, exactly(5, 1));
+        assert_eq!(flour.as_str(), "5 cups plain flour, sifted");
+        assert_eq!(doubled("1/3 cup milk").as_str(), "2/3 cup milk");
+
+        let oil = doubled("1-2 tbsp oil");
+        assert_eq!(
+            oil.quantity,
+            Some(Quantity {
+                min: Fraction::whole(2),
+                max: Some(Fraction::whole(4)),
+            })
+        );
+        assert_eq!(oil.as_str(), "2-4 tbsp oil");
+
+        let halved = Ingredient::parse("1 cup sugar").scale(fraction(1, 2));
+        assert_eq!(halved.as_str(), "1/2 cup sugar");
+    }
+
+    #[test]
+    fn scales_only_quantities() {
+        for raw in ["Salt and pepper, to taste", "a handful of basil"] {
+            let ingredient = Ingredient::parse(raw);
+            assert_eq!(ingredient.scale(Fraction::whole(3)), ingredient, "{}", raw);
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::v1::types::database::{Info, Recipe as DatabaseRecipe, Step, SubStep, Warning};
+use crate::v1::types::*;
+
+/// The JSON-LD context every document is described in.
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// A recipe described as a schema.org [`Recipe`], used by search engines
+/// to show rich results.
+///
+/// Every field it is made from is kept as it was written, apart from
+/// formattable text, which is rendered as plain text. The title, image,
+/// ingredients, structure of the method and credits can all be read back
+/// out of it.
+///
+/// [`Recipe`]: https://schema.org/Recipe
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeJsonLd {
+    #[serde(rename = "@context")]
+    context: String,
+    #[serde(rename = "@type")]
+    kind: String,
+    /// The UUID of the recipe.
+    identifier: Uuid,
+    /// The URL of the recipe on the website.
+    url: String,
+    /// The title of the recipe.
+    name: String,
+    /// The URL to the recipe image.
+    image: Url,
+    /// The date the recipe was released, in RFC 3339.
+    date_published: String,
+    /// The time to cook the recipe, as an ISO 8601 duration.
+    total_time: String,
+    /// The number of servings the recipe makes.
+    recipe_yield: String,
+    /// The ingredients of the recipe, as they were written.
+    recipe_ingredient: Vec<String>,
+    /// The steps of the method.
+    recipe_instructions: Vec<HowToSection>,
+    /// The diets the recipe is suitable for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    suitable_for_diet: Vec<String>,
+    /// Who made the recipe, from its credits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<Author>,
+}
+
+/// A step of the method, made of several substeps.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct HowToSection {
+    #[serde(rename = "@type")]
+    kind: String,
+    /// The title of the step.
+    name: String,
+    /// The substeps of the step.
+    item_list_element: Vec<HowToStep>,
+}
+
+/// A single substep of the method.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct HowToStep {
+    #[serde(rename = "@type")]
+    kind: String,
+    /// The content of the substep, as plain text.
+    text: String,
+    /// The image accompanying the substep.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<Url>,
+    /// The warnings, then the information, present with the substep.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    item_list_element: Vec<HowToTip>,
+}
+
+/// A warning or information pane of a substep.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct HowToTip {
+    #[serde(rename = "@type")]
+    kind: String,
+    /// Either `warning` or `info`, as schema.org has no type for warnings.
+    disambiguating_description: String,
+    /// The title of the pane.
+    name: String,
+    /// The content of the pane, as plain text.
+    text: String,
+}
+
+/// The creators of a recipe.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Author {
+    #[serde(rename = "@type")]
+    kind: String,
+    /// The credits of the recipe, as plain text.
+    name: String,
+}
+
+impl RecipeJsonLd {
+    /// Describes a [`database::Recipe`] as JSON-LD, where `url` is the
+    /// URL of the recipe on the website.
+    pub fn from_recipe(recipe: &DatabaseRecipe, url: String) -> Self {
+        let ingredients: Vec<String> = recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| ingredient.name.clone())
+            .collect();
+        Self {
+            context: SCHEMA_CONTEXT.to_string(),
+            kind: "Recipe".to_string(),
+            identifier: recipe.uuid,
+            url,
+            name: recipe.title.clone(),
+            image: recipe.image.clone(),
+            date_published: recipe.becomes_public.to_rfc3339(),
+            total_time: iso_duration(recipe.time_to_cook),
+            recipe_yield: recipe.servings.to_string(),
+            recipe_ingredient: recipe
+                .ingredients
+                .iter()
+                .map(|ingredient| ingredient.as_str().to_string())
+                .collect(),
+            recipe_instructions: recipe
+                .method
+                .steps()
+                .iter()
+                .map(|step| HowToSection::new(step, &ingredients))
+                .collect(),
+            suitable_for_diet: recipe
+                .diets
+                .iter()
+                .map(|&diet| restricted_diet(diet))
+                .collect(),
+            author: recipe.credits.as_ref().map(|credits| Author {
+                kind: "Person".to_string(),
+                name: plain_text(credits, &ingredients),
+            }),
+        }
+    }
+}
+
+impl HowToSection {
+    /// Describes a step, where `ingredients` are the names of the recipe's
+    /// ingredients.
+    fn new(step: &Step, ingredients: &[String]) -> Self {
+        Self {
+            kind: "HowToSection".to_string(),
+            name: step.title().to_string(),
+            item_list_element: step
+                .substeps()
+                .iter()
+                .map(|substep| HowToStep::new(substep, ingredients))
+                .collect(),
+        }
+    }
+}
+
+impl HowToStep {
+    /// Describes a substep, where `ingredients` are the names of the
+    /// recipe's ingredients.
+    fn new(substep: &SubStep, ingredients: &[String]) -> Self {
+        Self {
+            kind: "HowToStep".to_string(),
+            text: plain_text(substep.content(), ingredients),
+            image: substep.image().cloned(),
+            item_list_element: substep
+                .warnings()
+                .iter()
+                .map(|warning| HowToTip::warning(warning, ingredients))
+                .chain(
+                    substep
+                        .infos()
+                        .iter()
+                        .map(|info| HowToTip::info(info, ingredients)),
+                )
+                .collect(),
+        }
+    }
+}
+
+impl HowToTip {
+    /// Describes a warning pane.
+    fn warning(warning: &Warning, ingredients: &[String]) -> Self {
+        Self {
+            kind: "HowToTip".to_string(),
+            disambiguating_description: "warning".to_string(),
+            name: warning.title().to_string(),
+            text: plain_text(warning.content(), ingredients),
+        }
+    }
+
+    /// Describes an information pane.
+    fn info(info: &Info, ingredients: &[String]) -> Self {
+        Self {
+            kind: "HowToTip".to_string(),
+            disambiguating_description: "info".to_string(),
+            name: info.title().to_string(),
+            text: plain_text(info.content(), ingredients),
+        }
+    }
+}
+
+/// Renders formattable text as plain text, as search engines show text
+/// as it is written.
+///
+/// Text written before markup was checked may not parse, in which case it
+/// is kept as it was written.
+fn plain_text(formattable: &Formattable, ingredients: &[String]) -> String {
+    match formattable.parse() {
+        Ok(blocks) => markup::to_plain_text(&blocks, ingredients),
+        Err(_) => formattable.as_str().to_string(),
+    }
+}
+
+/// Returns the schema.org [`RestrictedDiet`] of a diet.
+///
+/// [`RestrictedDiet`]: https://schema.org/RestrictedDiet
+fn restricted_diet(diet: Diet) -> String {
+    let diet = match diet {
+        Diet::Vegetarian => "VegetarianDiet",
+        Diet::Vegan => "VeganDiet",
+        Diet::Halal => "HalalDiet",
+    };
+    format!("{}/{}", SCHEMA_CONTEXT, diet)
+}
+
+/// Formats a number of minutes as an ISO 8601 duration, such as `PT1H30M`.
+fn iso_duration(minutes: u16) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("PT{}M", minutes),
+        (hours, 0) => format!("PT{}H", hours),
+        (hours, minutes) => format!("PT{}H{}M", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Checks the JSON-LD of the recipe in `tests/golden/{name}.recipe.json`
+    /// matches `tests/golden/{name}.jsonld`.
+    ///
+    /// Run with `UPDATE_GOLDEN=1` to write the current output as the
+    /// expected output instead.
+    fn check_golden(name: &str) {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+        let recipe = std::fs::read_to_string(dir.join(format!("{}.recipe.json", name))).unwrap();
+        let recipe: DatabaseRecipe = serde_json::from_str(&recipe).unwrap();
+
+        let json_ld = RecipeJsonLd::from_recipe(
+            &recipe,
+            format!("https://example.com/recipe/{}", recipe.short),
+        );
+        let actual = serde_json::to_string_pretty(&json_ld).unwrap() + "\n";
+
+        let golden = dir.join(format!("{}.jsonld", name));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&golden, &actual).unwrap();
+        }
+        let expected = std::fs::read_to_string(&golden).unwrap();
+        assert_eq!(actual, expected, "JSON-LD of `{}` changed", name);
+
+        // Nothing is lost when the JSON-LD is read back.
+        let parsed: RecipeJsonLd = serde_json::from_str(&expected).unwrap();
+        assert_lossless(&recipe, &parsed);
+    }
+
+    /// Checks every mapped field of the recipe can be read back out of
+    /// its JSON-LD.
+    fn assert_lossless(recipe: &DatabaseRecipe, json_ld: &RecipeJsonLd) {
+        assert_eq!(json_ld.identifier, recipe.uuid);
+        assert_eq!(json_ld.name, recipe.title);
+        assert_eq!(json_ld.image, recipe.image);
+        assert_eq!(json_ld.total_time, iso_duration(recipe.time_to_cook));
+        assert_eq!(
+            json_ld.recipe_yield.parse::<u16>().unwrap(),
+            recipe.servings
+        );
+        let ingredient_names: Vec<String> = recipe
+            .ingredients
+            .iter()
+            .map(|ingredient| ingredient.name.clone())
+            .collect();
+        let text = |formattable| plain_text(formattable, &ingredient_names);
+        assert_eq!(
+            json_ld.author.as_ref().map(|author| author.name.clone()),
+            recipe.credits.as_ref().map(text)
+        );
+        let ingredients: Vec<Ingredient> = json_ld
+            .recipe_ingredient
+            .iter()
+            .map(Ingredient::parse)
+            .collect();
+        assert_eq!(ingredients, recipe.ingredients);
+
+        // The method keeps its structure, with its text rendered.
+        let steps = recipe.method.steps();
+        assert_eq!(json_ld.recipe_instructions.len(), steps.len());
+        for (section, step) in json_ld.recipe_instructions.iter().zip(steps) {
+            assert_eq!(section.name, step.title());
+            let substeps = step.substeps();
+            assert_eq!(section.item_list_element.len(), substeps.len());
+            for (how_to, substep) in section.item_list_element.iter().zip(substeps) {
+                assert_eq!(how_to.text, text(substep.content()));
+                assert_eq!(how_to.image.as_ref(), substep.image());
+                let tips: Vec<(&str, &str, String)> = substep
+                    .warnings()
+                    .iter()
+                    .map(|warning| ("warning", warning.title(), text(warning.content())))
+                    .chain(
+                        substep
+                            .infos()
+                            .iter()
+                            .map(|info| ("info", info.title(), text(info.content()))),
+                    )
+                    .collect();
+                let actual: Vec<(&str, &str, String)> = how_to
+                    .item_list_element
+                    .iter()
+                    .map(|tip| {
+                        (
+                            tip.disambiguating_description.as_str(),
+                            tip.name.as_str(),
+                            tip.text.clone(),
+                        )
+                    })
+                    .collect();
+                assert_eq!(actual, tips);
+            }
+        }
+    }
+
+    #[test]
+    fn full_recipe() {
+        check_golden("full");
+    }
+
+    #[test]
+    fn minimal_recipe() {
+        check_golden("minimal");
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(iso_duration(0), "PT0M");
+        assert_eq!(iso_duration(45), "PT45M");
+        assert_eq!(iso_duration(60), "PT1H");
+        assert_eq!(iso_duration(135), "PT2H15M");
+    }
+}
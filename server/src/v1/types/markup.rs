@@ -0,0 +1,681 @@
+//! The markup language of [`Formattable`] text.
+//!
+//! # Grammar
+//!
+//! ```text
+//! document    = block { blank-line block }
+//! block       = list | paragraph
+//! list        = list-item { newline list-item }    (all items the same kind)
+//! list-item   = ( "- " | digits ". " ) inline
+//! paragraph   = inline { newline inline }          (each newline is a line break)
+//! inline      = { text | escape | bold | italic | link | reference }
+//! bold        = "**" inline "**"
+//! italic      = "_" inline "_"
+//! link        = "[" inline "](" url ")"            (url starts with http:// or https://)
+//! reference   = "{nutrient:" nutrient-name "}"     (a name in NUTRIENTS, any case)
+//!             | "{ingredient:" digits "}"          (the 1-based index of an ingredient)
+//! escape      = "\" any character
+//! ```
+//!
+//! Bold, italic and links can be nested in each other, but links can not
+//! contain links. `]`, `)` and `}` only have a meaning when they close
+//! something, and are otherwise shown as they are. Anything else that
+//! does not match the grammar, such as an unclosed `**`, is an error.
+//!
+//! [`Formattable`]: crate::v1::types::Formattable
+
+use crate::v1::types::nutrient::*;
+use crate::v1::types::Formattable;
+use std::fmt::Write;
+
+/// A block of a document, separated from other blocks by a blank line.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Block {
+    /// A paragraph of text.
+    Paragraph { content: Vec<Inline> },
+    /// A list, where each item is a line of text.
+    List {
+        /// Whether the items are numbered rather than bulleted.
+        ordered: bool,
+        items: Vec<Vec<Inline>>,
+    },
+}
+
+/// A piece of text in a block.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Inline {
+    /// Plain text, with any escapes removed.
+    Text { text: String },
+    /// Bold text.
+    Bold { content: Vec<Inline> },
+    /// Italic text.
+    Italic { content: Vec<Inline> },
+    /// A link to a web page.
+    Link { url: String, content: Vec<Inline> },
+    /// A mention of a nutrient.
+    Nutrient { nutrient: SerdeStringNutrient },
+    /// A reference to one of the recipe's ingredients.
+    Ingredient {
+        /// The 1-based index of the ingredient.
+        index: usize,
+    },
+    /// A line break within a paragraph.
+    LineBreak,
+}
+
+/// The form formattable text can be returned in, so clients do not need
+/// to parse it themselves.
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkupFormat {
+    /// The parsed blocks of the document.
+    Ast,
+    /// The document rendered as HTML.
+    Html,
+    /// The document rendered as plain text.
+    Text,
+}
+
+impl MarkupFormat {
+    /// Renders formattable text in this format, where `ingredients` are
+    /// the names of the recipe's ingredients.
+    ///
+    /// Returns None if the text is not valid markup, which can only be the
+    /// case for text written before markup was checked.
+    pub fn render(
+        self,
+        formattable: &Formattable,
+        ingredients: &[String],
+    ) -> Option<RenderedMarkup> {
+        let blocks = formattable.parse().ok()?;
+        Some(match self {
+            MarkupFormat::Ast => RenderedMarkup::Ast(blocks),
+            MarkupFormat::Html => RenderedMarkup::Rendered(to_html(&blocks, ingredients)),
+            MarkupFormat::Text => RenderedMarkup::Rendered(to_plain_text(&blocks, ingredients)),
+        })
+    }
+}
+
+/// Formattable text in the format that was asked for.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RenderedMarkup {
+    Ast(Vec<Block>),
+    Rendered(String),
+}
+
+/// Where and why some markup could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupError {
+    /// The character the error is at, starting at 0.
+    pub position: usize,
+    /// What is wrong with the markup.
+    pub message: String,
+}
+
+impl MarkupError {
+    fn new(position: usize, message: impl ToString) -> Self {
+        Self {
+            position,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid markup at character {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+/// Parses markup into the blocks of the document.
+pub fn parse(text: &str) -> Result<Vec<Block>, MarkupError> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<Inline> = vec![];
+    let mut list: Option<(bool, Vec<Vec<Inline>>)> = None;
+
+    // Closes the block being built, if any.
+    fn finish(
+        blocks: &mut Vec<Block>,
+        paragraph: &mut Vec<Inline>,
+        list: &mut Option<(bool, Vec<Vec<Inline>>)>,
+    ) {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph {
+                content: std::mem::take(paragraph),
+            });
+        }
+        if let Some((ordered, items)) = list.take() {
+            blocks.push(Block::List { ordered, items });
+        }
+    }
+
+    let mut offset = 0;
+    for line in text.split('\n') {
+        let line_offset = offset;
+        offset += line.chars().count() + 1;
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if line.trim().is_empty() {
+            finish(&mut blocks, &mut paragraph, &mut list);
+            continue;
+        }
+
+        if let Some((ordered, marker)) = list_marker(line) {
+            let content = parse_inline(&line[marker..], line_offset + marker)?;
+            if !paragraph.is_empty() || list.as_ref().is_some_and(|(kind, _)| *kind != ordered) {
+                finish(&mut blocks, &mut paragraph, &mut list);
+            }
+            list.get_or_insert_with(|| (ordered, vec![]))
+                .1
+                .push(content);
+            continue;
+        }
+
+        // A line that is not a list item ends the list.
+        if list.is_some() {
+            finish(&mut blocks, &mut paragraph, &mut list);
+        }
+        if !paragraph.is_empty() {
+            paragraph.push(Inline::LineBreak);
+        }
+        paragraph.append(&mut parse_inline(line, line_offset)?);
+    }
+    finish(&mut blocks, &mut paragraph, &mut list);
+
+    Ok(blocks)
+}
+
+/// If the line is a list item, returns whether it is ordered and the
+/// length in bytes of its marker.
+fn list_marker(line: &str) -> Option<(bool, usize)> {
+    if line.starts_with("- ") {
+        return Some((false, 2));
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    match digits > 0 && line[digits..].starts_with(". ") {
+        true => Some((true, digits + 2)),
+        false => None,
+    }
+}
+
+/// Parses a single line of inline markup, where `offset` is the character
+/// the line starts at in the document.
+fn parse_inline(line: &str, offset: usize) -> Result<Vec<Inline>, MarkupError> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut parser = InlineParser {
+        chars: &chars,
+        position: 0,
+        offset,
+    };
+    parser.parse_until(None)
+}
+
+/// What ends the inline markup being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Closer {
+    Bold,
+    Italic,
+    LinkText,
+}
+
+/// Parses inline markup one character at a time.
+struct InlineParser<'a> {
+    chars: &'a [char],
+    /// The next character to parse.
+    position: usize,
+    /// The character the line starts at in the document.
+    offset: usize,
+}
+
+impl InlineParser<'_> {
+    /// Parses until `closer` is found, consuming it, or the end of the line
+    /// if there is no closer.
+    fn parse_until(&mut self, closer: Option<Closer>) -> Result<Vec<Inline>, MarkupError> {
+        let start = self.position;
+        let mut content = vec![];
+
+        while let Some(&c) = self.chars.get(self.position) {
+            match c {
+                '\\' => {
+                    let escaped = self.chars.get(self.position + 1).ok_or_else(|| {
+                        self.error(self.position, "Nothing to escape after `\\`.")
+                    })?;
+                    push_text(&mut content, *escaped);
+                    self.position += 2;
+                }
+                '*' if self.chars.get(self.position + 1) == Some(&'*') => {
+                    self.position += 2;
+                    if closer == Some(Closer::Bold) {
+                        return Ok(content);
+                    }
+                    let opened = self.position - 2;
+                    let inner = self.parse_until(Some(Closer::Bold))?;
+                    if inner.is_empty() {
+                        return Err(self.error(opened, "Bold text must not be empty."));
+                    }
+                    content.push(Inline::Bold { content: inner });
+                }
+                '_' => {
+                    self.position += 1;
+                    if closer == Some(Closer::Italic) {
+                        return Ok(content);
+                    }
+                    let opened = self.position - 1;
+                    let inner = self.parse_until(Some(Closer::Italic))?;
+                    if inner.is_empty() {
+                        return Err(self.error(opened, "Italic text must not be empty."));
+                    }
+                    content.push(Inline::Italic { content: inner });
+                }
+                ']' if closer == Some(Closer::LinkText) => {
+                    self.position += 1;
+                    return Ok(content);
+                }
+                '[' => {
+                    if closer == Some(Closer::LinkText) {
+                        return Err(self.error(self.position, "Links can not contain links."));
+                    }
+                    content.push(self.parse_link()?);
+                }
+                '{' => content.push(self.parse_reference()?),
+                c => {
+                    push_text(&mut content, c);
+                    self.position += 1;
+                }
+            }
+        }
+
+        match closer {
+            None => Ok(content),
+            Some(Closer::Bold) => Err(self.error(start - 2, "`**` is never closed.")),
+            Some(Closer::Italic) => Err(self.error(start - 1, "`_` is never closed.")),
+            Some(Closer::LinkText) => Err(self.error(start - 1, "`[` is never closed with `]`.")),
+        }
+    }
+
+    /// Parses a link, starting at its `[`.
+    fn parse_link(&mut self) -> Result<Inline, MarkupError> {
+        let opened = self.position;
+        self.position += 1;
+        let content = self.parse_until(Some(Closer::LinkText))?;
+        if content.is_empty() {
+            return Err(self.error(opened, "Link text must not be empty."));
+        }
+
+        if self.chars.get(self.position) != Some(&'(') {
+            return Err(self.error(self.position, "Expected `(` and a URL after the link text."));
+        }
+        let url_start = self.position + 1;
+        let url_end = self.chars[url_start..]
+            .iter()
+            .position(|&c| c == ')')
+            .map(|length| url_start + length)
+            .ok_or_else(|| self.error(self.position, "`(` is never closed with `)`."))?;
+        let url: String = self.chars[url_start..url_end].iter().collect();
+        if url.chars().any(char::is_whitespace) {
+            return Err(self.error(url_start, "URLs must not contain spaces."));
+        }
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(self.error(url_start, "URLs must start with `https://` or `http://`."));
+        }
+
+        self.position = url_end + 1;
+        Ok(Inline::Link { url, content })
+    }
+
+    /// Parses a nutrient or ingredient reference, starting at its `{`.
+    fn parse_reference(&mut self) -> Result<Inline, MarkupError> {
+        let opened = self.position;
+        let end = self.chars[opened..]
+            .iter()
+            .position(|&c| c == '}')
+            .map(|length| opened + length)
+            .ok_or_else(|| self.error(opened, "`{` is never closed with `}`."))?;
+        let reference: String = self.chars[opened + 1..end].iter().collect();
+        self.position = end + 1;
+
+        let (kind, value) = reference.split_once(':').ok_or_else(|| {
+            self.error(
+                opened,
+                "Expected `{nutrient:name}` or `{ingredient:number}`.",
+            )
+        })?;
+        match kind.trim() {
+            "nutrient" => Nutrient::from_str(value.trim())
+                .map(|nutrient| Inline::Nutrient {
+                    nutrient: nutrient.into(),
+                })
+                .ok_or_else(|| self.error(opened, format!("Unknown nutrient `{}`.", value.trim()))),
+            "ingredient" => match value.trim().parse::<usize>() {
+                Ok(index) if index > 0 => Ok(Inline::Ingredient { index }),
+                _ => Err(self.error(
+                    opened,
+                    "Ingredients are referred to by their number, starting at 1.",
+                )),
+            },
+            kind => Err(self.error(
+                opened,
+                format!(
+                    "Unknown reference `{}`. Expected `nutrient` or `ingredient`.",
+                    kind
+                ),
+            )),
+        }
+    }
+
+    /// Constructs an error at a character of the line.
+    fn error(&self, position: usize, message: impl ToString) -> MarkupError {
+        MarkupError::new(self.offset + position, message)
+    }
+}
+
+/// Adds a character to the end of the content, joining it onto the last
+/// piece of text if there is one.
+fn push_text(content: &mut Vec<Inline>, c: char) {
+    match content.last_mut() {
+        Some(Inline::Text { text }) => text.push(c),
+        _ => content.push(Inline::Text {
+            text: c.to_string(),
+        }),
+    }
+}
+
+/// Returns the ingredient a reference refers to, or a description of it if
+/// the ingredient is not known.
+fn ingredient_name(index: usize, ingredients: &[String]) -> String {
+    match index.checked_sub(1).and_then(|i| ingredients.get(i)) {
+        Some(name) => name.clone(),
+        None => format!("ingredient {}", index),
+    }
+}
+
+/// Renders a document as HTML.
+///
+/// `ingredients` are the names of the recipe's ingredients, which references
+/// to them are shown as.
+pub fn to_html(blocks: &[Block], ingredients: &[String]) -> String {
+    let mut html = String::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph { content } => {
+                html.push_str("<p>");
+                inline_html(&mut html, content, ingredients);
+                html.push_str("</p>");
+            }
+            Block::List { ordered, items } => {
+                let tag = if *ordered { "ol" } else { "ul" };
+                let _ = write!(html, "<{}>", tag);
+                for item in items {
+                    html.push_str("<li>");
+                    inline_html(&mut html, item, ingredients);
+                    html.push_str("</li>");
+                }
+                let _ = write!(html, "</{}>", tag);
+            }
+        }
+    }
+    html
+}
+
+fn inline_html(html: &mut String, content: &[Inline], ingredients: &[String]) {
+    for inline in content {
+        match inline {
+            Inline::Text { text } => html.push_str(&escape_html(text)),
+            Inline::Bold { content } => {
+                html.push_str("<strong>");
+                inline_html(html, content, ingredients);
+                html.push_str("</strong>");
+            }
+            Inline::Italic { content } => {
+                html.push_str("<em>");
+                inline_html(html, content, ingredients);
+                html.push_str("</em>");
+            }
+            Inline::Link { url, content } => {
+                let _ = write!(html, "<a href=\"{}\">", escape_html(url));
+                inline_html(html, content, ingredients);
+                html.push_str("</a>");
+            }
+            Inline::Nutrient { nutrient } => {
+                let _ = write!(
+                    html,
+                    "<span class=\"nutrient\">{}</span>",
+                    escape_html(nutrient.as_str())
+                );
+            }
+            Inline::Ingredient { index } => {
+                let _ = write!(
+                    html,
+                    "<span class=\"ingredient\" data-ingredient=\"{}\">{}</span>",
+                    index,
+                    escape_html(&ingredient_name(*index, ingredients))
+                );
+            }
+            Inline::LineBreak => html.push_str("<br>"),
+        }
+    }
+}
+
+/// Renders a document as plain text, without any formatting.
+///
+/// Links are followed by their URL in brackets. `ingredients` are the
+/// names of the recipe's ingredients, which references to them are
+/// shown as.
+pub fn to_plain_text(blocks: &[Block], ingredients: &[String]) -> String {
+    let mut blocks_text = vec![];
+    for block in blocks {
+        let mut text = String::new();
+        match block {
+            Block::Paragraph { content } => inline_text(&mut text, content, ingredients),
+            Block::List { ordered, items } => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        text.push('\n');
+                    }
+                    match ordered {
+                        true => {
+                            let _ = write!(text, "{}. ", i + 1);
+                        }
+                        false => text.push_str("- "),
+                    }
+                    inline_text(&mut text, item, ingredients);
+                }
+            }
+        }
+        blocks_text.push(text);
+    }
+    blocks_text.join("\n\n")
+}
+
+fn inline_text(text: &mut String, content: &[Inline], ingredients: &[String]) {
+    for inline in content {
+        match inline {
+            Inline::Text { text: t } => text.push_str(t),
+            Inline::Bold { content } | Inline::Italic { content } => {
+                inline_text(text, content, ingredients)
+            }
+            Inline::Link { url, content } => {
+                inline_text(text, content, ingredients);
+                let _ = write!(text, " ({})", url);
+            }
+            Inline::Nutrient { nutrient } => text.push_str(nutrient.as_str()),
+            Inline::Ingredient { index } => text.push_str(&ingredient_name(*index, ingredients)),
+            Inline::LineBreak => text.push('\n'),
+        }
+    }
+}
+
+/// Escapes the characters that have a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns every ingredient referred to in the document.
+pub fn ingredient_references(blocks: &[Block]) -> Vec<usize> {
+    fn collect(content: &[Inline], references: &mut Vec<usize>) {
+        for inline in content {
+            match inline {
+                Inline::Ingredient { index } => references.push(*index),
+                Inline::Bold { content }
+                | Inline::Italic { content }
+                | Inline::Link { content, .. } => collect(content, references),
+                _ => {}
+            }
+        }
+    }
+
+    let mut references = vec![];
+    for block in blocks {
+        match block {
+            Block::Paragraph { content } => collect(content, &mut references),
+            Block::List { items, .. } => {
+                for item in items {
+                    collect(item, &mut references);
+                }
+            }
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders() {
+        let ingredients = vec!["flour".to_string(), "egg".to_string()];
+        let blocks = parse(
+            "Mix **the** _flour_ with {ingredient:2}.\n\n- one\n- [a & b](https://example.com)",
+        )
+        .unwrap();
+        assert_eq!(
+            to_html(&blocks, &ingredients),
+            concat!(
+                r#"<p>Mix <strong>the</strong> <em>flour</em> with <span class="ingredient" data-ingredient="2">egg</span>.</p>"#,
+                r#"<ul><li>one</li><li><a href="https://example.com">a &amp; b</a></li></ul>"#,
+            )
+        );
+        assert_eq!(
+            to_plain_text(&blocks, &ingredients),
+            "Mix the flour with egg.\n\n- one\n- a & b (https://example.com)"
+        );
+        assert_eq!(ingredient_references(&blocks), vec![2]);
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert!(parse("**never closed").is_err());
+        assert!(parse("{ingredient:0}").is_err());
+        assert!(parse("{nutrient:unknown}").is_err());
+        assert!(parse("[link](javascript:alert)").is_err());
+        assert!(parse("\\**escaped\\**").is_ok());
+    }
+
+    fn text(text: &str) -> Inline {
+        Inline::Text {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_lists() {
+        let blocks = parse("Steps:\n1. mix\n2. bake\n- serve\nEnjoy").unwrap();
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph {
+                    content: vec![text("Steps:")]
+                },
+                Block::List {
+                    ordered: true,
+                    items: vec![vec![text("mix")], vec![text("bake")]],
+                },
+                Block::List {
+                    ordered: false,
+                    items: vec![vec![text("serve")]],
+                },
+                Block::Paragraph {
+                    content: vec![text("Enjoy")]
+                },
+            ]
+        );
+        assert_eq!(
+            to_html(&blocks, &[]),
+            "<p>Steps:</p><ol><li>mix</li><li>bake</li></ol><ul><li>serve</li></ul><p>Enjoy</p>"
+        );
+        // Markers need a space, so these are text.
+        assert_eq!(
+            parse("-1 egg\n2.5 cups").unwrap(),
+            vec![Block::Paragraph {
+                content: vec![text("-1 egg"), Inline::LineBreak, text("2.5 cups")]
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_nesting() {
+        let blocks = parse("**bold _and italic_** [_link_ **text**](https://a.com)").unwrap();
+        assert_eq!(
+            to_html(&blocks, &[]),
+            concat!(
+                "<p><strong>bold <em>and italic</em></strong> ",
+                r#"<a href="https://a.com"><em>link</em> <strong>text</strong></a></p>"#,
+            )
+        );
+        assert_eq!(
+            to_plain_text(&blocks, &[]),
+            "bold and italic link text (https://a.com)"
+        );
+        assert!(parse("[a [b](https://b.com)](https://a.com)").is_err());
+    }
+
+    #[test]
+    fn parses_line_breaks() {
+        let blocks = parse("one\r\ntwo\n\n\nthree").unwrap();
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph {
+                    content: vec![text("one"), Inline::LineBreak, text("two")]
+                },
+                Block::Paragraph {
+                    content: vec![text("three")]
+                },
+            ]
+        );
+        assert_eq!(to_html(&blocks, &[]), "<p>one<br>two</p><p>three</p>");
+        assert_eq!(to_plain_text(&blocks, &[]), "one\ntwo\n\nthree");
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let position = |text| parse(text).unwrap_err().position;
+        assert_eq!(position("**never closed"), 0);
+        assert_eq!(position("a _b"), 2);
+        // Positions count characters, not bytes, from the start of the
+        // document.
+        assert_eq!(position("crème\nfraîche _b"), 14);
+        assert_eq!(position("one\n- two {ingredient:0}"), 10);
+        assert_eq!(position("[link](ftp://a.com)"), 7);
+        assert_eq!(position("end \\"), 4);
+    }
+}
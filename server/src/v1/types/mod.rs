@@ -1,18 +1,36 @@
 pub mod basic_recipe;
+pub mod cookbook;
 pub mod database;
 pub mod date;
+pub mod dietary;
 pub mod formattable;
 pub mod gradient;
+pub mod ingredient;
+pub mod json_ld;
+pub mod markup;
 pub mod nutrient;
+pub mod nutrition;
+pub mod quiz;
 pub mod recipe;
+pub mod units;
 pub mod url;
 pub mod uuid;
+pub mod validation;
 
 pub use self::basic_recipe::BasicRecipe;
+pub use self::cookbook::{BasicCookbook, Cookbook};
 pub use self::date::Date;
+pub use self::dietary::*;
 pub use self::formattable::Formattable;
 pub use self::gradient::Gradient;
+pub use self::ingredient::*;
+pub use self::json_ld::RecipeJsonLd;
+pub use self::markup::{MarkupFormat, RenderedMarkup};
 pub use self::nutrient::*;
+pub use self::nutrition::*;
+pub use self::quiz::{Quiz, QuizResult, QuizSubmission};
 pub use self::recipe::Recipe;
+pub use self::units::*;
 pub use self::url::Url;
 pub use self::uuid::Uuid;
+pub use self::validation::*;
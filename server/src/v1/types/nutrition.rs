@@ -0,0 +1,248 @@
+use crate::v1::types::nutrient::*;
+use crate::v1::types::validation::*;
+use std::collections::HashMap;
+
+/// The percent of its daily value a nutrient must make up to be tagged on
+/// a recipe. 10% is the threshold for a "good source" of a nutrient.
+const TAG_PERCENT: f64 = 10.0;
+/// The most nutrients a recipe can be tagged with.
+const MAX_TAGS: usize = 3;
+
+/// The nutrients `daily-intake-percent` has no unit for that are measured
+/// in μg. Any other nutrient without a unit there is measured in g.
+const MICROGRAM_NUTRIENTS: [&str; 3] = ["Lycopene", "Lutein & Zeazanthin", "Iodine"];
+
+lazy_static::lazy_static! {
+    /// The unit each nutrient is measured in, and its daily value if it
+    /// has one, keyed by the nutrient's ID in [`NUTRIENTS`].
+    ///
+    /// These come from `daily-intake-percent`, so the two never disagree.
+    /// Nutrients without a unit there are measured in the unit they are
+    /// usually reported in.
+    pub static ref DAILY_VALUES: HashMap<u16, (&'static str, Option<f64>)> = {
+        let nutrients = daily_intake_percent::nutrients::Nutrients::default();
+        NUTRIENTS
+            .iter()
+            .map(|(id, name)| {
+                let nutrient = nutrients
+                    .get_nutrient(name)
+                    .unwrap_or_else(|| panic!("No daily value for nutrient {}", name));
+                let unit = match nutrient.unit {
+                    "?" if MICROGRAM_NUTRIENTS.contains(name) => "μg",
+                    "?" => "g",
+                    unit => unit,
+                };
+                let daily_value = Some(nutrient.daily_value).filter(|dv| *dv > 0.0);
+                (*id, (unit, daily_value))
+            })
+            .collect()
+    };
+}
+
+/// The amount of a nutrient in a single serving of a recipe.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+pub struct NutrientAmount {
+    /// The nutrient.
+    pub nutrient: Nutrient,
+    /// The amount of the nutrient, in the unit it is measured in.
+    pub amount: f64,
+}
+
+// Amounts are validated to be finite, so are never NaN.
+impl Eq for NutrientAmount {}
+
+impl NutrientAmount {
+    /// Returns the unit the nutrient is measured in.
+    pub fn unit(&self) -> &'static str {
+        DAILY_VALUES
+            .get(&u16::from(self.nutrient))
+            .map_or("", |(unit, _)| unit)
+    }
+
+    /// Returns the daily value of the nutrient, if it has one.
+    pub fn daily_value(&self) -> Option<f64> {
+        DAILY_VALUES
+            .get(&u16::from(self.nutrient))
+            .and_then(|(_, daily_value)| *daily_value)
+    }
+
+    /// Returns the percent of its daily value the amount makes up, if the
+    /// nutrient has a daily value.
+    pub fn percent_daily_value(&self) -> Option<f64> {
+        self.daily_value()
+            .map(|daily_value| self.amount / daily_value * 100.0)
+    }
+}
+
+/// The nutrients in a single serving of a recipe.
+///
+/// Only nutrients with an amount are stored. Any other nutrient in
+/// [`NUTRIENTS`] is not in the recipe, or its amount is not known.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct NutritionFacts(Vec<NutrientAmount>);
+
+impl NutritionFacts {
+    /// Constructs the nutrition facts from amounts keyed by the name of
+    /// their nutrient, such as `Saturated Fat`.
+    ///
+    /// Returns a violation for every name that is not a nutrient.
+    pub fn from_names(amounts: HashMap<String, f64>, path: &str) -> Result<Self, Vec<Violation>> {
+        let mut violations = vec![];
+        let mut facts = vec![];
+        for (name, amount) in amounts {
+            match Nutrient::from_str(&name) {
+                Some(nutrient) => facts.push(NutrientAmount { nutrient, amount }),
+                None => violations.push(Violation::new(
+                    format!("{}.{}", path, name),
+                    "Unknown nutrient.",
+                )),
+            }
+        }
+
+        // Keep the same order as NUTRIENTS, so the panel is always in the
+        // same order.
+        facts.sort_by_key(|fact| u16::from(fact.nutrient));
+        match violations.is_empty() {
+            true => Ok(Self(facts)),
+            false => Err(violations),
+        }
+    }
+
+    /// Returns if no amounts are known.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the amount of every nutrient that is known.
+    pub fn amounts(&self) -> &[NutrientAmount] {
+        &self.0
+    }
+
+    /// Returns the nutrients the recipe should be tagged with.
+    ///
+    /// These are the nutrients that make up the largest percent of their
+    /// daily value, as long as they make up at least 10% of it. If none do,
+    /// the recipe is tagged with the single largest one.
+    pub fn tags(&self) -> Vec<Nutrient> {
+        let mut percents: Vec<(Nutrient, f64)> = self
+            .0
+            .iter()
+            .filter_map(|fact| Some((fact.nutrient, fact.percent_daily_value()?)))
+            .filter(|(_, percent)| *percent > 0.0)
+            .collect();
+        percents.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let tags: Vec<Nutrient> = percents
+            .iter()
+            .filter(|(_, percent)| *percent >= TAG_PERCENT)
+            .take(MAX_TAGS)
+            .map(|(nutrient, _)| *nutrient)
+            .collect();
+        match tags.is_empty() {
+            true => percents
+                .first()
+                .map(|(nutrient, _)| *nutrient)
+                .into_iter()
+                .collect(),
+            false => tags,
+        }
+    }
+
+    /// Checks every amount is a valid, non-negative number.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        for fact in &self.0 {
+            if !fact.amount.is_finite() || fact.amount < 0.0 {
+                violations.push(Violation::new(
+                    format!("{}.{}", path, fact.nutrient),
+                    "Must be a number that is at least 0.",
+                ));
+            }
+        }
+    }
+}
+
+/// A single row of the nutrition panel shown with a recipe.
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NutritionPanelEntry {
+    /// The name of the nutrient.
+    pub nutrient: SerdeStringNutrient,
+    /// The amount in a single serving.
+    pub amount: f64,
+    /// The unit the amount is in, such as `mg`.
+    pub unit: &'static str,
+    /// The percent of its daily value the amount makes up. None if the
+    /// nutrient has no daily value.
+    pub percent_daily_value: Option<f64>,
+}
+
+impl From<&NutrientAmount> for NutritionPanelEntry {
+    fn from(fact: &NutrientAmount) -> Self {
+        Self {
+            nutrient: fact.nutrient.into(),
+            amount: fact.amount,
+            unit: fact.unit(),
+            percent_daily_value: fact.percent_daily_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts(amounts: &[(&str, f64)]) -> NutritionFacts {
+        let amounts = amounts
+            .iter()
+            .map(|(name, amount)| (name.to_string(), *amount))
+            .collect();
+        NutritionFacts::from_names(amounts, "nutrition").unwrap()
+    }
+
+    fn names(tags: Vec<Nutrient>) -> Vec<&'static str> {
+        tags.iter().map(|nutrient| nutrient.as_str()).collect()
+    }
+
+    #[test]
+    fn uses_daily_intake_percent_values() {
+        assert_eq!(DAILY_VALUES.len(), NUTRIENTS.len());
+        assert_eq!(DAILY_VALUES[&1], ("kcal", Some(2000.0)));
+        assert_eq!(DAILY_VALUES[&20], ("μg", Some(120.0)));
+        assert_eq!(DAILY_VALUES[&22], ("μg", None));
+        assert_eq!(DAILY_VALUES[&26], ("μg", None));
+        assert_eq!(DAILY_VALUES[&37], ("g", None));
+    }
+
+    #[test]
+    fn tags_nutrients_of_at_least_ten_percent() {
+        // 10% of Vitamin C is 9mg, and 10% of Iron is 1.8mg.
+        let tags = facts(&[("Vitamin C", 9.0), ("Iron", 1.7), ("Fiber", 14.0)]).tags();
+        assert_eq!(names(tags), ["Fiber", "Vitamin C"]);
+    }
+
+    #[test]
+    fn tags_at_most_three_largest_nutrients() {
+        let tags = facts(&[
+            ("Vitamin C", 45.0),
+            ("Iron", 18.0),
+            ("Fiber", 5.6),
+            ("Calcium", 650.0),
+        ])
+        .tags();
+        assert_eq!(names(tags), ["Iron", "Vitamin C", "Calcium"]);
+    }
+
+    #[test]
+    fn tags_largest_nutrient_if_none_are_ten_percent() {
+        let tags = facts(&[("Vitamin C", 4.5), ("Iron", 0.9), ("Starch", 30.0)]).tags();
+        assert_eq!(names(tags), ["Vitamin C"]);
+    }
+
+    #[test]
+    fn does_not_tag_missing_or_unknown_amounts() {
+        assert!(facts(&[]).tags().is_empty());
+        assert!(facts(&[("Vitamin C", 0.0), ("Starch", 30.0)])
+            .tags()
+            .is_empty());
+    }
+}
@@ -0,0 +1,800 @@
+use crate::v1::types::database::{Answers, Quiz as DatabaseQuiz, QuizReward};
+use crate::v1::types::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::{BTreeMap, BTreeSet};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A quiz that is sent to the client, without its answers.
+///
+/// The answers of each question are sent as options, each with an opaque
+/// ID. The IDs are signed with the secret key, so the correct options can
+/// only be found by submitting them to be graded.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Quiz {
+    /// The seed the options were shuffled with. Sending it back with the
+    /// recipe shows the options in the same order again.
+    pub seed: u64,
+    /// The questions present in the quiz.
+    pub questions: Vec<Question>,
+    /// The reward for getting all questions correct.
+    pub all_correct_reward: u16,
+    /// The recipe the quiz is from, which the option IDs are signed for.
+    #[serde(skip)]
+    recipe: Uuid,
+}
+
+/// A question of a [`Quiz`], without its answers.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Question {
+    /// The question title.
+    pub question: Formattable,
+    /// The question description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Formattable>,
+    /// An image associated with the question.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<Url>,
+    /// The type of question, along with what can be chosen to answer it.
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+    /// The reward for getting this question correct.
+    pub reward: u16,
+}
+
+/// The type of a [`Question`], along with what can be chosen to answer it.
+/// See [`database::Answers`] for how each type is answered.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QuestionKind {
+    /// One option must be chosen.
+    SingleChoice { options: Vec<AnswerOption> },
+    /// Every correct option must be chosen.
+    MultiSelect { options: Vec<AnswerOption> },
+    /// Whether the question is true must be given.
+    TrueFalse,
+    /// Every item must be given in order.
+    Ordering { items: Vec<AnswerOption> },
+    /// One image must be chosen.
+    ImageChoice { options: Vec<ImageOption> },
+    /// A number must be given.
+    Numeric { unit: Option<String> },
+}
+
+/// An option that can be chosen to answer a [`Question`].
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerOption {
+    /// The ID to submit to choose the option.
+    pub id: String,
+    /// The content of the option.
+    pub content: Formattable,
+}
+
+/// An image that can be chosen to answer a [`Question`].
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageOption {
+    /// The ID to submit to choose the image.
+    pub id: String,
+    /// The URL of the image.
+    pub image: Url,
+}
+
+impl Quiz {
+    /// Creates a new `Quiz` from the [`database::Quiz`] of `recipe`, with
+    /// the options shuffled from a random seed.
+    pub fn from_quiz(quiz: &DatabaseQuiz, recipe: Uuid) -> Self {
+        let seed = u128::from(Uuid::random()) as u64;
+        Self::with_key(quiz, recipe, &secret_key()).shuffled_with_key(seed, &secret_key())
+    }
+
+    /// Creates a new `Quiz`, signing the option IDs with `key`. The options
+    /// are not shuffled.
+    fn with_key(quiz: &DatabaseQuiz, recipe: Uuid, key: &[u8]) -> Self {
+        let questions = quiz
+            .questions()
+            .iter()
+            .enumerate()
+            .map(|(i, question)| {
+                let ids = answer_ids(key, recipe, i, question.answers());
+                let options = |answers: Vec<&Formattable>| {
+                    ids.iter()
+                        .zip(answers)
+                        .map(|(id, answer)| AnswerOption {
+                            id: id.clone(),
+                            content: answer.clone(),
+                        })
+                        .collect()
+                };
+                let kind = match question.answers() {
+                    Answers::SingleChoice {
+                        correct_answer,
+                        wrong_answers,
+                    } => QuestionKind::SingleChoice {
+                        options: options(
+                            std::iter::once(correct_answer)
+                                .chain(wrong_answers)
+                                .collect(),
+                        ),
+                    },
+                    Answers::MultiSelect {
+                        correct_answers,
+                        wrong_answers,
+                    } => QuestionKind::MultiSelect {
+                        options: options(correct_answers.iter().chain(wrong_answers).collect()),
+                    },
+                    Answers::TrueFalse { .. } => QuestionKind::TrueFalse,
+                    Answers::Ordering { items } => QuestionKind::Ordering {
+                        items: options(items.iter().collect()),
+                    },
+                    Answers::ImageChoice {
+                        correct_image,
+                        wrong_images,
+                    } => QuestionKind::ImageChoice {
+                        options: ids
+                            .iter()
+                            .zip(std::iter::once(correct_image).chain(wrong_images))
+                            .map(|(id, image)| ImageOption {
+                                id: id.clone(),
+                                image: image.clone(),
+                            })
+                            .collect(),
+                    },
+                    Answers::Numeric { unit, .. } => QuestionKind::Numeric { unit: unit.clone() },
+                };
+
+                Question {
+                    question: question.question().clone(),
+                    description: question.description().cloned(),
+                    image: question.image().cloned(),
+                    kind,
+                    reward: question.reward(),
+                }
+            })
+            .collect();
+
+        Self {
+            seed: 0,
+            questions,
+            all_correct_reward: quiz.all_correct_reward(),
+            recipe,
+        }
+    }
+
+    /// Shuffles the options of every question from `seed`.
+    ///
+    /// The same seed always gives the same order, so a quiz can be shown
+    /// again the same way when it is retried.
+    pub fn shuffled(self, seed: u64) -> Self {
+        self.shuffled_with_key(seed, &secret_key())
+    }
+
+    /// Shuffles the options, where the option IDs were signed with `key`.
+    fn shuffled_with_key(mut self, seed: u64, key: &[u8]) -> Self {
+        // Options are ordered by a signature of their ID, so the order can
+        // not be undone to find which options came first.
+        let order = |id: &str| sign(key, &format!("{}.{}", seed, id));
+        for (i, question) in self.questions.iter_mut().enumerate() {
+            match &mut question.kind {
+                QuestionKind::SingleChoice { options } | QuestionKind::MultiSelect { options } => {
+                    options.sort_by_cached_key(|option| order(&option.id));
+                }
+                QuestionKind::ImageChoice { options } => {
+                    options.sort_by_cached_key(|option| order(&option.id));
+                }
+                QuestionKind::Ordering { items } => {
+                    items.sort_by_cached_key(|item| order(&item.id));
+                    // Never give the items already in order. They are shuffled
+                    // again until they are not, so every other order is as
+                    // likely as the rest.
+                    let in_order = |items: &[AnswerOption]| {
+                        items
+                            .iter()
+                            .enumerate()
+                            .all(|(j, item)| item.id == answer_id(key, self.recipe, i, j))
+                    };
+                    let mut round = 0;
+                    while items.len() > 1 && in_order(items) {
+                        round += 1;
+                        items.sort_by_cached_key(|item| {
+                            sign(key, &format!("{}.{}.{}", seed, item.id, round))
+                        });
+                    }
+                }
+                QuestionKind::TrueFalse | QuestionKind::Numeric { .. } => {}
+            }
+        }
+        self.seed = seed;
+        self
+    }
+
+    /// Returns every formattable in the quiz, along with its path.
+    pub fn formattables(&self, path: &str) -> Vec<(String, &Formattable)> {
+        let mut formattables = vec![];
+        for (i, question) in self.questions.iter().enumerate() {
+            let path = format!("{}.questions[{}]", path, i);
+            formattables.push((format!("{}.question", path), &question.question));
+            if let Some(description) = &question.description {
+                formattables.push((format!("{}.description", path), description));
+            }
+            let (field, options) = match &question.kind {
+                QuestionKind::SingleChoice { options } | QuestionKind::MultiSelect { options } => {
+                    ("options", options)
+                }
+                QuestionKind::Ordering { items } => ("items", items),
+                _ => continue,
+            };
+            for (j, option) in options.iter().enumerate() {
+                formattables.push((
+                    format!("{}.{}[{}].content", path, field, j),
+                    &option.content,
+                ));
+            }
+        }
+        formattables
+    }
+}
+
+/// The answers to a quiz, sent to be graded.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizSubmission {
+    /// The answers to each question. Questions that are not answered are
+    /// graded as wrong.
+    pub answers: Vec<SubmittedAnswer>,
+}
+
+/// The answer to a single question of a quiz. Which field is set depends on
+/// the type of question.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmittedAnswer {
+    /// The index of the question being answered.
+    pub question: usize,
+    /// The IDs of the options chosen, or for ordering questions, the IDs
+    /// of every item in order.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Whether a true or false question is true.
+    pub value: Option<bool>,
+    /// The answer to a numeric question.
+    pub number: Option<f64>,
+}
+
+/// The grade of a quiz submission.
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizResult {
+    /// The result of each question, in the order of the quiz.
+    pub questions: Vec<QuestionResult>,
+    /// The number of questions answered correctly.
+    pub correct: usize,
+    /// The number of questions in the quiz.
+    pub total: usize,
+    /// Whether every question was answered correctly.
+    pub all_correct: bool,
+    /// The reward for the correct questions, plus the reward for getting
+    /// them all correct if they were.
+    pub reward: u32,
+}
+
+/// The result of a single question of a quiz submission.
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionResult {
+    /// Whether the question was answered correctly.
+    pub correct: bool,
+    /// The correct answer, so it can be shown once answered. Only set once
+    /// the learner has earned every reward of the quiz, so it can not be
+    /// used to earn them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<Solution>,
+    /// The reward given for the question.
+    pub reward: u16,
+}
+
+/// The correct answer to a question, in the same form it is submitted in.
+#[derive(Debug, serde::Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Solution {
+    /// The IDs of the correct options, or every item in order.
+    Options(Vec<String>),
+    /// Whether a true or false question is true.
+    Value(bool),
+    /// The answer to a numeric question, and how far from it is correct.
+    Number { answer: f64, tolerance: f64 },
+}
+
+impl QuizSubmission {
+    /// Grades the submission against the [`database::Quiz`] of `recipe`.
+    ///
+    /// Returns every answer that is not to a question in the quiz, answers
+    /// a question twice, or does not fit the type of question, such as
+    /// choosing an option the question does not have.
+    pub fn grade(&self, quiz: &DatabaseQuiz, recipe: Uuid) -> Result<QuizResult, Vec<Violation>> {
+        self.grade_with_key(quiz, recipe, &secret_key())
+    }
+
+    /// Grades the submission, where the option IDs were signed with `key`.
+    fn grade_with_key(
+        &self,
+        quiz: &DatabaseQuiz,
+        recipe: Uuid,
+        key: &[u8],
+    ) -> Result<QuizResult, Vec<Violation>> {
+        let questions = quiz.questions();
+        let mut violations = vec![];
+        let mut graded: BTreeMap<usize, bool> = BTreeMap::new();
+        for (i, answer) in self.answers.iter().enumerate() {
+            let path = format!("answers[{}]", i);
+            let question = match questions.get(answer.question) {
+                Some(question) => question,
+                None => {
+                    violations.push(Violation::new(
+                        format!("{}.question", path),
+                        format!("The quiz only has {} questions.", questions.len()),
+                    ));
+                    continue;
+                }
+            };
+            if graded.contains_key(&answer.question) {
+                violations.push(Violation::new(
+                    format!("{}.question", path),
+                    "The question has already been answered.",
+                ));
+                continue;
+            }
+
+            let ids = answer_ids(key, recipe, answer.question, question.answers());
+            if let Some(correct) =
+                grade_answer(question.answers(), &ids, answer, &path, &mut violations)
+            {
+                graded.insert(answer.question, correct);
+            }
+        }
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        let results: Vec<QuestionResult> = questions
+            .iter()
+            .enumerate()
+            .map(|(i, question)| {
+                let correct = graded.get(&i).copied().unwrap_or(false);
+                let ids = answer_ids(key, recipe, i, question.answers());
+                QuestionResult {
+                    correct,
+                    solution: Some(solution(question.answers(), ids)),
+                    reward: if correct { question.reward() } else { 0 },
+                }
+            })
+            .collect();
+
+        let correct = results.iter().filter(|result| result.correct).count();
+        let all_correct = correct == results.len();
+        let mut reward: u32 = results.iter().map(|result| result.reward as u32).sum();
+        if all_correct {
+            reward += quiz.all_correct_reward() as u32;
+        }
+        Ok(QuizResult {
+            total: results.len(),
+            questions: results,
+            correct,
+            all_correct,
+            reward,
+        })
+    }
+}
+
+impl QuizResult {
+    /// Returns every reward the submission earned, along with its amount.
+    pub fn rewards(&self) -> Vec<(QuizReward, u32)> {
+        let mut rewards: Vec<(QuizReward, u32)> = self
+            .questions
+            .iter()
+            .enumerate()
+            .filter(|(_, question)| question.reward > 0)
+            .map(|(i, question)| (QuizReward::Question(i as u32), question.reward as u32))
+            .collect();
+        let all_correct_reward =
+            self.reward - rewards.iter().map(|(_, amount)| amount).sum::<u32>();
+        if all_correct_reward > 0 {
+            rewards.push((QuizReward::AllCorrect, all_correct_reward));
+        }
+        rewards
+    }
+
+    /// Removes the solution of every question.
+    ///
+    /// The option IDs are the same every time the quiz is sent, so a
+    /// solution can be submitted again to earn the rewards it is for.
+    pub fn hide_solutions(&mut self) {
+        for question in &mut self.questions {
+            question.solution = None;
+        }
+    }
+}
+
+/// Grades the answer to a question, where `ids` are the IDs of its answers.
+///
+/// Returns whether it is correct, or `None` if the answer does not fit the
+/// type of question, adding why to `violations`.
+fn grade_answer(
+    answers: &Answers,
+    ids: &[String],
+    answer: &SubmittedAnswer,
+    path: &str,
+    violations: &mut Vec<Violation>,
+) -> Option<bool> {
+    let mut invalid = |field: &str, message: &str| {
+        violations.push(Violation::new(format!("{}.{}", path, field), message));
+        None
+    };
+
+    match answers {
+        Answers::TrueFalse { answer: expected } => match answer.value {
+            Some(value) => Some(value == *expected),
+            None => invalid(
+                "value",
+                "True or false questions are answered with `value`.",
+            ),
+        },
+        Answers::Numeric {
+            answer: expected,
+            tolerance,
+            ..
+        } => match answer.number {
+            Some(number) if number.is_finite() => Some((number - expected).abs() <= *tolerance),
+            Some(_) => invalid("number", "The answer must be a finite number."),
+            None => invalid("number", "Numeric questions are answered with `number`."),
+        },
+        _ => {
+            let chosen: BTreeSet<&str> = answer.options.iter().map(String::as_str).collect();
+            if chosen.len() != answer.options.len() {
+                return invalid("options", "An option can only be given once.");
+            }
+            if let Some(j) = answer.options.iter().position(|id| !ids.contains(id)) {
+                return invalid(
+                    &format!("options[{}]", j),
+                    "The question has no option with this ID.",
+                );
+            }
+
+            match answers {
+                Answers::SingleChoice { .. } | Answers::ImageChoice { .. } => {
+                    match answer.options.as_slice() {
+                        [chosen] => Some(*chosen == ids[0]),
+                        _ => invalid("options", "Exactly one option must be chosen."),
+                    }
+                }
+                Answers::MultiSelect {
+                    correct_answers, ..
+                } => {
+                    let correct = &ids[..correct_answers.len()];
+                    Some(
+                        chosen.len() == correct.len()
+                            && correct.iter().all(|id| chosen.contains(id.as_str())),
+                    )
+                }
+                Answers::Ordering { .. } => {
+                    if answer.options.len() != ids.len() {
+                        return invalid("options", "Every item must be given, in order.");
+                    }
+                    Some(answer.options == ids)
+                }
+                Answers::TrueFalse { .. } | Answers::Numeric { .. } => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Returns the correct answer to a question, where `ids` are the IDs of
+/// its answers.
+fn solution(answers: &Answers, mut ids: Vec<String>) -> Solution {
+    match answers {
+        Answers::SingleChoice { .. } | Answers::ImageChoice { .. } => {
+            ids.truncate(1);
+            Solution::Options(ids)
+        }
+        Answers::MultiSelect {
+            correct_answers, ..
+        } => {
+            ids.truncate(correct_answers.len());
+            Solution::Options(ids)
+        }
+        Answers::Ordering { .. } => Solution::Options(ids),
+        Answers::TrueFalse { answer } => Solution::Value(*answer),
+        Answers::Numeric {
+            answer, tolerance, ..
+        } => Solution::Number {
+            answer: *answer,
+            tolerance: *tolerance,
+        },
+    }
+}
+
+/// Returns the key option IDs are signed with.
+fn secret_key() -> Vec<u8> {
+    crate::envvar!(SECRET_KEY).unwrap().into_bytes()
+}
+
+/// Returns the IDs of every answer of a question that is chosen from
+/// options, with the correct answers first. Ordering questions have their
+/// items in order.
+fn answer_ids(key: &[u8], recipe: Uuid, question: usize, answers: &Answers) -> Vec<String> {
+    let count = match answers {
+        Answers::SingleChoice { wrong_answers, .. } => 1 + wrong_answers.len(),
+        Answers::MultiSelect {
+            correct_answers,
+            wrong_answers,
+        } => correct_answers.len() + wrong_answers.len(),
+        Answers::Ordering { items } => items.len(),
+        Answers::ImageChoice { wrong_images, .. } => 1 + wrong_images.len(),
+        Answers::TrueFalse { .. } | Answers::Numeric { .. } => 0,
+    };
+    (0..count)
+        .map(|answer| answer_id(key, recipe, question, answer))
+        .collect()
+}
+
+/// Returns the opaque ID of the answer numbered `answer` of a question.
+fn answer_id(key: &[u8], recipe: Uuid, question: usize, answer: usize) -> String {
+    hex::encode(&sign(key, &format!("{}.{}.{}", recipe, question, answer))[..8])
+}
+
+/// Returns the HMAC-SHA256 of `message` with `key`.
+fn sign(key: &[u8], message: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take a key of any size");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test key";
+
+    fn quiz() -> DatabaseQuiz {
+        serde_json::from_value(serde_json::json!({
+            "questions": [
+                {
+                    "question": "Which is a vegetable?",
+                    "correctAnswers": ["Carrot"],
+                    "wrongAnswers": ["Apple", "Banana"],
+                    "reward": 5,
+                },
+                {
+                    "question": "Which are dairy?",
+                    "answers": {
+                        "type": "multiSelect",
+                        "correctAnswers": ["Milk", "Cheese"],
+                        "wrongAnswers": ["Bread"],
+                    },
+                    "reward": 10,
+                },
+                {
+                    "question": "Carrots are orange.",
+                    "answers": { "type": "trueFalse", "answer": true },
+                    "reward": 1,
+                },
+                {
+                    "question": "Put the steps in order.",
+                    "answers": { "type": "ordering", "items": ["Wash", "Chop", "Cook"] },
+                    "reward": 3,
+                },
+                {
+                    "question": "Which is the carrot?",
+                    "answers": {
+                        "type": "imageChoice",
+                        "correctImage": "carrot.jpg",
+                        "wrongImages": ["apple.jpg"],
+                    },
+                    "reward": 2,
+                },
+                {
+                    "question": "How many cups of flour?",
+                    "answers": { "type": "numeric", "answer": 2.5, "tolerance": 0.25, "unit": "cups" },
+                    "reward": 4,
+                },
+            ],
+            "allCorrectReward": 20,
+        }))
+        .unwrap()
+    }
+
+    /// Returns the ID of the option of a question with `content`.
+    fn option(quiz: &Quiz, question: usize, content: &str) -> String {
+        let found = match &quiz.questions[question].kind {
+            QuestionKind::SingleChoice { options }
+            | QuestionKind::MultiSelect { options }
+            | QuestionKind::Ordering { items: options } => options
+                .iter()
+                .find(|option| option.content.as_str() == content)
+                .map(|option| option.id.clone()),
+            QuestionKind::ImageChoice { options } => options
+                .iter()
+                .find(|option| option.image.to_string() == content)
+                .map(|option| option.id.clone()),
+            _ => None,
+        };
+        found.unwrap()
+    }
+
+    fn answer(question: usize, options: Vec<String>) -> SubmittedAnswer {
+        SubmittedAnswer {
+            question,
+            options,
+            value: None,
+            number: None,
+        }
+    }
+
+    #[test]
+    fn hides_answers() {
+        let recipe = Uuid::random();
+        let public = Quiz::with_key(&quiz(), recipe, KEY).shuffled_with_key(7, KEY);
+        let json = serde_json::to_value(&public).unwrap();
+        assert!(!json.to_string().contains("correct"));
+        assert!(!json.to_string().contains("2.5"));
+        assert_eq!(json["questions"][0]["type"], "singleChoice");
+        assert_eq!(json["questions"][2]["type"], "trueFalse");
+        assert_eq!(json["questions"][5]["unit"], "cups");
+    }
+
+    #[test]
+    fn shuffles_from_seed() {
+        let recipe = Uuid::random();
+        let public = Quiz::with_key(&quiz(), recipe, KEY);
+        let shuffled = |seed| public.clone().shuffled_with_key(seed, KEY);
+
+        // The same seed always gives the same order, whatever the order was.
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_eq!(shuffled(7), shuffled(8).shuffled_with_key(7, KEY));
+        assert!((0..20).any(|seed| shuffled(seed) != shuffled(7)));
+
+        // Items to order are never given in order.
+        let items: Vec<String> = ["Wash", "Chop", "Cook"]
+            .iter()
+            .map(|item| option(&public, 3, item))
+            .collect();
+        for seed in 0..20 {
+            let QuestionKind::Ordering { items: shuffled } = &shuffled(seed).questions[3].kind
+            else {
+                panic!("not an ordering question");
+            };
+            let shuffled: Vec<String> = shuffled.iter().map(|item| item.id.clone()).collect();
+            assert_ne!(shuffled, items);
+        }
+    }
+
+    #[test]
+    fn shuffles_ordering_evenly() {
+        let recipe = Uuid::random();
+        let public = Quiz::with_key(&quiz(), recipe, KEY);
+        let items: Vec<String> = ["Wash", "Chop", "Cook"]
+            .iter()
+            .map(|item| option(&public, 3, item))
+            .collect();
+
+        // Three items can be given in 5 orders that are not in order, which
+        // should each come up about a fifth of the time.
+        let mut counts: BTreeMap<Vec<String>, usize> = BTreeMap::new();
+        for seed in 0..1000 {
+            let QuestionKind::Ordering { items: shuffled } =
+                &public.clone().shuffled_with_key(seed, KEY).questions[3].kind
+            else {
+                panic!("not an ordering question");
+            };
+            let shuffled = shuffled.iter().map(|item| item.id.clone()).collect();
+            *counts.entry(shuffled).or_default() += 1;
+        }
+        assert!(!counts.contains_key(&items));
+        assert_eq!(counts.len(), 5);
+        for count in counts.values() {
+            assert!((140..=260).contains(count), "uneven counts: {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn grades_every_type() {
+        let recipe = Uuid::random();
+        let public = Quiz::with_key(&quiz(), recipe, KEY);
+        let in_order =
+            |items: [&str; 3]| items.iter().map(|item| option(&public, 3, item)).collect();
+        let submission = QuizSubmission {
+            answers: vec![
+                answer(0, vec![option(&public, 0, "Carrot")]),
+                answer(
+                    1,
+                    vec![option(&public, 1, "Cheese"), option(&public, 1, "Milk")],
+                ),
+                SubmittedAnswer {
+                    value: Some(true),
+                    ..answer(2, vec![])
+                },
+                answer(3, in_order(["Wash", "Chop", "Cook"])),
+                answer(4, vec![option(&public, 4, "carrot.jpg")]),
+                SubmittedAnswer {
+                    number: Some(2.7),
+                    ..answer(5, vec![])
+                },
+            ],
+        };
+        let result = submission.grade_with_key(&quiz(), recipe, KEY).unwrap();
+        assert_eq!((result.correct, result.total), (6, 6));
+        assert!(result.all_correct);
+        assert_eq!(result.reward, 5 + 10 + 1 + 3 + 2 + 4 + 20);
+        assert_eq!(result.rewards().len(), 7);
+        assert_eq!(result.rewards()[6], (QuizReward::AllCorrect, 20));
+
+        // Some of the correct options, the wrong order, or a number too
+        // far away is wrong, as is not answering.
+        let submission = QuizSubmission {
+            answers: vec![
+                answer(1, vec![option(&public, 1, "Milk")]),
+                answer(3, in_order(["Chop", "Wash", "Cook"])),
+                SubmittedAnswer {
+                    number: Some(3.0),
+                    ..answer(5, vec![])
+                },
+            ],
+        };
+        let result = submission.grade_with_key(&quiz(), recipe, KEY).unwrap();
+        assert_eq!(result.correct, 0);
+        assert_eq!(result.reward, 0);
+        assert!(result.rewards().is_empty());
+        assert_eq!(
+            result.questions[0].solution,
+            Some(Solution::Options(vec![option(&public, 0, "Carrot")]))
+        );
+        assert_eq!(result.questions[2].solution, Some(Solution::Value(true)));
+
+        let mut hidden = result;
+        hidden.hide_solutions();
+        let json = serde_json::to_value(&hidden).unwrap();
+        assert!(!json.to_string().contains("solution"));
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        let recipe = Uuid::random();
+        let public = Quiz::with_key(&quiz(), recipe, KEY);
+        let carrot = option(&public, 0, "Carrot");
+        let apple = option(&public, 0, "Apple");
+
+        let submission = QuizSubmission {
+            answers: vec![
+                answer(0, vec![carrot.clone()]),
+                answer(0, vec![carrot.clone()]),
+                answer(1, vec![carrot.clone()]),
+                answer(2, vec![]),
+                answer(3, vec![option(&public, 3, "Wash")]),
+                answer(4, vec![]),
+                answer(6, vec![]),
+            ],
+        };
+        let violations = submission.grade_with_key(&quiz(), recipe, KEY).unwrap_err();
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "answers[1].question",
+                "answers[2].options[0]",
+                "answers[3].value",
+                "answers[4].options",
+                "answers[5].options",
+                "answers[6].question",
+            ]
+        );
+
+        let submission = QuizSubmission {
+            answers: vec![answer(0, vec![carrot, apple])],
+        };
+        assert!(submission.grade_with_key(&quiz(), recipe, KEY).is_err());
+    }
+}
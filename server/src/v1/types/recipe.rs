@@ -1,5 +1,6 @@
-use crate::v1::types::database::{Method, Quiz, Recipe as DatabaseRecipe};
+use crate::v1::types::database::{Method, Recipe as DatabaseRecipe};
 use crate::v1::types::*;
+use std::collections::BTreeMap;
 
 /// A recipe that contains less information than a standard `Recipe` or
 /// a database Recipe. This is used to reduce the amount of data that is
@@ -9,6 +10,8 @@ use crate::v1::types::*;
 pub struct Recipe {
     /// The unique identifier of the recipe.
     pub uuid: Uuid,
+    /// The revision of the recipe. Must be sent back when overwriting it.
+    pub revision: u32,
     /// The date the recipe was made public.
     pub date_added: Date,
     /// Whether the recipe is *currently* the weekly recipe.
@@ -21,20 +24,44 @@ pub struct Recipe {
     pub title: String,
     /// The nutrients found in the recipe.
     pub nutrients: Vec<SerdeStringNutrient>,
+    /// The amount of each nutrient in a single serving, and the percent of
+    /// its daily value it makes up. Only nutrients with a known amount are
+    /// included.
+    pub nutrition: Vec<NutritionPanelEntry>,
+    /// The allergens the recipe contains. None if they are not known.
+    pub allergens: Option<Vec<Allergen>>,
+    /// The diets the recipe is suitable for.
+    pub diets: Vec<Diet>,
     /// The amount of time, in minutes, to cook the recipe.
     pub time_to_cook: u16,
     /// The number of servings the recipe makes.
+    ///
+    /// If the recipe was scaled, this is the number it was scaled to.
     pub servings: u16,
+    /// The number of servings the recipe makes as it was written.
+    pub original_servings: u16,
+    /// How much the ingredients were multiplied by to make `servings`.
+    /// 1 if the recipe was not scaled.
+    pub scale: f64,
     /// The URL to the image of the recipe.
     pub image: Url,
     /// The gradient of the recipe.
     pub gradient: Gradient,
-    /// The ingredients of the recipe.
+    /// The ingredients of the recipe, as they were written.
     pub ingredients: Vec<String>,
+    /// The ingredients of the recipe, parsed into their quantity, unit
+    /// and name. In the same order as `ingredients`.
+    pub ingredient_details: Vec<Ingredient>,
     /// The recipe's method
     pub method: Method,
-    /// The recipe's quiz.
+    /// The recipe's quiz, without its answers.
     pub quiz: Quiz,
+    /// The formattable text of the method and quiz in the format that was
+    /// asked for, keyed by its path, such as `method.steps[0].substeps[1].content`.
+    ///
+    /// Text that is not valid markup is left out. Not sent unless asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markup: Option<BTreeMap<String, RenderedMarkup>>,
 }
 
 impl Recipe {
@@ -43,24 +70,181 @@ impl Recipe {
         recipe: &DatabaseRecipe,
         weekly_getter: &crate::WeeklyRecipeGetter,
     ) -> Self {
+        Self::from_recipe_with_weekly(recipe, recipe.is_weekly(weekly_getter).await)
+    }
+
+    /// Creates a new `Recipe` from a [`database::Recipe`], setting
+    /// the `is_weekly` field manually.
+    pub fn from_recipe_with_weekly(recipe: &DatabaseRecipe, is_weekly: bool) -> Self {
         Recipe {
             uuid: recipe.uuid,
+            revision: recipe.revision,
             // Return the date it became public instead of the date it
             // was added to the database
             date_added: recipe.becomes_public,
-            is_weekly: recipe.is_weekly(weekly_getter).await,
+            is_weekly,
             short: recipe.short.clone(),
             title: recipe.title.clone(),
             // Convert Nutrient to SerdeStringNutrient so when sent to the
             // client it will be serialized as a string.
             nutrients: recipe.nutrients.iter().map(|&n| n.into()).collect(),
+            nutrition: recipe
+                .nutrition
+                .amounts()
+                .iter()
+                .map(|fact| fact.into())
+                .collect(),
+            allergens: recipe.allergens.clone(),
+            diets: recipe.diets.clone(),
             time_to_cook: recipe.time_to_cook,
             servings: recipe.servings,
+            original_servings: recipe.servings,
+            scale: 1.0,
             image: recipe.image.clone(),
             gradient: recipe.gradient.clone(),
-            ingredients: recipe.ingredients.clone(),
+            ingredients: recipe
+                .ingredients
+                .iter()
+                .map(|ingredient| ingredient.as_str().to_string())
+                .collect(),
+            ingredient_details: recipe.ingredients.clone(),
             method: recipe.method.clone(),
-            quiz: recipe.quiz.clone(),
+            quiz: Quiz::from_quiz(&recipe.quiz, recipe.uuid),
+            markup: None,
         }
     }
+
+    /// Scales the ingredients of the recipe so it makes `servings` servings.
+    ///
+    /// Quantities are rounded to amounts that are easy to measure, so
+    /// the scaled ingredients are not exactly proportional.
+    pub fn scaled(mut self, servings: u16) -> Self {
+        let factor = match Fraction::new(servings as u32, self.original_servings as u32) {
+            // Scaling to nothing would lose every quantity.
+            Some(factor) if servings > 0 => factor,
+            // A recipe that makes nothing can't be scaled.
+            _ => return self,
+        };
+
+        self.ingredient_details = self
+            .ingredient_details
+            .iter()
+            .map(|ingredient| ingredient.scale(factor))
+            .collect();
+        self.ingredients = self
+            .ingredient_details
+            .iter()
+            .map(|ingredient| ingredient.as_str().to_string())
+            .collect();
+        self.servings = servings;
+        self.scale = factor.as_f64();
+        self
+    }
+
+    /// Converts the ingredients and oven temperatures of the recipe to the
+    /// units used by `system`.
+    pub fn converted(mut self, system: UnitSystem) -> Self {
+        self.ingredient_details = self
+            .ingredient_details
+            .iter()
+            .map(|ingredient| ingredient.convert(system))
+            .collect();
+        self.ingredients = self
+            .ingredient_details
+            .iter()
+            .map(|ingredient| ingredient.as_str().to_string())
+            .collect();
+        self.method = self.method.convert_temperatures(system);
+        self
+    }
+
+    /// Shuffles the options of the quiz from `seed`, instead of the random
+    /// seed it was shuffled with.
+    pub fn with_quiz_seed(mut self, seed: u64) -> Self {
+        self.quiz = self.quiz.shuffled(seed);
+        self
+    }
+
+    /// Renders the formattable text of the method and quiz in `format`.
+    ///
+    /// Should be called after the recipe is scaled and converted, so the
+    /// rendered text matches the rest of the recipe.
+    pub fn with_markup(mut self, format: MarkupFormat) -> Self {
+        let ingredients: Vec<String> = self
+            .ingredient_details
+            .iter()
+            .map(|ingredient| ingredient.name.clone())
+            .collect();
+        let mut formattables = self.method.formattables("method");
+        formattables.append(&mut self.quiz.formattables("quiz"));
+        let markup = formattables
+            .into_iter()
+            .filter_map(|(path, formattable)| {
+                Some((path, format.render(formattable, &ingredients)?))
+            })
+            .collect();
+        self.markup = Some(markup);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+    use crate::v1::utils::preview::set_test_secret_key;
+
+    /// Returns a recipe for 4 with a measured, a ranged and an unmeasured
+    /// ingredient.
+    fn recipe() -> Recipe {
+        // The options of the quiz are signed.
+        set_test_secret_key();
+        let recipe = fixture_recipe(|json| {
+            json["servings"] = 4.into();
+            json["ingredients"] =
+                serde_json::json!(["2 1/2 cups plain flour", "1-2 tbsp oil", "Salt, to taste"]);
+        });
+        Recipe::from_recipe_with_weekly(&recipe, false)
+    }
+
+    #[test]
+    fn scales_ingredients_to_servings() {
+        let recipe = recipe().scaled(6);
+        assert_eq!(recipe.servings, 6);
+        assert_eq!(recipe.scale, 1.5);
+        assert_eq!(
+            recipe.ingredients,
+            [
+                "3 3/4 cups plain flour",
+                "1 1/2-3 tbsp oil",
+                "Salt, to taste"
+            ]
+        );
+        assert_eq!(
+            recipe.ingredient_details[0].quantity.unwrap().min,
+            Fraction::new(15, 4).unwrap()
+        );
+    }
+
+    #[test]
+    fn does_not_scale_to_nothing() {
+        let recipe = recipe().scaled(0);
+        assert_eq!(recipe.servings, 4);
+        assert_eq!(recipe.scale, 1.0);
+        assert_eq!(recipe.ingredients[0], "2 1/2 cups plain flour");
+    }
+
+    #[test]
+    fn scales_to_many_servings() {
+        let recipe = recipe().scaled(u16::MAX);
+        assert_eq!(recipe.servings, u16::MAX);
+        assert_eq!(
+            recipe.ingredients,
+            [
+                "40959 cups plain flour",
+                "16384-32768 tbsp oil",
+                "Salt, to taste"
+            ]
+        );
+    }
 }
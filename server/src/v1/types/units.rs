@@ -0,0 +1,546 @@
+use crate::v1::types::ingredient::*;
+
+/// The size of a teaspoon, in millilitres.
+const TEASPOON_ML: f64 = 5.0;
+/// The size of a tablespoon, in millilitres.
+const TABLESPOON_ML: f64 = 15.0;
+/// The size of a NZ (and metric) cup, in millilitres.
+const NZ_CUP_ML: f64 = 250.0;
+/// The size of a US customary teaspoon, in millilitres.
+const US_TEASPOON_ML: f64 = 4.92892;
+/// The size of a US customary tablespoon, in millilitres.
+const US_TABLESPOON_ML: f64 = 14.7868;
+/// The size of a US customary cup, in millilitres.
+const US_CUP_ML: f64 = 236.588;
+/// The size of a US fluid ounce, in millilitres.
+const FLUID_OUNCE_ML: f64 = 29.5735;
+/// The weight of an ounce, in grams.
+const OUNCE_G: f64 = 28.3495;
+/// The weight of a pound, in grams.
+const POUND_G: f64 = 453.592;
+
+/// A set of units that a recipe can be shown in.
+///
+/// Recipes are written in NZ units, as that is where most of our audience
+/// is. Cups and spoons differ in size between countries, so converting
+/// between them changes the quantity even though the unit looks the same.
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    /// Millilitres, litres, grams and kilograms, with staples such as flour
+    /// and sugar weighed rather than measured in cups. Temperatures in °C.
+    Metric,
+    /// US customary cups and spoons, ounces and pounds, with staples
+    /// measured in cups. Temperatures in °F.
+    Us,
+    /// NZ cups and spoons, grams and kilograms. Temperatures in °C.
+    Nz,
+}
+
+/// Whether an amount is a volume or a mass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    /// A volume, in millilitres.
+    Volume,
+    /// A mass, in grams.
+    Mass,
+}
+
+/// The density of a common staple, which allows it to be converted between
+/// a volume and a mass.
+struct Density {
+    /// Part of the name of the ingredient, in lowercase.
+    name: &'static str,
+    /// Grams per millilitre.
+    grams_per_ml: f64,
+    /// Whether the staple is a liquid, which is measured by volume even in
+    /// metric recipes.
+    liquid: bool,
+}
+
+impl Density {
+    /// A staple that is weighed in metric recipes.
+    const fn solid(name: &'static str, grams_per_ml: f64) -> Self {
+        Self {
+            name,
+            grams_per_ml,
+            liquid: false,
+        }
+    }
+
+    /// A staple that is measured by volume in every recipe.
+    const fn liquid(name: &'static str, grams_per_ml: f64) -> Self {
+        Self {
+            name,
+            grams_per_ml,
+            liquid: true,
+        }
+    }
+}
+
+/// The densities of common staples.
+///
+/// Ingredients are matched by the entry their name ends with, in whole
+/// words, as the last words of a name say what it is. `rice bran oil` is an
+/// oil and `cream cheese` is a cheese. If several entries match, the longest
+/// is used, so `icing sugar` is used over `sugar`.
+const DENSITIES: [Density; 23] = [
+    Density::solid("cornflour", 0.5),
+    Density::solid("flour", 0.6),
+    Density::solid("icing sugar", 0.5),
+    Density::solid("brown sugar", 0.8),
+    Density::solid("sugar", 0.85),
+    Density::solid("butter", 0.96),
+    Density::solid("oats", 0.36),
+    Density::solid("rice", 0.8),
+    Density::solid("cocoa", 0.42),
+    Density::solid("cocoa powder", 0.42),
+    Density::solid("desiccated coconut", 0.32),
+    Density::solid("chocolate chips", 0.68),
+    Density::solid("grated cheese", 0.4),
+    Density::solid("salt", 1.2),
+    Density::solid("breadcrumbs", 0.44),
+    Density::liquid("golden syrup", 1.42),
+    Density::liquid("honey", 1.42),
+    Density::liquid("milk", 1.03),
+    Density::liquid("buttermilk", 1.03),
+    Density::liquid("cream", 1.0),
+    Density::liquid("yoghurt", 1.03),
+    Density::liquid("oil", 0.92),
+    Density::liquid("water", 1.0),
+];
+
+/// Returns the density of an ingredient, if it is a common staple.
+fn density_of(name: &str) -> Option<&'static Density> {
+    let name = name.to_lowercase();
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    DENSITIES
+        .iter()
+        .filter(|density| {
+            let entry: Vec<&str> = density.name.split(' ').collect();
+            words.ends_with(&entry)
+        })
+        .max_by_key(|density| density.name.len())
+}
+
+impl Unit {
+    /// Returns the dimension of the unit and its size in millilitres or grams,
+    /// as written in a NZ recipe.
+    ///
+    /// Returns None for units that can't be converted, such as a pinch.
+    fn size(&self) -> Option<(Dimension, f64)> {
+        match self {
+            Unit::Teaspoon => Some((Dimension::Volume, TEASPOON_ML)),
+            Unit::Tablespoon => Some((Dimension::Volume, TABLESPOON_ML)),
+            Unit::Cup => Some((Dimension::Volume, NZ_CUP_ML)),
+            Unit::Millilitre => Some((Dimension::Volume, 1.0)),
+            Unit::Litre => Some((Dimension::Volume, 1000.0)),
+            Unit::FluidOunce => Some((Dimension::Volume, FLUID_OUNCE_ML)),
+            Unit::Gram => Some((Dimension::Mass, 1.0)),
+            Unit::Kilogram => Some((Dimension::Mass, 1000.0)),
+            Unit::Ounce => Some((Dimension::Mass, OUNCE_G)),
+            Unit::Pound => Some((Dimension::Mass, POUND_G)),
+            Unit::Pinch => None,
+        }
+    }
+}
+
+impl UnitSystem {
+    /// Converts a quantity of an ingredient to the units of this system.
+    ///
+    /// Returns the new quantity and unit, or None if the quantity is already
+    /// in the right units or can't be converted.
+    pub fn convert(&self, quantity: Quantity, unit: Unit, name: &str) -> Option<(Quantity, Unit)> {
+        // Spoons are close enough in size everywhere that converting them
+        // would only make the quantity harder to measure.
+        if matches!(unit, Unit::Teaspoon | Unit::Tablespoon) {
+            return None;
+        }
+
+        let (dimension, size) = unit.size()?;
+        // The unit is chosen from the smallest amount, so both ends of a
+        // range are in the same unit.
+        let amount = quantity.min.as_f64() * size;
+        let (target, target_size) = self.target(dimension, amount, unit, density_of(name))?;
+        if target == unit && (target_size - size).abs() < f64::EPSILON {
+            return None;
+        }
+
+        let convert = |fraction: Fraction| {
+            Fraction::approximate(fraction.as_f64() * size / target_size, Some(target))
+        };
+        let quantity = Quantity {
+            min: convert(quantity.min),
+            max: quantity.max.map(convert),
+        };
+        Some((quantity, target))
+    }
+
+    /// Chooses the unit to show `amount` millilitres or grams of an
+    /// ingredient in, returning the unit and its size in this system.
+    ///
+    /// The size is in millilitres or grams of the original `dimension`, so
+    /// staples can be converted between a volume and a mass.
+    fn target(
+        &self,
+        dimension: Dimension,
+        amount: f64,
+        unit: Unit,
+        density: Option<&Density>,
+    ) -> Option<(Unit, f64)> {
+        match (self, dimension) {
+            (UnitSystem::Metric, Dimension::Volume) => match density {
+                // Dry staples are weighed.
+                Some(density) if !density.liquid => {
+                    let (unit, size) = metric_mass(amount * density.grams_per_ml);
+                    Some((unit, size / density.grams_per_ml))
+                }
+                _ => Some(metric_volume(amount)),
+            },
+            (UnitSystem::Metric | UnitSystem::Nz, Dimension::Mass) => Some(metric_mass(amount)),
+            // NZ recipes are already in NZ cups.
+            (UnitSystem::Nz, Dimension::Volume) if unit == Unit::Cup => None,
+            (UnitSystem::Nz, Dimension::Volume) => Some(nz_volume(amount)),
+            (UnitSystem::Us, Dimension::Volume) => Some(us_volume(amount)),
+            (UnitSystem::Us, Dimension::Mass) => match density {
+                // Staples are measured in cups.
+                Some(density) => {
+                    let (unit, size) = us_volume(amount / density.grams_per_ml);
+                    Some((unit, size * density.grams_per_ml))
+                }
+                None if amount < POUND_G => Some((Unit::Ounce, OUNCE_G)),
+                None => Some((Unit::Pound, POUND_G)),
+            },
+        }
+    }
+
+    /// Returns the temperature scale of the system.
+    pub fn temperature_scale(&self) -> TemperatureScale {
+        match self {
+            UnitSystem::Metric | UnitSystem::Nz => TemperatureScale::Celsius,
+            UnitSystem::Us => TemperatureScale::Fahrenheit,
+        }
+    }
+}
+
+/// Chooses between millilitres and litres.
+fn metric_volume(ml: f64) -> (Unit, f64) {
+    match ml < 1000.0 {
+        true => (Unit::Millilitre, 1.0),
+        false => (Unit::Litre, 1000.0),
+    }
+}
+
+/// Chooses between grams and kilograms.
+fn metric_mass(grams: f64) -> (Unit, f64) {
+    match grams < 1000.0 {
+        true => (Unit::Gram, 1.0),
+        false => (Unit::Kilogram, 1000.0),
+    }
+}
+
+/// Chooses between NZ spoons, cups and litres.
+fn nz_volume(ml: f64) -> (Unit, f64) {
+    if ml < TABLESPOON_ML {
+        (Unit::Teaspoon, TEASPOON_ML)
+    } else if ml < NZ_CUP_ML / 4.0 {
+        (Unit::Tablespoon, TABLESPOON_ML)
+    } else if ml < 1000.0 {
+        (Unit::Cup, NZ_CUP_ML)
+    } else {
+        (Unit::Litre, 1000.0)
+    }
+}
+
+/// Chooses between US spoons and cups.
+fn us_volume(ml: f64) -> (Unit, f64) {
+    if ml < US_TABLESPOON_ML {
+        (Unit::Teaspoon, US_TEASPOON_ML)
+    } else if ml < US_CUP_ML / 4.0 {
+        (Unit::Tablespoon, US_TABLESPOON_ML)
+    } else {
+        (Unit::Cup, US_CUP_ML)
+    }
+}
+
+/// A scale that temperatures can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureScale {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureScale {
+    /// Converts a temperature in this scale to `target`.
+    ///
+    /// Oven dials are marked in steps, so converted temperatures are rounded
+    /// to the nearest 10°C or 25°F.
+    pub fn convert(&self, degrees: f64, target: TemperatureScale) -> f64 {
+        match (self, target) {
+            (TemperatureScale::Celsius, TemperatureScale::Fahrenheit) => {
+                ((degrees * 9.0 / 5.0 + 32.0) / 25.0).round() * 25.0
+            }
+            (TemperatureScale::Fahrenheit, TemperatureScale::Celsius) => {
+                ((degrees - 32.0) * 5.0 / 9.0 / 10.0).round() * 10.0
+            }
+            _ => degrees,
+        }
+    }
+
+    /// Returns the symbol of the scale, such as `°C`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TemperatureScale::Celsius => "°C",
+            TemperatureScale::Fahrenheit => "°F",
+        }
+    }
+}
+
+/// The ways a temperature scale can be written after a number, longest
+/// first so `°C` is matched before `C`. Matched case-insensitively, except
+/// for the single letters, which must be uppercase so `5c` is not taken as
+/// a temperature.
+const TEMPERATURE_SUFFIXES: [(&str, TemperatureScale); 12] = [
+    (" degrees celsius", TemperatureScale::Celsius),
+    (" degrees fahrenheit", TemperatureScale::Fahrenheit),
+    (" degrees c", TemperatureScale::Celsius),
+    (" degrees f", TemperatureScale::Fahrenheit),
+    (" °c", TemperatureScale::Celsius),
+    (" °f", TemperatureScale::Fahrenheit),
+    ("°c", TemperatureScale::Celsius),
+    ("°f", TemperatureScale::Fahrenheit),
+    ("ºc", TemperatureScale::Celsius),
+    ("ºf", TemperatureScale::Fahrenheit),
+    ("C", TemperatureScale::Celsius),
+    ("F", TemperatureScale::Fahrenheit),
+];
+
+/// Rewrites every temperature in `text`, such as `180°C`, `350 °F` or
+/// `200 degrees Celsius`, in the temperature scale of `system`. Ranges such
+/// as `180-200°C` are one temperature, so both ends are converted.
+///
+/// Temperatures already in the right scale are left as they were written.
+pub fn convert_temperatures(text: &str, system: UnitSystem) -> String {
+    let target = system.temperature_scale();
+    let mut converted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        // Numbers in the middle of a word, like `B12`, are not temperatures.
+        let in_word = rest[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '.');
+        converted.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (number, after) = rest.split_at(digits);
+        let range = range_end(after);
+        let unit = match range {
+            Some((length, _)) => &after[length..],
+            None => after,
+        };
+        let suffix = match in_word {
+            true => None,
+            false => temperature_suffix(unit),
+        };
+
+        match suffix {
+            Some((length, scale)) if scale != target => {
+                let convert =
+                    |number: &str| scale.convert(number.parse::<f64>().unwrap_or_default(), target);
+                converted.push_str(&convert(number).to_string());
+                if let Some((range_length, end)) = range {
+                    // Keep the separator as it was written.
+                    converted.push_str(&after[..range_length - end.len()]);
+                    converted.push_str(&convert(end).to_string());
+                }
+                converted.push_str(target.symbol());
+                rest = &unit[length..];
+            }
+            _ => {
+                converted.push_str(number);
+                rest = after;
+            }
+        }
+    }
+
+    converted.push_str(rest);
+    converted
+}
+
+/// Returns the length and number of the end of a range at the start of
+/// `text`, such as `-200` or `–200`, if there is one.
+fn range_end(text: &str) -> Option<(usize, &str)> {
+    let separator = text.chars().next().filter(|c| matches!(c, '-' | '–'))?;
+    let end = &text[separator.len_utf8()..];
+    let digits = end.find(|c: char| !c.is_ascii_digit()).unwrap_or(end.len());
+    match digits {
+        0 => None,
+        digits => Some((separator.len_utf8() + digits, &end[..digits])),
+    }
+}
+
+/// Returns the length and scale of the temperature suffix at the start of
+/// `text`, if there is one.
+fn temperature_suffix(text: &str) -> Option<(usize, TemperatureScale)> {
+    TEMPERATURE_SUFFIXES.iter().find_map(|(suffix, scale)| {
+        let candidate = text.get(..suffix.len())?;
+        // A suffix must not be the start of a longer word, like `180 cups`.
+        let ends_word = text[suffix.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        match (candidate == *suffix || candidate.to_lowercase() == *suffix) && ends_word {
+            true => Some((suffix.len(), *scale)),
+            false => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the name of the staple an ingredient is matched to.
+    fn staple(name: &str) -> Option<&'static str> {
+        density_of(name).map(|density| density.name)
+    }
+
+    #[test]
+    fn matches_staples_by_whole_words() {
+        assert_eq!(staple("plain flour"), Some("flour"));
+        assert_eq!(staple("Icing Sugar"), Some("icing sugar"));
+        assert_eq!(staple("unsalted butter"), Some("butter"));
+        assert_eq!(staple("rolled oats"), Some("oats"));
+        assert_eq!(staple("self-raising flour"), Some("flour"));
+        assert_eq!(staple("milk chocolate chips"), Some("chocolate chips"));
+        assert_eq!(staple("cocoa powder"), Some("cocoa powder"));
+
+        // Names that only contain a staple are not that staple.
+        assert_eq!(staple("buttermilk"), Some("buttermilk"));
+        assert_eq!(staple("goats cheese"), None);
+        assert_eq!(staple("cream cheese"), None);
+        assert_eq!(staple("rice bran oil"), Some("oil"));
+        assert_eq!(staple("boiling water"), Some("water"));
+        assert_eq!(staple("flour tortillas"), None);
+        assert_eq!(staple("saltines"), None);
+    }
+
+    #[test]
+    fn weighs_only_dry_staples() {
+        let cup = Quantity {
+            min: Fraction::whole(1),
+            max: None,
+        };
+        let metric = |name| UnitSystem::Metric.convert(cup, Unit::Cup, name);
+        assert_eq!(metric("plain flour").unwrap().1, Unit::Gram);
+        assert_eq!(metric("buttermilk").unwrap().1, Unit::Millilitre);
+        assert_eq!(metric("boiling water").unwrap().1, Unit::Millilitre);
+        assert_eq!(metric("cream cheese").unwrap().1, Unit::Millilitre);
+    }
+
+    #[test]
+    fn converts_temperatures() {
+        let us = |text| convert_temperatures(text, UnitSystem::Us);
+        let metric = |text| convert_temperatures(text, UnitSystem::Metric);
+        assert_eq!(us("Bake at 180°C."), "Bake at 350°F.");
+        assert_eq!(us("Heat to 200 degrees Celsius"), "Heat to 400°F");
+        assert_eq!(
+            us("Preheat to 160C, then 220 °C"),
+            "Preheat to 325°F, then 425°F"
+        );
+        assert_eq!(metric("Bake at 350°F."), "Bake at 180°C.");
+        assert_eq!(metric("Heat to 425 degrees F"), "Heat to 220°C");
+
+        // Temperatures already in the right scale are left as written.
+        assert_eq!(us("Bake at 350 °F."), "Bake at 350 °F.");
+        assert_eq!(
+            convert_temperatures("Bake at 180 °C.", UnitSystem::Nz),
+            "Bake at 180 °C."
+        );
+    }
+
+    #[test]
+    fn converts_temperature_ranges() {
+        let us = |text| convert_temperatures(text, UnitSystem::Us);
+        assert_eq!(us("Bake at 180-200°C."), "Bake at 350-400°F.");
+        assert_eq!(us("Bake at 180–200 °C."), "Bake at 350–400°F.");
+        assert_eq!(
+            convert_temperatures("Bake at 350-400°F.", UnitSystem::Metric),
+            "Bake at 180-200°C."
+        );
+        // Only a number right before the scale is a range.
+        assert_eq!(
+            us("Bake 20-25 minutes at 180°C."),
+            "Bake 20-25 minutes at 350°F."
+        );
+    }
+
+    #[test]
+    fn leaves_other_numbers() {
+        let us = |text| convert_temperatures(text, UnitSystem::Us);
+        assert_eq!(us("Gas mark 4"), "Gas mark 4");
+        assert_eq!(us("Rich in vitamin B12"), "Rich in vitamin B12");
+        assert_eq!(us("Add 5c of water"), "Add 5c of water");
+        assert_eq!(us("Add 180 cups"), "Add 180 cups");
+        assert_eq!(us("Use 2.5C"), "Use 2.5C");
+    }
+
+    /// Converts `quantity` of `unit` of water to `system`, returning the
+    /// formatted quantity and unit.
+    fn water(system: UnitSystem, quantity: Fraction, unit: Unit) -> Option<(String, Unit)> {
+        let quantity = Quantity {
+            min: quantity,
+            max: None,
+        };
+        system
+            .convert(quantity, unit, "water")
+            .map(|(quantity, unit)| (quantity.to_string(), unit))
+    }
+
+    #[test]
+    fn converts_cups_between_systems() {
+        let cup = Fraction::whole(1);
+        assert_eq!(water(UnitSystem::Nz, cup, Unit::Cup), None);
+        assert_eq!(
+            water(UnitSystem::Metric, cup, Unit::Cup),
+            Some(("250".to_string(), Unit::Millilitre))
+        );
+        assert_eq!(
+            water(UnitSystem::Us, cup, Unit::Cup),
+            Some(("1".to_string(), Unit::Cup))
+        );
+        assert_eq!(
+            water(UnitSystem::Us, Fraction::whole(4), Unit::Cup),
+            Some(("4 1/4".to_string(), Unit::Cup))
+        );
+        assert_eq!(
+            water(UnitSystem::Nz, Fraction::whole(500), Unit::Millilitre),
+            Some(("2".to_string(), Unit::Cup))
+        );
+    }
+
+    #[test]
+    fn converts_spoons_only_from_other_units() {
+        let two = Fraction::whole(2);
+        for system in [UnitSystem::Metric, UnitSystem::Us, UnitSystem::Nz] {
+            assert_eq!(water(system, two, Unit::Tablespoon), None);
+            assert_eq!(water(system, two, Unit::Teaspoon), None);
+        }
+        assert_eq!(
+            water(UnitSystem::Nz, Fraction::whole(30), Unit::Millilitre),
+            Some(("2".to_string(), Unit::Tablespoon))
+        );
+        assert_eq!(
+            water(UnitSystem::Us, Fraction::whole(30), Unit::Millilitre),
+            Some(("2".to_string(), Unit::Tablespoon))
+        );
+    }
+}
@@ -1,6 +1,11 @@
-/// A URL to a resource, generally an image hosted in S3. URLs should be
-/// 400 characters or less.
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+use crate::v1::types::validation::*;
+
+/// The maximum length of a URL, in characters.
+const MAX_URL_CHARS: usize = 400;
+
+/// A URL to a resource, generally an image uploaded through
+/// `POST /upload/image`. URLs should be 400 characters or less.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq, Default)]
 pub struct Url(String);
 
 impl Url {
@@ -8,6 +13,11 @@ impl Url {
     pub fn new(url: impl ToString) -> Self {
         Self(url.to_string())
     }
+
+    /// Checks the URL is within the length limit.
+    pub fn validate(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_max_chars(violations, path, &self.0, MAX_URL_CHARS);
+    }
 }
 
 impl std::fmt::Display for Url {
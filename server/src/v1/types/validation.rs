@@ -0,0 +1,37 @@
+/// A single problem found while validating a request, such as a field
+/// being too long.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The JSON path to the invalid field, such as
+    /// `method.steps[2].substeps[0].content`.
+    pub path: String,
+    /// What is wrong with the field.
+    pub message: String,
+}
+
+impl Violation {
+    /// Constructs a new Violation.
+    pub fn new(path: impl ToString, message: impl ToString) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+/// Adds a violation to `violations` if `value` is longer than `max` characters.
+pub fn check_max_chars(violations: &mut Vec<Violation>, path: &str, value: &str, max: usize) {
+    let len = value.chars().count();
+    if len > max {
+        violations.push(Violation::new(
+            path,
+            format!("Must be at most {} characters, but is {}.", max, len),
+        ));
+    }
+}
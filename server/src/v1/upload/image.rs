@@ -0,0 +1,280 @@
+use crate::id_error;
+use crate::v1::{types::*, utils::*};
+use actix_api_macros::*;
+use actix_multipart::Multipart;
+use actix_web::{post, web, HttpRequest, Responder};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use tracing::trace;
+
+#[derive(ActixApiEnum)]
+enum UploadResponse {
+    /// If the upload was successful, returns the URLs of the image.
+    #[success(message = "Successfully uploaded the image", json)]
+    Success(UploadedImage),
+    /// Returns if the user provided an invalid authorization token.
+    #[failure(
+        message = "Invalid authorization. Either there is no Authorization header or the bearer token is invalid."
+    )]
+    #[status_code(401)]
+    InvalidAuth,
+    /// Returns if a `cookLog` photo is uploaded without the learner it is for.
+    #[failure(message = "The `learner` query parameter is required for `cookLog` uploads.")]
+    #[status_code(400)]
+    NoLearner,
+    #[failure(message = "The specified UUID was not found.", json)]
+    #[status_code(404)]
+    NotFound(Uuid),
+    /// Returns if the body is not a multipart form with a `file` field.
+    #[failure(message = "Expected a multipart form with the image in a `file` field.")]
+    #[status_code(400)]
+    NoFile,
+    /// Returns if the image is not a JPEG, PNG or WebP. Contains the
+    /// content type of the upload.
+    #[failure(
+        message = "Unsupported content type. Expected `image/jpeg`, `image/png` or `image/webp`.",
+        json
+    )]
+    #[status_code(415)]
+    UnsupportedType(String),
+    /// Returns if the image is over [`MAX_IMAGE_BYTES`].
+    #[failure(message = "The image is too large. Images must be 10 MiB or less.")]
+    #[status_code(413)]
+    TooLarge,
+    /// Returns if the image could not be read. Contains why.
+    #[failure(message = "The image could not be read.", json)]
+    #[status_code(400)]
+    InvalidImage(String),
+    /// Returns if the server has nowhere to store uploads, as the
+    /// `BLOB_STORE` envvar is not set up.
+    #[failure(message = "Uploads are disabled on this server.")]
+    #[status_code(503)]
+    UploadsDisabled,
+    /// In the event an issue in the server occured, returns this error.
+    /// Contains a UUID that can be used to identify the issue.
+    #[failure(message = "Internal server error. Error UUID: `{}`")]
+    #[status_code(500)]
+    InternalError(Uuid),
+}
+
+impl From<ImageError> for UploadResponse {
+    fn from(err: ImageError) -> Self {
+        match err {
+            ImageError::UnsupportedType(content_type) => {
+                UploadResponse::UnsupportedType(content_type)
+            }
+            ImageError::TooLarge => UploadResponse::TooLarge,
+            ImageError::Invalid(reason) => UploadResponse::InvalidImage(reason),
+        }
+    }
+}
+
+/// What an image is uploaded for, which decides the sizes it is resized to.
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImageKind {
+    /// The main image of a recipe, shown large on the recipe page and
+    /// small in lists of recipes.
+    Recipe,
+    /// The image of a substep of a method.
+    SubStep,
+    /// The image of a quiz question.
+    Question,
+    /// A learner's photo of a recipe they cooked, for their cook log.
+    CookLog,
+}
+
+impl ImageKind {
+    /// Returns the widths the image is resized to, in pixels.
+    fn widths(self) -> &'static [u32] {
+        match self {
+            ImageKind::Recipe => &[1600, 800, 400],
+            ImageKind::SubStep => &[1200, 600],
+            ImageKind::Question => &[800, 400],
+            ImageKind::CookLog => &[1200, 600],
+        }
+    }
+}
+
+/// The query parameters of an image upload.
+#[derive(Debug, serde::Deserialize)]
+pub struct UploadQuery {
+    /// What the image is for.
+    kind: ImageKind,
+    /// The learner a `cookLog` photo is for, whose account's token must be
+    /// used to upload it.
+    learner: Option<Uuid>,
+}
+
+/// The URLs of an uploaded image.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedImage {
+    /// The URL to store on the recipe, substep or question, which is the
+    /// largest JPEG variant.
+    url: Url,
+    /// Every size and format the image is available in, largest first.
+    variants: Vec<UploadedVariant>,
+}
+
+/// A resized copy of an uploaded image.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedVariant {
+    /// Where the variant is served from.
+    url: Url,
+    /// The width of the variant, in pixels.
+    width: u32,
+    /// The height of the variant, in pixels.
+    height: u32,
+    /// The format the variant is encoded in.
+    format: VariantFormat,
+}
+
+/// Uploads an image, returning the URLs of its resized variants.
+///
+/// Only staff can upload images, except for `cookLog` photos, which can
+/// also be uploaded for a learner with their account's token.
+///
+/// The image is sent as the `file` field of a multipart form, and must be a
+/// JPEG, PNG or WebP of at most [`MAX_IMAGE_BYTES`]. It is resized for
+/// `kind`, and every size is stored as both JPEG and WebP under
+/// `images/{uuid}/{width}.{extension}`. The original is not kept.
+///
+/// Every upload is recorded, so images that are never used can be removed
+/// with `--purge-uploads`.
+#[post("/upload/image")]
+pub async fn upload(
+    req: HttpRequest,
+    client: web::Data<mongodb::Client>,
+    blob_store: web::Data<Option<Arc<dyn BlobStore>>>,
+    query: web::Query<UploadQuery>,
+    mut payload: Multipart,
+) -> impl Responder {
+    trace!("Attempting to upload image.");
+    // Important endpoint. Check for authorization before allowing
+    // access to upload files. Parents can only upload photos for their
+    // learners' cook logs.
+    match (query.kind, query.learner) {
+        (ImageKind::CookLog, Some(learner)) => match learner_account(&client, learner).await {
+            Ok(Some(account)) if check_account_auth(&req, &account).is_ok() => {}
+            Ok(Some(_)) => {
+                trace!("Invalid authorization attempt.");
+                return UploadResponse::InvalidAuth;
+            }
+            Ok(None) => return UploadResponse::NotFound(learner),
+            Err(err) => {
+                return UploadResponse::InternalError(id_error!(
+                    "Error getting learner's account from database: {}",
+                    err
+                ));
+            }
+        },
+        (ImageKind::CookLog, None) => return UploadResponse::NoLearner,
+        _ => {
+            if check_user_auth(req).is_err() {
+                trace!("Invalid authorization attempt.");
+                return UploadResponse::InvalidAuth;
+            }
+        }
+    }
+    let blob_store = match blob_store.get_ref() {
+        Some(blob_store) => blob_store,
+        None => return UploadResponse::UploadsDisabled,
+    };
+
+    // Find the file in the form, checking its type before reading it.
+    let mut file = loop {
+        match payload.next().await {
+            Some(Ok(field)) if field.name() == Some("file") => break field,
+            Some(Ok(_)) => continue,
+            Some(Err(_)) | None => return UploadResponse::NoFile,
+        }
+    };
+    let content_type = file
+        .content_type()
+        .map(|mime| mime.essence_str().to_string())
+        .unwrap_or_default();
+    let format = match upload_format(&content_type) {
+        Ok(format) => format,
+        Err(err) => return err.into(),
+    };
+
+    // Read the file, stopping as soon as it is too large.
+    let mut data = Vec::new();
+    while let Some(chunk) = file.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(_) => return UploadResponse::NoFile,
+        };
+        if data.len() + chunk.len() > MAX_IMAGE_BYTES {
+            return UploadResponse::TooLarge;
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    // Resizing is slow, so is done off the async threads.
+    let widths = query.kind.widths();
+    let variants = match web::block(move || resize_variants(&data, format, widths)).await {
+        Ok(Ok(variants)) => variants,
+        Ok(Err(err)) => return err.into(),
+        Err(err) => {
+            return UploadResponse::InternalError(id_error!(
+                "Error resizing uploaded image: {}",
+                err
+            ));
+        }
+    };
+
+    // The upload is recorded before anything is stored, so nothing stored
+    // is ever missed by the purge.
+    let image_uuid = Uuid::random();
+    let keys: Vec<String> = variants
+        .iter()
+        .map(|variant| {
+            format!(
+                "images/{}/{}.{}",
+                image_uuid,
+                variant.width,
+                variant.format.extension()
+            )
+        })
+        .collect();
+    if let Err(err) = client
+        .get_collection::<database::Upload>(Collections::Uploads)
+        .insert_one(database::Upload::new(image_uuid, keys.clone()), None)
+        .await
+    {
+        return UploadResponse::InternalError(id_error!("Error recording uploaded image: {}", err));
+    }
+
+    let mut uploaded = Vec::with_capacity(variants.len());
+    for (variant, key) in variants.into_iter().zip(keys) {
+        let url = match blob_store
+            .put(&key, variant.format.content_type(), variant.data)
+            .await
+        {
+            Ok(url) => url,
+            Err(err) => {
+                return UploadResponse::InternalError(id_error!(
+                    "Error storing uploaded image: {}",
+                    err
+                ));
+            }
+        };
+        uploaded.push(UploadedVariant {
+            url,
+            width: variant.width,
+            height: variant.height,
+            format: variant.format,
+        });
+    }
+
+    // Variants are largest first, with the JPEG of each size first.
+    let url = uploaded[0].url.clone();
+    trace!("Uploaded image {}.", image_uuid);
+    UploadResponse::Success(UploadedImage {
+        url,
+        variants: uploaded,
+    })
+}
@@ -0,0 +1,7 @@
+use actix_web::Scope;
+
+pub mod image;
+
+pub fn init(scope: Scope) -> Scope {
+    scope.service(image::upload)
+}
@@ -0,0 +1,103 @@
+use crate::v1::types::database::Account;
+use crate::v1::types::{Date, Uuid};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How long an account token works for after it is issued, in milliseconds.
+/// 90 days.
+const TOKEN_LIFETIME_MS: u64 = 1000 * 60 * 60 * 24 * 90;
+
+/// A token that lets a parent act for their [`Account`] and its learners.
+///
+/// Tokens are signed with the server's secret key, so can be checked
+/// without storing them. A token is written as
+/// `{account}.{version}.{expires}.{signature}`, where the signature covers
+/// the account's UUID, the account's token version and the expiry date.
+///
+/// Tokens stop working once they expire, or once the account's token
+/// version is incremented, which revokes every token issued before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountToken {
+    /// The account the token grants access to.
+    pub account: Uuid,
+    /// The token version of the account when the token was issued.
+    pub version: u32,
+    /// The date the token stops working.
+    pub expires: Date,
+}
+
+impl AccountToken {
+    /// Constructs a new token for an account, which expires after 90 days.
+    pub fn new(account: &Account) -> Self {
+        Self {
+            account: account.uuid,
+            version: account.token_version,
+            expires: Date::new(Date::now().ms() + TOKEN_LIFETIME_MS),
+        }
+    }
+
+    /// Signs the token, returning the string to give to the parent.
+    pub fn sign(&self) -> Result<String, String> {
+        let mac = self.mac()?.finalize().into_bytes();
+        Ok(format!(
+            "{}.{}.{}.{}",
+            self.account,
+            self.version,
+            self.expires.ms(),
+            hex::encode(mac)
+        ))
+    }
+
+    /// Checks that `token` is a valid, unexpired token, returning it.
+    ///
+    /// Whether it has been revoked can only be checked against the
+    /// account, with [`AccountToken::is_for`].
+    pub fn verify(token: &str) -> Option<Self> {
+        let mut parts = token.splitn(4, '.');
+        let account = Uuid::from_str(parts.next()?).ok()?;
+        let version = parts.next()?.parse::<u32>().ok()?;
+        let expires = Date::new(parts.next()?.parse::<u64>().ok()?);
+        let signature = hex::decode(parts.next()?).ok()?;
+        if expires < Date::now() {
+            return None;
+        }
+
+        let token = Self {
+            account,
+            version,
+            expires,
+        };
+        token
+            .mac()
+            .ok()?
+            .verify_slice(&signature)
+            .ok()
+            .map(|_| token)
+    }
+
+    /// Returns whether the token is for `account`, and has not been revoked.
+    pub fn is_for(&self, account: &Account) -> bool {
+        self.account == account.uuid && self.version == account.token_version
+    }
+
+    /// Creates the MAC of the token's contents using the secret key.
+    fn mac(&self) -> Result<HmacSha256, String> {
+        let key = crate::envvar!(SECRET_KEY)?;
+        let mut mac = HmacSha256::new_from_slice(key.as_bytes())
+            .map_err(|e| format!("Invalid secret key: {}", e))?;
+        // Prefixed so the signature can never be mistaken for one made for
+        // something else with the same key.
+        mac.update(
+            format!(
+                "account.{}.{}.{}",
+                self.account,
+                self.version,
+                self.expires.ms()
+            )
+            .as_bytes(),
+        );
+        Ok(mac)
+    }
+}
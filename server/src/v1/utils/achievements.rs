@@ -0,0 +1,174 @@
+use crate::v1::types::database::{
+    Achievement, Badge, Completion, CookLogEntry, LearnerEvent, LearnerHistory, QuizAttempt, Recipe,
+};
+use crate::v1::types::{Date, Uuid};
+use crate::v1::utils::collection::*;
+use crate::v1::utils::visibility::only_public;
+use mongodb::bson::{doc, Document};
+use mongodb::options::FindOptions;
+use mongodb::Client;
+use std::collections::HashMap;
+
+/// Returns everything a learner has done that achievements are unlocked by.
+pub async fn learner_history(
+    client: &Client,
+    learner: Uuid,
+) -> Result<LearnerHistory, mongodb::error::Error> {
+    let mut cursor = client
+        .get_collection::<QuizAttempt>(Collections::QuizAttempts)
+        .find(doc! {"learner": learner}, None)
+        .await?;
+    let mut attempts = vec![];
+    while cursor.advance().await? {
+        attempts.push(cursor.deserialize_current()?);
+    }
+
+    let mut cursor = client
+        .get_collection::<CookLogEntry>(Collections::CookLog)
+        .find(doc! {"learner": learner}, None)
+        .await?;
+    let mut cooked = vec![];
+    while cursor.advance().await? {
+        cooked.push(cursor.deserialize_current()?);
+    }
+
+    // Rules can filter by the nutrients of the recipes that were completed.
+    let recipes: Vec<Uuid> = attempts
+        .iter()
+        .map(|attempt| attempt.recipe)
+        .chain(cooked.iter().map(|entry| entry.recipe))
+        .collect();
+    let mut cursor = client
+        .get_collection::<Recipe>(Collections::Recipes)
+        .find(doc! {"_id": {"$in": recipes}}, None)
+        .await?;
+    let mut details = HashMap::new();
+    while cursor.advance().await? {
+        let recipe: Recipe = cursor.deserialize_current()?;
+        details.insert(
+            u128::from(recipe.uuid),
+            (recipe.nutrients.clone(), recipe.weekly_timestamp),
+        );
+    }
+    let nutrients_of = |recipe: Uuid| {
+        details
+            .get(&u128::from(recipe))
+            .map(|(nutrients, _)| nutrients.clone())
+            .unwrap_or_default()
+    };
+    // Attempts and cooks recorded before timestamps were stored only say
+    // whether they were weekly, so `weekly_of` falls back to the recipe's
+    // current `weekly_timestamp` for those.
+    let weekly_of = |recipe: Uuid, weekly: bool, timestamp: Option<Date>| match weekly {
+        true => timestamp.or_else(|| details.get(&u128::from(recipe))?.1),
+        false => None,
+    };
+
+    // Only attempts that passed complete the recipe, so an empty quiz can not
+    // be submitted to unlock achievements.
+    let attempted = attempts
+        .into_iter()
+        .filter(QuizAttempt::passed)
+        .map(|attempt| Completion {
+            recipe: attempt.recipe,
+            nutrients: nutrients_of(attempt.recipe),
+            date: attempt.date,
+            weekly: weekly_of(attempt.recipe, attempt.weekly, attempt.weekly_timestamp),
+            perfect: attempt.all_correct,
+        });
+    let cooked = cooked.into_iter().map(|entry| Completion {
+        recipe: entry.recipe,
+        nutrients: nutrients_of(entry.recipe),
+        date: entry.date,
+        weekly: weekly_of(entry.recipe, entry.weekly, entry.weekly_timestamp),
+        perfect: false,
+    });
+    let completions = attempted.chain(cooked).collect();
+
+    Ok(LearnerHistory {
+        completions,
+        weekly_timestamps: weekly_timestamps(client).await?,
+    })
+}
+
+/// Returns the date every weekly recipe so far went weekly, in order.
+async fn weekly_timestamps(client: &Client) -> Result<Vec<Date>, mongodb::error::Error> {
+    let options = FindOptions::builder()
+        .projection(doc! {"weeklyTimestamp": 1})
+        .sort(doc! {"weeklyTimestamp": 1})
+        .build();
+    // Not implemented for u64 but *is* implemented for i64, hence the
+    // conversion here.
+    let mut cursor = client
+        .get_collection::<Document>(Collections::Recipes)
+        .find(
+            only_public(doc! {"weeklyTimestamp": {"$lt": Date::now().ms() as i64}}),
+            options,
+        )
+        .await?;
+    let mut timestamps = vec![];
+    while cursor.advance().await? {
+        let recipe = cursor.current();
+        if let Ok(timestamp) = recipe.get_i64("weeklyTimestamp") {
+            timestamps.push(Date::new(timestamp as u64));
+        }
+    }
+    Ok(timestamps)
+}
+
+/// Checks every achievement `event` can make progress towards, unlocking
+/// the ones the learner has now met. Returns the achievements that were
+/// unlocked.
+///
+/// Achievements are only checked after events that affect them, so a new
+/// achievement a learner already meets is unlocked by their next such event.
+pub async fn unlock_achievements(
+    client: &Client,
+    event: LearnerEvent<'_>,
+) -> Result<Vec<Achievement>, mongodb::error::Error> {
+    let learner = event.learner();
+
+    let mut cursor = client
+        .get_collection::<Badge>(Collections::Badges)
+        .find(doc! {"learner": learner}, None)
+        .await?;
+    let mut unlocked = vec![];
+    while cursor.advance().await? {
+        unlocked.push(cursor.deserialize_current()?.achievement);
+    }
+
+    let mut cursor = client
+        .get_collection::<Achievement>(Collections::Achievements)
+        .find(doc! {"_id": {"$nin": unlocked}}, None)
+        .await?;
+    let mut achievements = vec![];
+    while cursor.advance().await? {
+        let achievement: Achievement = cursor.deserialize_current()?;
+        if achievement.rule.evaluated_on(&event) {
+            achievements.push(achievement);
+        }
+    }
+    if achievements.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let history = learner_history(client, learner).await?;
+    let badges = client.get_collection::<Badge>(Collections::Badges);
+    let mut newly_unlocked = vec![];
+    for achievement in achievements {
+        if !achievement.rule.is_met(&history) {
+            continue;
+        }
+        // The unique index on badges stops an achievement being unlocked
+        // twice if two events are checked at once.
+        match badges
+            .insert_one(Badge::new(learner, achievement.uuid), None)
+            .await
+        {
+            Ok(_) => newly_unlocked.push(achievement),
+            Err(e) if is_duplicate_key_error(&e) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(newly_unlocked)
+}
@@ -1,3 +1,6 @@
+use crate::v1::types::database::Account;
+use crate::v1::types::Uuid;
+use crate::v1::utils::AccountToken;
 use actix_web::HttpRequest;
 
 /// Any issues with checking the user is authenticated to perform an action.
@@ -17,6 +20,35 @@ pub enum AuthError {
 
 /// Checks if a user is authenticated to perform an action.
 pub fn check_user_auth(req: HttpRequest) -> Result<(), AuthError> {
+    let key = bearer_token(&req)?;
+
+    // Check the auth key matches the secret key.
+    if key != crate::envvar!(SECRET_KEY).unwrap() {
+        return Err(AuthError::InvalidKey);
+    }
+
+    Ok(())
+}
+
+/// Checks if a user is authenticated to act for an [`Account`] and its
+/// learners.
+///
+/// Either an unrevoked [`AccountToken`] for the account or the staff key is
+/// accepted, so staff can help parents with their accounts.
+pub fn check_account_auth(req: &HttpRequest, account: &Account) -> Result<(), AuthError> {
+    let key = bearer_token(req)?;
+    if key == crate::envvar!(SECRET_KEY).unwrap() {
+        return Ok(());
+    }
+
+    match AccountToken::verify(key) {
+        Some(token) if token.is_for(account) => Ok(()),
+        _ => Err(AuthError::InvalidKey),
+    }
+}
+
+/// Returns the bearer token in the authorization header of the request.
+fn bearer_token(req: &HttpRequest) -> Result<&str, AuthError> {
     // Get the auth header from the request. Return a 401 if not present.
     let auth_header = match req.headers().get("Authorization") {
         Some(header) => header,
@@ -35,15 +67,18 @@ pub fn check_user_auth(req: HttpRequest) -> Result<(), AuthError> {
     }
 
     // Convert to key.
-    let key = match auth_content.split_once("Bearer") {
-        Some(("", key)) => key.trim(),
-        _ => return Err(AuthError::AuthKeySplitError),
-    };
-
-    // Check the auth key matches the secret key.
-    if key != crate::envvar!(SECRET_KEY).unwrap() {
-        return Err(AuthError::InvalidKey);
+    match auth_content.split_once("Bearer") {
+        Some(("", key)) => Ok(key.trim()),
+        _ => Err(AuthError::AuthKeySplitError),
     }
+}
 
-    Ok(())
+/// Returns the staff member making the request, if they identified themselves.
+///
+/// As every staff member shares the same key, this is only used to record
+/// who made a change and must never be used for authorization. The UUID is
+/// read from the `X-Author` header.
+pub fn request_author(req: &HttpRequest) -> Option<Uuid> {
+    let header = req.headers().get("X-Author")?.to_str().ok()?;
+    Uuid::from_str(header.trim()).ok()
 }
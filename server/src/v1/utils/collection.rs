@@ -8,6 +8,17 @@ const DATABASE_NAME: &str = "recipe_db";
 /// the name of a collection.
 pub enum Collections {
     Recipes,
+    Revisions,
+    Cookbooks,
+    Accounts,
+    Points,
+    QuizAttempts,
+    Achievements,
+    Badges,
+    Favourites,
+    CookLog,
+    FamilyCooks,
+    Uploads,
 }
 
 impl Collections {
@@ -15,6 +26,17 @@ impl Collections {
     pub fn name(&self) -> &'static str {
         match self {
             Collections::Recipes => "recipes",
+            Collections::Revisions => "revisions",
+            Collections::Cookbooks => "cookbooks",
+            Collections::Accounts => "accounts",
+            Collections::Points => "points",
+            Collections::QuizAttempts => "quizAttempts",
+            Collections::Achievements => "achievements",
+            Collections::Badges => "badges",
+            Collections::Favourites => "favourites",
+            Collections::CookLog => "cookLog",
+            Collections::FamilyCooks => "familyCooks",
+            Collections::Uploads => "uploads",
         }
     }
 }
@@ -30,3 +52,18 @@ impl GetCollection for mongodb::Client {
         self.database(DATABASE_NAME).collection(collection.name())
     }
 }
+
+/// The error code MongoDB returns when a write violates a unique index.
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+/// Returns whether the error was caused by a write violating a unique index,
+/// such as inserting a document with an `_id` that already exists.
+pub fn is_duplicate_key_error(err: &mongodb::error::Error) -> bool {
+    use mongodb::error::{ErrorKind, WriteFailure};
+
+    matches!(
+        err.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(write_error))
+            if write_error.code == DUPLICATE_KEY_ERROR_CODE
+    )
+}
@@ -0,0 +1,116 @@
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// A single field that differs between two JSON values.
+#[derive(Debug, serde::Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// The path to the field, such as `method.steps[2].title`.
+    pub path: String,
+    /// The value before the change. `null` if the field was added.
+    pub before: Value,
+    /// The value after the change. `null` if the field was removed.
+    pub after: Value,
+}
+
+/// Compares two JSON values field by field, returning every field that differs.
+///
+/// Objects are compared key by key and arrays are compared index by index,
+/// so a change to a single substep is reported as that substep rather than
+/// the entire method. Items added to or removed from the end of an array are
+/// reported with a `null` before or after value.
+pub fn diff(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = vec![];
+    diff_into(String::new(), before, after, &mut changes);
+    changes
+}
+
+/// Recursively compares `before` and `after`, adding differences to `changes`.
+fn diff_into(path: String, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let keys = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_into(
+                    path,
+                    before.get(key).unwrap_or(&Value::Null),
+                    after.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for i in 0..before.len().max(after.len()) {
+                diff_into(
+                    format!("{}[{}]", path, i),
+                    before.get(i).unwrap_or(&Value::Null),
+                    after.get(i).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (before, after) if before != after => changes.push(FieldChange {
+            path,
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn finds_nested_changes() {
+        let before = json!({
+            "title": "Toast",
+            "method": {"steps": [{"title": "Toast"}, {"title": "Butter"}]},
+            "removed": 1,
+        });
+        let after = json!({
+            "title": "Toast",
+            "method": {"steps": [{"title": "Toast"}, {"title": "Spread"}]},
+            "added": true,
+        });
+        let changes = diff(&before, &after);
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(paths, vec!["added", "method.steps[1].title", "removed"]);
+        assert_eq!(changes[0].before, Value::Null);
+        assert_eq!(changes[1].after, json!("Spread"));
+        assert_eq!(changes[2].after, Value::Null);
+        assert!(diff(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn pads_arrays() {
+        let changes = diff(&json!({"a": [1]}), &json!({"a": [1, 2, 3]}));
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    path: "a[1]".to_string(),
+                    before: Value::Null,
+                    after: json!(2),
+                },
+                FieldChange {
+                    path: "a[2]".to_string(),
+                    before: Value::Null,
+                    after: json!(3),
+                },
+            ]
+        );
+
+        // A value that changes type is reported whole.
+        let changes = diff(&json!({"a": [1]}), &json!({"a": "one"}));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before, json!([1]));
+    }
+}
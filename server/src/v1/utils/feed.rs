@@ -0,0 +1,307 @@
+use crate::v1::types::database::Recipe;
+use crate::v1::types::Date;
+use std::fmt::Write;
+
+/// The title of the recipe feeds.
+const FEED_TITLE: &str = "Nutriblocks Recipes";
+/// The description of the recipe feeds.
+const FEED_DESCRIPTION: &str = "New kids' recipes from Nutriblocks, and the recipe of the week.";
+
+/// A single entry in a feed of recipes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedItem {
+    /// A unique, permanent identifier for the entry.
+    pub id: String,
+    /// The title of the entry.
+    pub title: String,
+    /// The URL of the recipe on the website.
+    pub link: String,
+    /// The date the entry was published.
+    pub published: Date,
+    /// A plain text summary of the recipe.
+    pub summary: String,
+}
+
+/// Gets the URL of the website the recipes are shown on, which every link
+/// to a recipe points to.
+pub fn site_url() -> Result<String, String> {
+    crate::envvar!(SITE_URL)
+}
+
+/// Returns the URL of a recipe on the website.
+pub fn recipe_url(site_url: &str, short: &str) -> String {
+    format!("{}/recipe/{}", site_url.trim_end_matches('/'), short)
+}
+
+/// Constructs the entries of a feed, newest first.
+///
+/// Every recipe gets an entry for when it was released, and the weekly
+/// recipe gets another for when it became the weekly recipe. Only public
+/// recipes are ever included, even if others are given.
+pub fn feed_items(site_url: &str, recipes: &[Recipe], weekly: Option<&Recipe>) -> Vec<FeedItem> {
+    let mut items: Vec<FeedItem> = recipes
+        .iter()
+        .filter(|recipe| is_published(recipe))
+        .map(|recipe| FeedItem {
+            id: format!("urn:uuid:{}", recipe.uuid),
+            title: recipe.title.clone(),
+            link: recipe_url(site_url, &recipe.short),
+            published: recipe.becomes_public,
+            summary: summary(recipe),
+        })
+        .collect();
+
+    if let Some(weekly) = weekly.filter(|weekly| is_published(weekly)) {
+        if let Some(timestamp) = weekly.weekly_timestamp {
+            let link = recipe_url(site_url, &weekly.short);
+            items.push(FeedItem {
+                id: format!("{}#weekly-{}", link, timestamp.ms()),
+                title: format!("Recipe of the week: {}", weekly.title),
+                link,
+                published: timestamp,
+                summary: summary(weekly),
+            });
+        }
+    }
+
+    // Newest first. Entries published at the same time are ordered by ID so
+    // the feed does not change between requests.
+    items.sort_by(|a, b| b.published.cmp(&a.published).then_with(|| a.id.cmp(&b.id)));
+    items
+}
+
+/// Renders the entries as an RSS 2.0 feed.
+pub fn render_rss(site_url: &str, feed_url: &str, items: &[FeedItem]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">"#);
+    xml.push_str("<channel>");
+    let _ = write!(
+        xml,
+        "<title>{}</title><link>{}</link><description>{}</description>",
+        escape_xml(FEED_TITLE),
+        escape_xml(site_url),
+        escape_xml(FEED_DESCRIPTION),
+    );
+    let _ = write!(
+        xml,
+        r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape_xml(feed_url),
+    );
+    if let Some(newest) = items.first() {
+        let _ = write!(
+            xml,
+            "<lastBuildDate>{}</lastBuildDate>",
+            newest.published.to_rfc2822()
+        );
+    }
+    for item in items {
+        let _ = write!(
+            xml,
+            concat!(
+                "<item><title>{}</title><link>{}</link>",
+                r#"<guid isPermaLink="false">{}</guid>"#,
+                "<pubDate>{}</pubDate><description>{}</description></item>",
+            ),
+            escape_xml(&item.title),
+            escape_xml(&item.link),
+            escape_xml(&item.id),
+            item.published.to_rfc2822(),
+            escape_xml(&item.summary),
+        );
+    }
+    xml.push_str("</channel></rss>\n");
+    xml
+}
+
+/// Renders the entries as an Atom feed.
+pub fn render_atom(site_url: &str, feed_url: &str, items: &[FeedItem]) -> String {
+    // Atom feeds must have an updated date, even when empty.
+    let updated = items
+        .first()
+        .map_or(Date::default(), |newest| newest.published);
+
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    let _ = write!(
+        xml,
+        concat!(
+            "<id>{}</id><title>{}</title><subtitle>{}</subtitle><updated>{}</updated>",
+            r#"<link href="{}"/><link href="{}" rel="self"/>"#,
+        ),
+        escape_xml(feed_url),
+        escape_xml(FEED_TITLE),
+        escape_xml(FEED_DESCRIPTION),
+        updated.to_rfc3339(),
+        escape_xml(site_url),
+        escape_xml(feed_url),
+    );
+    for item in items {
+        let _ = write!(
+            xml,
+            concat!(
+                "<entry><id>{}</id><title>{}</title><link href=\"{}\"/>",
+                "<published>{}</published><updated>{}</updated>",
+                "<summary>{}</summary></entry>",
+            ),
+            escape_xml(&item.id),
+            escape_xml(&item.title),
+            escape_xml(&item.link),
+            item.published.to_rfc3339(),
+            item.published.to_rfc3339(),
+            escape_xml(&item.summary),
+        );
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Renders an XML sitemap of the public recipes.
+pub fn render_sitemap(site_url: &str, recipes: &[Recipe]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for recipe in recipes.iter().filter(|recipe| is_published(recipe)) {
+        // Recipes written before they were released were last changed
+        // when they were released, as far as the public is concerned.
+        let last_modified = recipe
+            .last_modified
+            .map_or(recipe.becomes_public, |modified| {
+                modified.max(recipe.becomes_public)
+            });
+        let _ = write!(
+            xml,
+            "<url><loc>{}</loc><lastmod>{}</lastmod></url>",
+            escape_xml(&recipe_url(site_url, &recipe.short)),
+            last_modified.to_rfc3339(),
+        );
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Returns whether the recipe has been released and not deleted.
+fn is_published(recipe: &Recipe) -> bool {
+    recipe.deleted.is_none() && recipe.is_public()
+}
+
+/// Returns a plain text summary of a recipe for feed readers.
+fn summary(recipe: &Recipe) -> String {
+    format!(
+        "Serves {}. Ready in {} minutes.",
+        recipe.servings, recipe.time_to_cook
+    )
+}
+
+/// Escapes the characters that can not appear in XML text or attributes.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+
+    const SITE_URL: &str = "https://example.com/";
+    const FEED_URL: &str = "https://api.example.com/feed/rss.xml";
+
+    /// Returns a released recipe, an embargoed recipe that is not released
+    /// until next year and a deleted recipe.
+    fn recipes() -> Vec<Recipe> {
+        let next_year = Date::now().ms() + 365 * 24 * 60 * 60 * 1000;
+        vec![
+            fixture_recipe(|json| json["title"] = "Toast & jam".into()),
+            fixture_recipe(|json| {
+                json["_id"] = "0d5b1c7e-8f2a-4c3d-9e6f-1a2b3c4d5e6f".into();
+                json["short"] = "embargoed".into();
+                json["becomesPublic"] = next_year.into();
+            }),
+            fixture_recipe(|json| {
+                json["_id"] = "5f4e3d2c-1b0a-4987-8654-3210fedcba98".into();
+                json["short"] = "deleted".into();
+                json["deleted"] = 1656000000000u64.into();
+            }),
+        ]
+    }
+
+    #[test]
+    fn only_feeds_published_recipes() {
+        let recipes = recipes();
+        let items = feed_items(SITE_URL, &recipes, None);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].link, "https://example.com/recipe/toast");
+        assert_eq!(items[0].id, format!("urn:uuid:{}", recipes[0].uuid));
+
+        // An embargoed weekly recipe is not leaked either.
+        let weekly = fixture_recipe(|json| {
+            json["short"] = "embargoed".into();
+            json["becomesPublic"] = recipes[1].becomes_public.ms().into();
+            json["weeklyTimestamp"] = 1656000000000u64.into();
+        });
+        assert_eq!(feed_items(SITE_URL, &recipes, Some(&weekly)).len(), 1);
+    }
+
+    #[test]
+    fn feeds_weekly_recipe_newest_first() {
+        let weekly = fixture_recipe(|json| json["weeklyTimestamp"] = 1656000000000u64.into());
+        let items = feed_items(SITE_URL, &recipes(), Some(&weekly));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Recipe of the week: Toast");
+        assert_eq!(
+            items[0].id,
+            "https://example.com/recipe/toast#weekly-1656000000000"
+        );
+        assert_eq!(items[1].title, "Toast & jam");
+    }
+
+    #[test]
+    fn renders_rss() {
+        let items = feed_items(SITE_URL, &recipes(), None);
+        let rss = render_rss(SITE_URL, FEED_URL, &items);
+        assert!(rss.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(rss.contains(&format!(
+            r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+            FEED_URL
+        )));
+        assert!(rss.contains("<title>Toast &amp; jam</title>"));
+        assert!(rss.contains("<link>https://example.com/recipe/toast</link>"));
+        assert!(rss.contains(&format!(
+            "<pubDate>{}</pubDate>",
+            Date::new(1655000000000).to_rfc2822()
+        )));
+        assert_eq!(rss.matches("<item>").count(), 1);
+        assert!(!rss.contains("embargoed") && !rss.contains("deleted"));
+    }
+
+    #[test]
+    fn renders_sitemap() {
+        let sitemap = render_sitemap(SITE_URL, &recipes());
+        assert_eq!(
+            sitemap,
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "\n",
+                r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+                "<url><loc>https://example.com/recipe/toast</loc>",
+                "<lastmod>2022-06-12T02:13:20Z</lastmod></url>",
+                "</urlset>\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+
+/// The largest image that can be uploaded, in bytes.
+pub const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// The widest or tallest image that can be uploaded, in pixels.
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+/// The quality JPEG variants are encoded with, from 1 to 100.
+const JPEG_QUALITY: u8 = 85;
+/// The quality WebP variants are encoded with, from 1 to 100. Lower than
+/// JPEG, as WebP looks as good at a lower quality.
+const WEBP_QUALITY: u8 = 80;
+
+/// Any issue with an uploaded image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    /// The content type is not an image that can be uploaded.
+    UnsupportedType(String),
+    /// The image is over [`MAX_IMAGE_BYTES`].
+    TooLarge,
+    /// The image could not be read, or is not the type it claimed to be.
+    Invalid(String),
+}
+
+/// The format a variant of an image is encoded in.
+#[derive(Debug, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VariantFormat {
+    Jpeg,
+    Webp,
+}
+
+impl VariantFormat {
+    /// Returns the file extension of the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            VariantFormat::Jpeg => "jpg",
+            VariantFormat::Webp => "webp",
+        }
+    }
+
+    /// Returns the content type of the format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            VariantFormat::Jpeg => "image/jpeg",
+            VariantFormat::Webp => "image/webp",
+        }
+    }
+}
+
+/// A resized copy of an uploaded image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageVariant {
+    /// The width of the variant, in pixels.
+    pub width: u32,
+    /// The height of the variant, in pixels.
+    pub height: u32,
+    /// The format the variant is encoded in.
+    pub format: VariantFormat,
+    /// The encoded variant.
+    pub data: Vec<u8>,
+}
+
+/// Returns the format of an image that can be uploaded with
+/// `content_type`, which is either JPEG, PNG or WebP.
+pub fn upload_format(content_type: &str) -> Result<ImageFormat, ImageError> {
+    match content_type {
+        "image/jpeg" => Ok(ImageFormat::Jpeg),
+        "image/png" => Ok(ImageFormat::Png),
+        "image/webp" => Ok(ImageFormat::WebP),
+        _ => Err(ImageError::UnsupportedType(content_type.to_string())),
+    }
+}
+
+/// Reads an uploaded image of `format` and resizes it to each of `widths`,
+/// encoding every size as both JPEG and WebP.
+///
+/// Images are never enlarged, so widths wider than the image are replaced
+/// by the image's own width. Re-encoding also strips any metadata, such as
+/// where a photo was taken. This is slow, so should not be run on an async
+/// thread.
+pub fn resize_variants(
+    data: &[u8],
+    format: ImageFormat,
+    widths: &[u32],
+) -> Result<Vec<ImageVariant>, ImageError> {
+    if data.len() > MAX_IMAGE_BYTES {
+        return Err(ImageError::TooLarge);
+    }
+    match image::guess_format(data) {
+        Ok(actual) if actual == format => {}
+        _ => {
+            return Err(ImageError::Invalid(format!(
+                "The image is not a {}.",
+                format.extensions_str()[0].to_uppercase()
+            )))
+        }
+    }
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|e| ImageError::Invalid(e.to_string()))?;
+
+    let mut widths: Vec<u32> = widths
+        .iter()
+        .map(|&width| width.min(image.width()))
+        .collect();
+    widths.sort_unstable_by(|a, b| b.cmp(a));
+    widths.dedup();
+
+    let mut variants = Vec::with_capacity(widths.len() * 2);
+    for width in widths {
+        let resized = if width == image.width() {
+            image.clone()
+        } else {
+            // The height is only a bound, the aspect ratio is kept.
+            image.resize(width, u32::MAX, FilterType::CatmullRom)
+        };
+        for format in [VariantFormat::Jpeg, VariantFormat::Webp] {
+            variants.push(ImageVariant {
+                width: resized.width(),
+                height: resized.height(),
+                format,
+                data: encode(&resized, format)?,
+            });
+        }
+    }
+    Ok(variants)
+}
+
+/// Encodes an image in `format`.
+fn encode(image: &DynamicImage, format: VariantFormat) -> Result<Vec<u8>, ImageError> {
+    match format {
+        VariantFormat::Jpeg => {
+            // JPEG has no transparency.
+            let mut data = Vec::new();
+            DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY))
+                .map_err(|e| ImageError::Invalid(e.to_string()))?;
+            Ok(data)
+        }
+        // Lossy, as lossless photos are often larger than the upload.
+        VariantFormat::Webp if image.color().has_alpha() => {
+            let rgba = image.to_rgba8();
+            let encoder = webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height());
+            Ok(encoder.encode(WEBP_QUALITY as f32).to_vec())
+        }
+        VariantFormat::Webp => {
+            let rgb = image.to_rgb8();
+            let encoder = webp::Encoder::from_rgb(&rgb, rgb.width(), rgb.height());
+            Ok(encoder.encode(WEBP_QUALITY as f32).to_vec())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes a blank PNG of the given size.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        DynamicImage::new_rgba8(width, height)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn resizes_without_enlarging() {
+        let variants = resize_variants(&png(300, 150), ImageFormat::Png, &[800, 200]).unwrap();
+        let sizes: Vec<_> = variants
+            .iter()
+            .map(|variant| (variant.width, variant.height, variant.format))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (300, 150, VariantFormat::Jpeg),
+                (300, 150, VariantFormat::Webp),
+                (200, 100, VariantFormat::Jpeg),
+                (200, 100, VariantFormat::Webp),
+            ]
+        );
+        for variant in variants {
+            let format = image::guess_format(&variant.data).unwrap();
+            assert_eq!(format.to_mime_type(), variant.format.content_type());
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_content() {
+        assert!(matches!(
+            resize_variants(&png(10, 10), ImageFormat::Jpeg, &[10]),
+            Err(ImageError::Invalid(_))
+        ));
+        assert_eq!(
+            upload_format("image/gif"),
+            Err(ImageError::UnsupportedType("image/gif".to_string()))
+        );
+    }
+
+    #[test]
+    fn encodes_lossy_webp() {
+        let mut data = Vec::new();
+        DynamicImage::new_rgb8(64, 64)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        let variants = resize_variants(&data, ImageFormat::Png, &[64]).unwrap();
+        let webp = variants
+            .iter()
+            .find(|variant| variant.format == VariantFormat::Webp)
+            .unwrap();
+        // Lossy WebP is stored in a `VP8 ` chunk, and lossless in `VP8L`.
+        assert_eq!(&webp.data[12..16], b"VP8 ");
+        assert_eq!(image::load_from_memory(&webp.data).unwrap().width(), 64);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::v1::types::database::{Account, CookLogEntry, FamilyCook, PointsEntry, QuizAttempt};
+use crate::v1::types::{Date, QuizResult, Uuid};
+use crate::v1::utils::collection::*;
+use mongodb::bson::{doc, Bson};
+use mongodb::Client;
+
+/// Returns the account the learner with `learner` belongs to, if there is
+/// one.
+pub async fn learner_account(
+    client: &Client,
+    learner: Uuid,
+) -> Result<Option<Account>, mongodb::error::Error> {
+    client
+        .get_collection::<Account>(Collections::Accounts)
+        .find_one(doc! {"learners._id": learner}, None)
+        .await
+}
+
+/// Returns the number of points a learner has, which is the sum of every
+/// entry in their ledger.
+pub async fn points_balance(client: &Client, learner: Uuid) -> Result<i64, mongodb::error::Error> {
+    let mut cursor = client
+        .get_collection::<PointsEntry>(Collections::Points)
+        .aggregate(
+            vec![
+                doc! {"$match": {"learner": learner}},
+                doc! {"$group": {"_id": null, "balance": {"$sum": "$amount"}}},
+            ],
+            None,
+        )
+        .await?;
+
+    // There is no group if the learner has no entries.
+    if !cursor.advance().await? {
+        return Ok(0);
+    }
+    // The sum is only widened to 64 bits if it needs to be.
+    Ok(match cursor.deserialize_current()?.get("balance") {
+        Some(Bson::Int32(balance)) => *balance as i64,
+        Some(Bson::Int64(balance)) => *balance,
+        _ => 0,
+    })
+}
+
+/// Records a learner's graded attempt at a recipe's quiz, giving them the
+/// rewards it earned.
+///
+/// A learner can only earn each reward of a recipe once, so only the rewards
+/// no earlier attempt earned are given. The attempt is returned with the
+/// points it was given.
+pub async fn record_quiz_attempt(
+    client: &Client,
+    learner: Uuid,
+    recipe: Uuid,
+    weekly: Option<Date>,
+    result: &QuizResult,
+) -> Result<QuizAttempt, mongodb::error::Error> {
+    let mut attempt = QuizAttempt::new(learner, recipe, weekly, result);
+
+    // The unique index on the ledger rejects a reward that was already
+    // earned, even if two attempts are graded at once.
+    let points = client.get_collection::<PointsEntry>(Collections::Points);
+    let mut earned = 0;
+    for (reward, amount) in result.rewards() {
+        let entry = PointsEntry::quiz(&attempt, reward, amount);
+        match points.insert_one(&entry, None).await {
+            Ok(_) => earned += amount,
+            Err(e) if is_duplicate_key_error(&e) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    attempt.earned(earned);
+
+    client
+        .get_collection::<QuizAttempt>(Collections::QuizAttempts)
+        .insert_one(&attempt, None)
+        .await?;
+    Ok(attempt)
+}
+
+/// Returns whether a learner has answered every question of a recipe's quiz
+/// correctly in a single attempt, which earns them every reward it has.
+pub async fn quiz_mastered(
+    client: &Client,
+    learner: Uuid,
+    recipe: Uuid,
+) -> Result<bool, mongodb::error::Error> {
+    let count = client
+        .get_collection::<QuizAttempt>(Collections::QuizAttempts)
+        .count_documents(
+            doc! {"learner": learner, "recipe": recipe, "allCorrect": true},
+            None,
+        )
+        .await?;
+    Ok(count > 0)
+}
+
+/// Records a time a learner cooked a recipe, marking that their family has
+/// cooked it if it is the first time any of them did.
+pub async fn record_cook(
+    client: &Client,
+    entry: &CookLogEntry,
+) -> Result<(), mongodb::error::Error> {
+    client
+        .get_collection::<CookLogEntry>(Collections::CookLog)
+        .insert_one(entry, None)
+        .await?;
+
+    // The unique index on the marks stops a family being counted twice,
+    // even if two of their learners cook the recipe at once.
+    match client
+        .get_collection::<FamilyCook>(Collections::FamilyCooks)
+        .insert_one(FamilyCook::new(entry), None)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) if is_duplicate_key_error(&e) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the number of families that have cooked a recipe.
+///
+/// Each family has a single [`FamilyCook`] mark for the recipe, so the
+/// marks are counted rather than keeping a separate count that could
+/// disagree with them.
+pub async fn cook_count(client: &Client, recipe: Uuid) -> Result<u32, mongodb::error::Error> {
+    let count = client
+        .get_collection::<FamilyCook>(Collections::FamilyCooks)
+        .count_documents(doc! {"recipe": recipe}, None)
+        .await?;
+    Ok(count as u32)
+}
@@ -1,7 +1,34 @@
+pub mod account_token;
+pub mod achievements;
 pub mod auth_user;
+pub mod blob_store;
 pub mod collection;
+pub mod diff;
+pub mod feed;
+pub mod images;
+pub mod learners;
+pub mod pagination;
+pub mod preview;
+pub mod purge;
+pub mod related;
+pub mod revisions;
+pub mod slug;
+pub mod visibility;
 pub mod weekly;
 
+pub use account_token::*;
+pub use achievements::*;
 pub use auth_user::*;
+pub use blob_store::*;
 pub use collection::*;
+pub use feed::*;
+pub use images::*;
+pub use learners::*;
+pub use pagination::*;
+pub use preview::*;
+pub use purge::*;
+pub use related::*;
+pub use revisions::*;
+pub use slug::*;
+pub use visibility::*;
 pub use weekly::*;
@@ -0,0 +1,57 @@
+use mongodb::bson::Document;
+use mongodb::options::FindOptions;
+
+/// The most results a page can have.
+const MAX_PAGE_LIMIT: u8 = 20;
+
+/// The query parameters used to page through a list, the same way as a
+/// [`SearchRequest`].
+///
+/// [`SearchRequest`]: crate::v1::search::search::SearchRequest
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageQuery {
+    /// The amount of results per page. Defaults to 10.
+    #[serde(default = "page_limit_default")]
+    pub page_limit: u8,
+    /// The page number. Default to 1.
+    #[serde(default = "page_number_default")]
+    pub page_number: u32,
+}
+
+fn page_limit_default() -> u8 {
+    10
+}
+
+fn page_number_default() -> u32 {
+    1
+}
+
+impl PageQuery {
+    /// Ensures the page is within bounds.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.page_limit == 0 || self.page_limit > MAX_PAGE_LIMIT {
+            return Err(format!(
+                "Invalid `pageLimit`: Not within bounds. Please limit to between 1 and {}.",
+                MAX_PAGE_LIMIT
+            ));
+        }
+
+        if self.page_number == 0 {
+            return Err("Invalid `pageNumber`: Must be greater than 0.".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the options to find the page, sorted by `sort`.
+    pub fn find_options(&self, sort: Document) -> FindOptions {
+        FindOptions::builder()
+            .sort(sort)
+            .skip(Some(
+                (self.page_number as u64 - 1) * (self.page_limit as u64),
+            ))
+            .limit(Some(self.page_limit as i64))
+            .build()
+    }
+}
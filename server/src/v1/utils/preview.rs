@@ -0,0 +1,153 @@
+use crate::v1::types::{Date, Uuid};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The query parameters used to pass a preview token to a read endpoint.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct PreviewQuery {
+    /// A preview token created by [`PreviewToken::sign`].
+    pub preview: Option<String>,
+}
+
+/// A token that lets an editor view a single unreleased recipe before
+/// its `becomes_public` date.
+///
+/// Tokens are signed with the server's secret key and expire, so they can be
+/// shared with people who do not have the key. A token is written as
+/// `{expires}.{signature}`, where the signature covers both the recipe's UUID
+/// and the expiry date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewToken {
+    /// The recipe the token grants access to.
+    pub recipe: Uuid,
+    /// The date the token stops working.
+    pub expires: Date,
+}
+
+impl PreviewToken {
+    /// Constructs a new preview token for a recipe.
+    pub fn new(recipe: Uuid, expires: Date) -> Self {
+        Self { recipe, expires }
+    }
+
+    /// Signs the token, returning the string to give to the editor.
+    pub fn sign(&self) -> Result<String, String> {
+        let mac = self.mac()?.finalize().into_bytes();
+        Ok(format!("{}.{}", self.expires.ms(), hex::encode(mac)))
+    }
+
+    /// Checks that `token` is a valid, unexpired token for `recipe`.
+    pub fn verify(token: &str, recipe: Uuid) -> bool {
+        let (expires, signature) = match token.split_once('.') {
+            Some(parts) => parts,
+            None => return false,
+        };
+        let expires = match expires.parse::<u64>() {
+            Ok(expires) => Date::new(expires),
+            Err(_) => return false,
+        };
+        let signature = match hex::decode(signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        if expires < Date::now() {
+            return false;
+        }
+
+        match Self::new(recipe, expires).mac() {
+            Ok(mac) => mac.verify_slice(&signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Creates the MAC of the token's contents using the secret key.
+    fn mac(&self) -> Result<HmacSha256, String> {
+        let key = crate::envvar!(SECRET_KEY)?;
+        let mut mac = HmacSha256::new_from_slice(key.as_bytes())
+            .map_err(|e| format!("Invalid secret key: {}", e))?;
+        mac.update(format!("{}.{}", self.recipe, self.expires.ms()).as_bytes());
+        Ok(mac)
+    }
+}
+
+/// Sets the secret key that tokens are signed with, for tests that sign
+/// or verify them.
+#[cfg(test)]
+pub fn set_test_secret_key() {
+    static SET: std::sync::Once = std::sync::Once::new();
+    SET.call_once(|| std::env::set_var("SECRET_KEY", "test-secret-key"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a date `minutes` from now, or ago if negative.
+    fn in_minutes(minutes: i64) -> Date {
+        Date::new((Date::now().ms() as i64 + minutes * 60 * 1000) as u64)
+    }
+
+    #[test]
+    fn verifies_signed_tokens() {
+        set_test_secret_key();
+        let recipe = Uuid::random();
+        let token = PreviewToken::new(recipe, in_minutes(10)).sign().unwrap();
+        assert!(PreviewToken::verify(&token, recipe));
+    }
+
+    #[test]
+    fn rejects_expired_tokens() {
+        set_test_secret_key();
+        let recipe = Uuid::random();
+        let token = PreviewToken::new(recipe, in_minutes(-1)).sign().unwrap();
+        assert!(!PreviewToken::verify(&token, recipe));
+    }
+
+    #[test]
+    fn rejects_tokens_for_other_recipes() {
+        set_test_secret_key();
+        let token = PreviewToken::new(Uuid::random(), in_minutes(10))
+            .sign()
+            .unwrap();
+        assert!(!PreviewToken::verify(&token, Uuid::random()));
+    }
+
+    #[test]
+    fn rejects_tampered_tokens() {
+        set_test_secret_key();
+        let recipe = Uuid::random();
+        let token = PreviewToken::new(recipe, in_minutes(10)).sign().unwrap();
+        let (expires, signature) = token.split_once('.').unwrap();
+
+        // A changed signature.
+        let last = match signature.ends_with('0') {
+            true => '1',
+            false => '0',
+        };
+        let tampered = format!("{}.{}{}", expires, &signature[..signature.len() - 1], last);
+        assert!(!PreviewToken::verify(&tampered, recipe));
+
+        // A later expiry with the original signature.
+        let extended = format!("{}.{}", in_minutes(60 * 24 * 365).ms(), signature);
+        assert!(!PreviewToken::verify(&extended, recipe));
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        set_test_secret_key();
+        let recipe = Uuid::random();
+        let expires = in_minutes(10).ms();
+        for token in [
+            String::new(),
+            "no-separator".to_string(),
+            format!("{}.", expires),
+            format!("{}.not-hex", expires),
+            format!("soon.{}", "ab".repeat(32)),
+            format!("-1.{}", "ab".repeat(32)),
+        ] {
+            assert!(!PreviewToken::verify(&token, recipe), "{:?}", token);
+        }
+    }
+}
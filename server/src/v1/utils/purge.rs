@@ -0,0 +1,201 @@
+use crate::v1::types::database::{Recipe, RecipeRevision, Upload};
+use crate::v1::types::{Date, Uuid};
+use crate::v1::utils::blob_store::BlobStore;
+use crate::v1::utils::collection::*;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::Client;
+use std::collections::HashSet;
+use tracing::info;
+
+/// The collections whose documents can contain the URL of an upload.
+const UPLOAD_USERS: [Collections; 5] = [
+    Collections::Recipes,
+    Collections::Revisions,
+    Collections::Cookbooks,
+    Collections::Achievements,
+    Collections::CookLog,
+];
+
+/// The number of milliseconds in a day.
+const DAY_MS: u64 = 1000 * 60 * 60 * 24;
+
+/// Permanently removes every recipe that was deleted more than
+/// `max_age_days` days ago, along with its revisions, returning the number of
+/// recipes removed.
+///
+/// Recipes deleted more recently are kept so they can still be restored.
+pub async fn purge_deleted_recipes(client: &Client, max_age_days: u64) -> Result<u64, String> {
+    let cutoff = Date::now().ms().saturating_sub(max_age_days * DAY_MS);
+    let filter = doc! {"deleted": { "$lt": cutoff as i64 }};
+
+    let db = client.get_collection::<Recipe>(Collections::Recipes);
+    let mut cursor = db
+        .find(filter.clone(), None)
+        .await
+        .map_err(|e| format!("Could not find deleted recipes: {}", e))?;
+    let mut uuids = vec![];
+    while cursor
+        .advance()
+        .await
+        .map_err(|e| format!("Could not find deleted recipes: {}", e))?
+    {
+        let recipe = cursor
+            .deserialize_current()
+            .map_err(|e| format!("Could not read deleted recipe: {}", e))?;
+        uuids.push(recipe.uuid);
+    }
+
+    // The revisions are removed first, as they hold the full content of the
+    // recipe. If removing the recipes then fails, they are still deleted and
+    // are removed by the next purge.
+    let revisions = client
+        .get_collection::<RecipeRevision>(Collections::Revisions)
+        .delete_many(doc! {"recipe": {"$in": uuids.clone()}}, None)
+        .await
+        .map_err(|e| format!("Could not purge revisions of deleted recipes: {}", e))?;
+
+    let mut filter = filter;
+    filter.insert("_id", doc! {"$in": uuids});
+    let result = db
+        .delete_many(filter, None)
+        .await
+        .map_err(|e| format!("Could not purge deleted recipes: {}", e))?;
+
+    info!(
+        "Purged {} recipes deleted more than {} days ago, and their {} revisions.",
+        result.deleted_count, max_age_days, revisions.deleted_count
+    );
+    Ok(result.deleted_count)
+}
+
+/// Permanently removes every image that was uploaded more than
+/// `max_age_days` days ago and is not used by anything, returning the number
+/// of images removed.
+///
+/// An image is used if any string in a recipe, revision, cookbook,
+/// achievement or cook log entry contains its `images/{uuid}/` path, so
+/// images are kept as long as a revision that used them can be rolled back
+/// to. Newer images are kept, as they may be about to be used.
+pub async fn purge_unattached_uploads(
+    client: &Client,
+    blob_store: &dyn BlobStore,
+    max_age_days: u64,
+) -> Result<u64, String> {
+    let cutoff = Date::now().ms().saturating_sub(max_age_days * DAY_MS);
+
+    // Find every image that is used. Read the uploads after, so an image
+    // uploaded and used in between is never seen as unused.
+    let mut used = HashSet::new();
+    for collection in UPLOAD_USERS {
+        let name = collection.name();
+        let mut cursor = client
+            .get_collection::<Document>(collection)
+            .find(doc! {}, None)
+            .await
+            .map_err(|e| format!("Could not find {}: {}", name, e))?;
+        while cursor
+            .advance()
+            .await
+            .map_err(|e| format!("Could not find {}: {}", name, e))?
+        {
+            let document = cursor
+                .deserialize_current()
+                .map_err(|e| format!("Could not read {}: {}", name, e))?;
+            used_uploads(&Bson::Document(document), &mut used);
+        }
+    }
+
+    let db = client.get_collection::<Upload>(Collections::Uploads);
+    let mut cursor = db
+        .find(doc! {"date": { "$lt": cutoff as i64 }}, None)
+        .await
+        .map_err(|e| format!("Could not find uploads: {}", e))?;
+    let mut unused = vec![];
+    while cursor
+        .advance()
+        .await
+        .map_err(|e| format!("Could not find uploads: {}", e))?
+    {
+        let upload = cursor
+            .deserialize_current()
+            .map_err(|e| format!("Could not read upload: {}", e))?;
+        if !used.contains(&u128::from(upload.uuid)) {
+            unused.push(upload);
+        }
+    }
+
+    // The files are removed first, so if that fails the upload is still
+    // recorded and is removed by the next purge.
+    let mut purged = 0;
+    for upload in unused {
+        for key in &upload.keys {
+            blob_store.delete(key).await?;
+        }
+        db.delete_one(doc! {"_id": upload.uuid}, None)
+            .await
+            .map_err(|e| format!("Could not purge upload {}: {}", upload.uuid, e))?;
+        purged += 1;
+    }
+
+    info!(
+        "Purged {} unused images uploaded more than {} days ago.",
+        purged, max_age_days
+    );
+    Ok(purged)
+}
+
+/// Adds the UUID of every image whose `images/{uuid}/` path is in a string
+/// anywhere in `value` to `used`.
+fn used_uploads(value: &Bson, used: &mut HashSet<u128>) {
+    match value {
+        Bson::String(text) => {
+            for (start, _) in text.match_indices("images/") {
+                let rest = &text[start + "images/".len()..];
+                if let Some(uuid) = rest
+                    .split('/')
+                    .next()
+                    .and_then(|id| Uuid::from_str(id).ok())
+                {
+                    used.insert(u128::from(uuid));
+                }
+            }
+        }
+        Bson::Document(document) => {
+            for value in document.values() {
+                used_uploads(value, used);
+            }
+        }
+        Bson::Array(values) => {
+            for value in values {
+                used_uploads(value, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_used_uploads_anywhere() {
+        let image = "7a3e9c42-5b1d-4f08-8c6e-2d9b0a4f1e37";
+        let photo = "0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f";
+        let recipe = doc! {
+            "image": format!("https://cdn.example.com/images/{}/1600.jpg", image),
+            "quiz": {"questions": [{"options": [
+                {"image": format!("/uploads/images/{}/400.webp", photo)},
+            ]}]},
+            "title": "Not an image: images/toast/",
+            "servings": 2,
+        };
+        let mut used = HashSet::new();
+        used_uploads(&Bson::Document(recipe), &mut used);
+        let expected: HashSet<u128> = [image, photo]
+            .iter()
+            .map(|uuid| u128::from(Uuid::from_str(uuid).unwrap()))
+            .collect();
+        assert_eq!(used, expected);
+    }
+}
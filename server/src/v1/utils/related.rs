@@ -0,0 +1,230 @@
+use crate::v1::types::database::Recipe;
+use crate::v1::types::{Ingredient, Nutrient, Uuid};
+use mongodb::bson::{doc, Document};
+use std::collections::BTreeSet;
+
+/// How much sharing nutrient tags counts towards two recipes being related.
+const NUTRIENT_WEIGHT: f64 = 3.0;
+/// How much sharing ingredients counts towards two recipes being related.
+const INGREDIENT_WEIGHT: f64 = 4.0;
+/// How much having similar titles counts towards two recipes being related.
+const TITLE_WEIGHT: f64 = 2.0;
+/// How much taking a similar time to cook counts towards two recipes
+/// being related.
+const TIME_WEIGHT: f64 = 1.0;
+
+/// Words in titles too common to say anything about a recipe.
+const TITLE_STOP_WORDS: &[&str] = &["and", "the", "with", "for", "easy", "quick"];
+
+/// The parts of a recipe used to rank how related it is to others.
+///
+/// Every public recipe is a candidate, so only these fields are read from
+/// the database, using [`RelatedRecipe::projection`].
+#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedRecipe {
+    /// The unique identifier of the recipe.
+    #[serde(rename = "_id")]
+    pub uuid: Uuid,
+    /// The title of the recipe.
+    pub title: String,
+    /// The nutrients the recipe is tagged with.
+    pub nutrients: Vec<Nutrient>,
+    /// The time it takes to cook the recipe, in minutes.
+    pub time_to_cook: u16,
+    /// The ingredients of the recipe.
+    pub ingredients: Vec<Ingredient>,
+}
+
+impl RelatedRecipe {
+    /// Returns the projection that only reads the fields of a
+    /// [`RelatedRecipe`].
+    pub fn projection() -> Document {
+        doc! {"title": 1, "nutrients": 1, "timeToCook": 1, "ingredients": 1}
+    }
+}
+
+impl From<&Recipe> for RelatedRecipe {
+    fn from(recipe: &Recipe) -> Self {
+        Self {
+            uuid: recipe.uuid,
+            title: recipe.title.clone(),
+            nutrients: recipe.nutrients.clone(),
+            time_to_cook: recipe.time_to_cook,
+            ingredients: recipe.ingredients.clone(),
+        }
+    }
+}
+
+/// Scores how related `candidate` is to `source`, from 0 to 10.
+///
+/// Recipes are more related the more nutrient tags and ingredients they
+/// share, the more similar their titles are, and the closer their times
+/// to cook are.
+pub fn relatedness(source: &RelatedRecipe, candidate: &RelatedRecipe) -> f64 {
+    let nutrients = overlap(
+        &source.nutrients.iter().map(|&n| u16::from(n)).collect(),
+        &candidate.nutrients.iter().map(|&n| u16::from(n)).collect(),
+    );
+    let ingredients = overlap(&ingredient_names(source), &ingredient_names(candidate));
+    let title = overlap(&title_words(&source.title), &title_words(&candidate.title));
+    let time = match source.time_to_cook.max(candidate.time_to_cook) {
+        0 => 1.0,
+        longest => {
+            1.0 - source.time_to_cook.abs_diff(candidate.time_to_cook) as f64 / longest as f64
+        }
+    };
+
+    nutrients * NUTRIENT_WEIGHT
+        + ingredients * INGREDIENT_WEIGHT
+        + title * TITLE_WEIGHT
+        + time * TIME_WEIGHT
+}
+
+/// Ranks `candidates` by how related they are to `source`, returning at
+/// most `limit` of them, most related first.
+///
+/// `source` itself is never returned. Recipes that are equally related are
+/// ordered by UUID, so the same recipes always give the same ranking.
+pub fn rank_related<'a>(
+    source: &RelatedRecipe,
+    candidates: &'a [RelatedRecipe],
+    limit: usize,
+) -> Vec<&'a RelatedRecipe> {
+    let mut scored: Vec<(f64, &RelatedRecipe)> = candidates
+        .iter()
+        .filter(|candidate| candidate.uuid != source.uuid)
+        .map(|candidate| (relatedness(source, candidate), candidate))
+        .collect();
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .total_cmp(a_score)
+            .then_with(|| u128::from(a.uuid).cmp(&u128::from(b.uuid)))
+    });
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, recipe)| recipe)
+        .collect()
+}
+
+/// Returns the proportion of items that are in both sets, out of the items
+/// in either set. 0 if both are empty.
+fn overlap<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> f64 {
+    match a.union(b).count() {
+        0 => 0.0,
+        total => a.intersection(b).count() as f64 / total as f64,
+    }
+}
+
+/// Returns the names of a recipe's ingredients, in lowercase and singular,
+/// so `Tomatoes` and `tomato` count as the same ingredient.
+fn ingredient_names(recipe: &RelatedRecipe) -> BTreeSet<String> {
+    recipe
+        .ingredients
+        .iter()
+        .map(|ingredient| singular(&ingredient.name.to_lowercase()).to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Returns the meaningful words in a title, in lowercase and singular.
+fn title_words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|word| word.to_lowercase())
+        .filter(|word| word.chars().count() > 2 && !TITLE_STOP_WORDS.contains(&word.as_str()))
+        .map(|word| singular(&word).to_string())
+        .collect()
+}
+
+/// Roughly removes the plural from the end of a word or name.
+fn singular(word: &str) -> &str {
+    if let Some(stem) = word.strip_suffix("oes") {
+        if !stem.is_empty() {
+            return &word[..word.len() - 2];
+        }
+    }
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem,
+        _ => word,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(uuid: u128, title: &str, nutrients: &[&str], ingredients: &[&str]) -> RelatedRecipe {
+        RelatedRecipe {
+            uuid: Uuid::from(uuid),
+            title: title.to_string(),
+            nutrients: nutrients
+                .iter()
+                .map(|name| Nutrient::from_str(name).unwrap())
+                .collect(),
+            time_to_cook: 30,
+            ingredients: ingredients.iter().map(Ingredient::parse).collect(),
+        }
+    }
+
+    fn uuids(ranked: Vec<&RelatedRecipe>) -> Vec<u128> {
+        ranked
+            .iter()
+            .map(|recipe| u128::from(recipe.uuid))
+            .collect()
+    }
+
+    #[test]
+    fn removes_plurals() {
+        assert_eq!(singular("tomatoes"), "tomato");
+        assert_eq!(singular("potatoes"), "potato");
+        assert_eq!(singular("carrots"), "carrot");
+        assert_eq!(singular("eggs"), "egg");
+        // Words that only look plural are left alone.
+        assert_eq!(singular("glass"), "glass");
+        assert_eq!(singular("gas"), "gas");
+        assert_eq!(singular("oes"), "oes");
+    }
+
+    #[test]
+    fn ignores_common_title_words() {
+        let words = title_words("Quick Tomato and Basil Pasta with Eggs");
+        let expected = ["basil", "egg", "pasta", "tomato"];
+        assert_eq!(words, expected.iter().map(|w| w.to_string()).collect());
+    }
+
+    #[test]
+    fn ranks_most_related_first() {
+        let source = recipe(1, "Tomato Soup", &["Vitamin C"], &["4 tomatoes", "1 onion"]);
+        let candidates = [
+            recipe(2, "Chocolate Cake", &["Sugar"], &["200g chocolate"]),
+            recipe(3, "Tomato Pasta", &["Vitamin C"], &["2 tomatoes", "pasta"]),
+            recipe(
+                4,
+                "Roast Tomatoes",
+                &["Vitamin C"],
+                &["1 tomato", "1 onion"],
+            ),
+        ];
+        assert_eq!(uuids(rank_related(&source, &candidates, 6)), [4, 3, 2]);
+        assert_eq!(uuids(rank_related(&source, &candidates, 1)), [4]);
+    }
+
+    #[test]
+    fn never_ranks_source() {
+        let source = recipe(1, "Tomato Soup", &[], &["4 tomatoes"]);
+        let candidates = [source.clone(), recipe(2, "Toast", &[], &["bread"])];
+        assert_eq!(uuids(rank_related(&source, &candidates, 6)), [2]);
+    }
+
+    #[test]
+    fn orders_ties_by_uuid() {
+        let source = recipe(1, "Tomato Soup", &[], &["4 tomatoes"]);
+        let candidates = [
+            recipe(3, "Toast", &[], &["bread"]),
+            recipe(2, "Toast", &[], &["bread"]),
+        ];
+        assert_eq!(uuids(rank_related(&source, &candidates, 6)), [2, 3]);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::v1::types::database::{Recipe, RecipeRevision};
+use crate::v1::types::Uuid;
+use crate::v1::utils::collection::*;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::Client;
+
+/// Returns the filter matching a recipe only if it is still at `revision`,
+/// so a write based on an outdated copy of it matches nothing.
+///
+/// Recipes written before revisions were tracked have no revision field,
+/// which is read as revision 0, so revision 0 also matches a missing field.
+pub fn revision_filter(recipe: Uuid, revision: u32) -> Document {
+    if revision == 0 {
+        doc! {"_id": recipe, "revision": {"$in": [0, Bson::Null]}}
+    } else {
+        doc! {"_id": recipe, "revision": revision as i64}
+    }
+}
+
+/// Stores the current state of a recipe in the revisions collection, so it
+/// can be viewed and rolled back to after the recipe is overwritten.
+///
+/// Should be called *before* the recipe is overwritten. Archiving the same
+/// revision twice is not an error, as the write may have been retried.
+pub async fn archive_revision(
+    client: &Client,
+    recipe: Recipe,
+) -> Result<(), mongodb::error::Error> {
+    let db = client.get_collection::<RecipeRevision>(Collections::Revisions);
+    match db.insert_one(RecipeRevision::new(recipe), None).await {
+        Err(e) if !is_duplicate_key_error(&e) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+
+    #[test]
+    fn matches_untracked_revisions() {
+        let recipe = Uuid::random();
+        assert_eq!(
+            revision_filter(recipe, 0),
+            doc! {"_id": recipe, "revision": {"$in": [0, Bson::Null]}}
+        );
+        assert_eq!(
+            revision_filter(recipe, 3),
+            doc! {"_id": recipe, "revision": 3_i64}
+        );
+
+        // A recipe stored before revisions were tracked is read as revision
+        // 0, which the filter for revision 0 must match.
+        let legacy = fixture_recipe(|json| {
+            json.as_object_mut().unwrap().remove("revision");
+        });
+        assert_eq!(legacy.revision, 0);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::v1::types::Uuid;
+use crate::v1::utils::collection::*;
+use mongodb::bson::{doc, Document};
+use mongodb::options::FindOptions;
+use mongodb::Client;
+use std::collections::HashSet;
+
+/// Finds a short for a recipe that is not used by any other recipe.
+///
+/// If `short` is taken, a number is appended to it, starting at 2.
+/// For example, a second "Banana Bread" recipe would get `banana-bread-2`.
+/// Shorts that other recipes *used* to have are also avoided, so old links
+/// keep pointing at the recipe they were made for.
+pub async fn unique_short(
+    client: &Client,
+    uuid: Uuid,
+    short: &str,
+) -> Result<String, mongodb::error::Error> {
+    // Find every short that could collide with the candidates.
+    let pattern = candidates_pattern(short);
+    let find_options = FindOptions::builder()
+        .projection(doc! { "short": 1, "previousShorts": 1 })
+        .build();
+    let mut cursor = client
+        .get_collection::<Document>(Collections::Recipes)
+        .find(
+            doc! {
+                "_id": { "$ne": uuid },
+                "$or": [
+                    { "short": { "$regex": &pattern } },
+                    { "previousShorts": { "$regex": &pattern } },
+                ],
+            },
+            find_options,
+        )
+        .await?;
+
+    let mut taken = HashSet::new();
+    while cursor.advance().await? {
+        add_taken_shorts(&cursor.deserialize_current()?, &mut taken);
+    }
+    Ok(first_free_short(short, &taken))
+}
+
+/// Returns a regex matching `short` and every numbered candidate for it,
+/// such as `banana-bread-2`.
+fn candidates_pattern(short: &str) -> String {
+    format!("^{}(-[0-9]+)?$", escape_regex(short))
+}
+
+/// Adds the short and previous shorts of a recipe document to `taken`.
+fn add_taken_shorts(document: &Document, taken: &mut HashSet<String>) {
+    if let Ok(short) = document.get_str("short") {
+        taken.insert(short.to_string());
+    }
+    if let Ok(previous_shorts) = document.get_array("previousShorts") {
+        taken.extend(
+            previous_shorts
+                .iter()
+                .filter_map(|short| short.as_str())
+                .map(str::to_string),
+        );
+    }
+}
+
+/// Returns `short` if it is not taken, or else the first numbered candidate
+/// for it that is not.
+fn first_free_short(short: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(short) {
+        return short.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", short, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("There are infinitely many candidates")
+}
+
+/// Escapes every character that has a special meaning in a regex.
+fn escape_regex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if !c.is_alphanumeric() && c != '-' && c != '_' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the shorts taken by recipe documents.
+    fn taken(documents: &[Document]) -> HashSet<String> {
+        let mut taken = HashSet::new();
+        for document in documents {
+            add_taken_shorts(document, &mut taken);
+        }
+        taken
+    }
+
+    #[test]
+    fn keeps_free_shorts() {
+        let taken = taken(&[doc! {"short": "toast-2"}]);
+        assert_eq!(first_free_short("toast", &taken), "toast");
+    }
+
+    #[test]
+    fn numbers_taken_shorts() {
+        let taken = taken(&[doc! {"short": "toast"}, doc! {"short": "toast-2"}]);
+        assert_eq!(first_free_short("toast", &taken), "toast-3");
+    }
+
+    #[test]
+    fn avoids_previous_shorts() {
+        let taken = taken(&[doc! {"short": "jam-toast", "previousShorts": ["toast"]}]);
+        assert!(taken.contains("jam-toast"));
+        assert_eq!(first_free_short("toast", &taken), "toast-2");
+    }
+
+    #[test]
+    fn escapes_regex_characters() {
+        assert_eq!(
+            candidates_pattern("banana-bread"),
+            "^banana-bread(-[0-9]+)?$"
+        );
+        assert_eq!(candidates_pattern("c++"), r"^c\+\+(-[0-9]+)?$");
+        assert_eq!(candidates_pattern("a.b(c)"), r"^a\.b\(c\)(-[0-9]+)?$");
+    }
+}
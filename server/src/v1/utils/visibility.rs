@@ -0,0 +1,91 @@
+use crate::v1::types::database::Recipe;
+use crate::v1::types::Date;
+use crate::v1::utils::preview::PreviewToken;
+use mongodb::bson::{doc, Document};
+
+/// Restricts a recipe query to only the recipes that can be shown to the
+/// public. That is, recipes that have not been deleted and whose
+/// `becomes_public` date has passed.
+///
+/// Every read path that returns more than a single recipe should use this.
+pub fn only_public(mut filter: Document) -> Document {
+    filter.insert("deleted", mongodb::bson::Bson::Null);
+    // Not implemented for u64 but *is* implemented for i64,
+    // hence the conversion here.
+    filter.insert("becomesPublic", doc! { "$lte": Date::now().ms() as i64 });
+    filter
+}
+
+/// Returns whether a single recipe may be returned to the client.
+///
+/// Public recipes can always be viewed. Unreleased recipes can only be viewed
+/// with a valid preview token for that recipe. Deleted recipes can never
+/// be viewed.
+pub fn can_view(recipe: &Recipe, preview: Option<&str>) -> bool {
+    if recipe.deleted.is_some() {
+        return false;
+    }
+    if recipe.is_public() {
+        return true;
+    }
+    match preview {
+        Some(token) => PreviewToken::verify(token, recipe.uuid),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::types::database::fixture_recipe;
+    use crate::v1::utils::preview::set_test_secret_key;
+
+    /// Returns a preview token for `recipe` that expires in an hour.
+    fn preview(recipe: &Recipe) -> String {
+        set_test_secret_key();
+        let expires = Date::new(Date::now().ms() + 60 * 60 * 1000);
+        PreviewToken::new(recipe.uuid, expires).sign().unwrap()
+    }
+
+    /// Returns a recipe that is released a year from now.
+    fn unreleased() -> Recipe {
+        let next_year = Date::now().ms() + 365 * 24 * 60 * 60 * 1000;
+        fixture_recipe(|json| json["becomesPublic"] = next_year.into())
+    }
+
+    #[test]
+    fn shows_public_recipes() {
+        let recipe = fixture_recipe(|_| {});
+        assert!(can_view(&recipe, None));
+        assert!(can_view(&recipe, Some("not-a-token")));
+    }
+
+    #[test]
+    fn shows_unreleased_recipes_only_with_their_token() {
+        let recipe = unreleased();
+        assert!(!can_view(&recipe, None));
+        assert!(!can_view(&recipe, Some("not-a-token")));
+        assert!(can_view(&recipe, Some(&preview(&recipe))));
+
+        let other = fixture_recipe(|json| {
+            json["_id"] = "0f6b1d2e-3c4a-4b5d-8e6f-7a8b9c0d1e2f".into();
+        });
+        assert!(!can_view(&recipe, Some(&preview(&other))));
+    }
+
+    #[test]
+    fn hides_deleted_recipes_even_with_a_token() {
+        let deleted = |becomes_public: u64| {
+            fixture_recipe(|json| {
+                json["becomesPublic"] = becomes_public.into();
+                json["deleted"] = 1656000000000u64.into();
+            })
+        };
+        let recipe = deleted(1655000000000);
+        assert!(!can_view(&recipe, None));
+        assert!(!can_view(&recipe, Some(&preview(&recipe))));
+
+        let recipe = deleted(unreleased().becomes_public.ms());
+        assert!(!can_view(&recipe, Some(&preview(&recipe))));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::v1::types::database::Recipe;
 use crate::v1::types::Date;
 use crate::v1::utils::collection::*;
+use crate::v1::utils::visibility::only_public;
 use mongodb::options::FindOneOptions;
 use mongodb::{bson::doc, Client};
 use std::sync::RwLock;
@@ -63,6 +64,17 @@ impl WeeklyRecipeGetter {
         Err("Weekly recipe was not found.".to_string())
     }
 
+    /// Expires the cache, so the weekly recipe is retrieved from the database
+    /// on the next call to [`WeeklyRecipeGetter::get`].
+    ///
+    /// Should be called whenever a change is made that could affect which
+    /// recipe is the weekly recipe.
+    pub fn invalidate(&self) {
+        if let Ok(mut last_checked) = self.last_checked.write() {
+            *last_checked = Date::default();
+        }
+    }
+
     /// Checks if the cache is expired.
     pub fn is_cache_expired(&self) -> bool {
         let last_checked = match self.last_checked.read() {
@@ -88,7 +100,7 @@ impl WeeklyRecipeGetter {
             db.find_one(
                 // Not implemented for u64 but *is* implemented for i64,
                 // hence the conversion here.
-                doc! {"weeklyTimestamp": { "$lt": Date::now().ms() as i64 }},
+                only_public(doc! {"weeklyTimestamp": { "$lt": Date::now().ms() as i64 }}),
                 find_options,
             )
             .await